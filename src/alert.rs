@@ -0,0 +1,112 @@
+use alloc::boxed::Box;
+use core::{fmt, time::Duration};
+use std::{
+    sync::{Mutex, PoisonError},
+    time::Instant,
+};
+
+/// Longest interval over which the miss rate is measured.
+const MAX_BUCKET: Duration = Duration::from_secs(1);
+
+/// A sustained burst of misses reported by a [`MissAlert`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MissSpike {
+    /// The miss rate of the last measured interval, in misses per second.
+    pub misses_per_sec: f64,
+    /// How long the miss rate has stayed above the limit.
+    pub sustained: Duration,
+}
+
+struct AlertState {
+    bucket_start: Instant,
+    misses: usize,
+    over_since: Option<Instant>,
+    fired: bool,
+}
+
+/// A callback fired when the misses of a pool exceed a rate for a sustained
+/// time.
+///
+/// Installed with `with_miss_alert` on [`crate::Pool`] and
+/// [`crate::LocalPool`]. The rate is measured on the miss path itself, over
+/// intervals of at most a second, so short spikes are caught without polling
+/// the stats. The callback fires once per spike, and again only after the
+/// rate has dropped back under the limit.
+///
+/// The callback runs on the thread whose get crossed the threshold. It may use
+/// the pool.
+pub struct MissAlert {
+    limit: f64,
+    sustain: Duration,
+    bucket: Duration,
+    callback: Box<dyn Fn(&MissSpike) + Send + Sync>,
+    state: Mutex<AlertState>,
+}
+
+impl MissAlert {
+    /// Creates an alert calling `callback` once the pool has missed more than
+    /// `misses_per_sec` times per second for at least `sustain`.
+    pub fn new(
+        misses_per_sec: usize,
+        sustain: Duration,
+        callback: impl Fn(&MissSpike) + Send + Sync + 'static,
+    ) -> Self {
+        MissAlert {
+            limit: misses_per_sec as f64,
+            sustain,
+            bucket: sustain.clamp(Duration::from_millis(1), MAX_BUCKET),
+            callback: Box::new(callback),
+            state: Mutex::new(AlertState {
+                bucket_start: Instant::now(),
+                misses: 0,
+                over_since: None,
+                fired: false,
+            }),
+        }
+    }
+
+    /// Records a miss, firing the callback if it completes a spike.
+    #[cold]
+    pub(crate) fn record(&self) {
+        let now = Instant::now();
+        let spike = {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            let elapsed = now.duration_since(state.bucket_start);
+            let mut spike = None;
+            if elapsed >= self.bucket {
+                let rate = state.misses as f64 / elapsed.as_secs_f64();
+                if rate > self.limit {
+                    let bucket_start = state.bucket_start;
+                    let since = *state.over_since.get_or_insert(bucket_start);
+                    let sustained = now.duration_since(since);
+                    if sustained >= self.sustain && !state.fired {
+                        state.fired = true;
+                        spike = Some(MissSpike {
+                            misses_per_sec: rate,
+                            sustained,
+                        });
+                    }
+                } else {
+                    state.over_since = None;
+                    state.fired = false;
+                }
+                state.bucket_start = now;
+                state.misses = 0;
+            }
+            state.misses += 1;
+            spike
+        };
+        if let Some(spike) = spike {
+            (self.callback)(&spike);
+        }
+    }
+}
+
+impl fmt::Debug for MissAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MissAlert")
+            .field("misses_per_sec", &self.limit)
+            .field("sustain", &self.sustain)
+            .finish_non_exhaustive()
+    }
+}
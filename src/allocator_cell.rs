@@ -0,0 +1,80 @@
+use crate::{
+    queue::Lock,
+    sync::{AtomicPtr, Ordering},
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, mem, ops::Deref, ptr};
+
+/// The allocator of a pool, which can be replaced through a shared reference.
+///
+/// The pool dereferences the cell on every get and return, so reading it is a
+/// single atomic load. Replacements are boxed and kept until the cell is
+/// borrowed exclusively or dropped, as other threads may still hold a
+/// reference to an allocator that was replaced.
+pub(crate) struct AllocatorCell<P> {
+    initial: P,
+    /// The latest replacement, or null while `initial` is in use.
+    current: AtomicPtr<P>,
+    replaced: Lock<Vec<Box<P>>>,
+}
+
+impl<P> AllocatorCell<P> {
+    pub(crate) fn new(allocator: P) -> Self {
+        AllocatorCell {
+            initial: allocator,
+            current: AtomicPtr::new(ptr::null_mut()),
+            replaced: Lock::new(Vec::new()),
+        }
+    }
+
+    /// Makes `allocator` the allocator of the cell, returning the allocator
+    /// it replaces.
+    pub(crate) fn replace(&self, allocator: P) -> &P {
+        let mut allocator = Box::new(allocator);
+        self.replaced.with(|replaced| {
+            let old = self.current.swap(&mut *allocator, Ordering::AcqRel);
+            replaced.push(allocator);
+            if old.is_null() {
+                &self.initial
+            } else {
+                // Safety: replacements are only freed when the cell is dropped.
+                unsafe { &*old }
+            }
+        })
+    }
+
+    /// Returns the current allocator, freeing the allocators it replaced,
+    /// which nothing can reference while the cell is exclusively borrowed.
+    pub(crate) fn get_mut(&mut self) -> &mut P {
+        if !self.current.load(Ordering::Relaxed).is_null() {
+            self.current.store(ptr::null_mut(), Ordering::Relaxed);
+            // The current allocator is the latest replacement.
+            let mut replaced = self.replaced.with(mem::take);
+            if let Some(current) = replaced.pop() {
+                self.initial = *current;
+            }
+        }
+        &mut self.initial
+    }
+}
+
+impl<P> Deref for AllocatorCell<P> {
+    type Target = P;
+
+    #[inline(always)]
+    fn deref(&self) -> &P {
+        let current = self.current.load(Ordering::Acquire);
+        if current.is_null() {
+            &self.initial
+        } else {
+            // Safety: replacements are only freed when the cell is dropped.
+            unsafe { &*current }
+        }
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for AllocatorCell<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,152 @@
+use crate::PoolObserver;
+use core::{fmt, future::Future};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+/// The [`PoolObserver`] hook that produced an [`ObserverEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObserverHook {
+    /// [`PoolObserver::on_allocate`].
+    Allocate,
+    /// [`PoolObserver::on_recycle`].
+    Recycle,
+    /// [`PoolObserver::on_discard`].
+    Discard,
+    /// [`PoolObserver::on_validation_failure`].
+    ValidationFailure,
+}
+
+/// An event queued by an [`AsyncObserver`], with the data captured from the
+/// object it is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObserverEvent<M> {
+    /// The hook that produced the event.
+    pub hook: ObserverHook,
+    /// The data captured from the object.
+    pub data: M,
+}
+
+/// A [`PoolObserver`] whose events are handled by an async task, for actions
+/// that need to await, such as notifying a remote quota service or flushing
+/// telemetry.
+///
+/// Hooks run inline on the thread that triggered the event, often while a
+/// guard is being dropped, so they cannot await. This observer only runs a
+/// cheap `capture` function in the hook, which copies what the handler needs
+/// out of the object, and queues the result. An [`ObserverWorker`] then
+/// awaits the async `handler` on each queued event, in order. The observer
+/// does not spawn the worker itself, so it works with any executor:
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use opool::{AsyncObserver, ObserverHook, Pool, PoolAllocator};
+///
+/// struct Alloc;
+///
+/// impl PoolAllocator<Vec<u8>> for Alloc {
+///     fn allocate(&self) -> Vec<u8> {
+///         Vec::new()
+///     }
+/// }
+///
+/// let (observer, worker) = AsyncObserver::new(
+///     |hook, buf: &Vec<u8>| (hook == ObserverHook::Recycle).then(|| buf.len()),
+///     |event| async move {
+///         tokio::task::yield_now().await;
+///         assert_eq!(event.data, 1);
+///     },
+/// );
+/// let worker = tokio::spawn(worker.run());
+/// let pool = Pool::new(8, Alloc).with_observer(observer);
+/// pool.get().push(1);
+/// drop(pool);
+/// worker.await.unwrap();
+/// # }
+/// ```
+///
+/// The queue is unbounded, so a hook never waits for the handler. Events
+/// raised after the worker is dropped are discarded.
+pub struct AsyncObserver<F, M> {
+    capture: F,
+    events: UnboundedSender<ObserverEvent<M>>,
+}
+
+impl<F, M> AsyncObserver<F, M> {
+    /// Creates a new AsyncObserver and the worker that handles its events.
+    ///
+    /// `capture` is called in every hook with the hook and the object, and
+    /// returns the data to queue for the event, or `None` to skip it.
+    pub fn new<H, Fut>(capture: F, handler: H) -> (Self, ObserverWorker<M, H>)
+    where
+        H: FnMut(ObserverEvent<M>) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let (events, queue) = unbounded_channel();
+        let worker = ObserverWorker { queue, handler };
+        (AsyncObserver { capture, events }, worker)
+    }
+
+    fn queue<T>(&self, hook: ObserverHook, obj: &T)
+    where
+        F: Fn(ObserverHook, &T) -> Option<M>,
+    {
+        if let Some(data) = (self.capture)(hook, obj) {
+            // The worker is gone, so nobody is interested in the event.
+            let _ = self.events.send(ObserverEvent { hook, data });
+        }
+    }
+}
+
+impl<T, F, M> PoolObserver<T> for AsyncObserver<F, M>
+where
+    F: Fn(ObserverHook, &T) -> Option<M>,
+{
+    fn on_allocate(&self, obj: &T) {
+        self.queue(ObserverHook::Allocate, obj);
+    }
+
+    fn on_recycle(&self, obj: &T) {
+        self.queue(ObserverHook::Recycle, obj);
+    }
+
+    fn on_discard(&self, obj: &T) {
+        self.queue(ObserverHook::Discard, obj);
+    }
+
+    fn on_validation_failure(&self, obj: &T) {
+        self.queue(ObserverHook::ValidationFailure, obj);
+    }
+}
+
+impl<F, M> fmt::Debug for AsyncObserver<F, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncObserver").finish_non_exhaustive()
+    }
+}
+
+/// The task handling the events of an [`AsyncObserver`].
+pub struct ObserverWorker<M, H> {
+    queue: UnboundedReceiver<ObserverEvent<M>>,
+    handler: H,
+}
+
+impl<M, H, Fut> ObserverWorker<M, H>
+where
+    H: FnMut(ObserverEvent<M>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    /// Handles events until the observer is dropped along with its pool.
+    pub async fn run(mut self) {
+        while let Some(event) = self.queue.recv().await {
+            (self.handler)(event).await;
+        }
+    }
+}
+
+impl<M, H> fmt::Debug for ObserverWorker<M, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObserverWorker")
+            .field("queued", &self.queue.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{Pool, PoolAllocator};
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    future::Future,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+/// A pool whose objects are cleaned up by an async task when they are
+/// returned, instead of synchronously in `Drop`.
+///
+/// Dropping an [`AsyncReturnGuard`] only queues its object. A
+/// [`ReturnWorker`] runs an async cleanup function on each queued object, such
+/// as flushing a connection, and puts the objects that survive it back into
+/// the pool. The pool does not spawn the worker itself, so it works with any
+/// executor:
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use opool::{AsyncReturnPool, Pool, PoolAllocator};
+///
+/// struct Alloc;
+///
+/// impl PoolAllocator<Vec<u8>> for Alloc {
+///     fn allocate(&self) -> Vec<u8> {
+///         Vec::new()
+///     }
+/// }
+///
+/// let (pool, worker) = AsyncReturnPool::new(Pool::new(8, Alloc), |mut buf: Vec<u8>| async {
+///     buf.clear();
+///     Some(buf)
+/// });
+/// let worker = tokio::spawn(worker.run());
+/// pool.get().push(1);
+/// drop(pool);
+/// worker.await.unwrap();
+/// # }
+/// ```
+///
+/// Clones share the same pool and worker.
+pub struct AsyncReturnPool<P: PoolAllocator<T>, T> {
+    pool: Arc<Pool<P, T>>,
+    returns: UnboundedSender<T>,
+}
+
+impl<P: PoolAllocator<T>, T> AsyncReturnPool<P, T> {
+    /// Creates a new AsyncReturnPool over `pool` and the worker that cleans
+    /// up its returned objects with `cleanup`.
+    ///
+    /// `cleanup` returns the object to put back into the pool, or `None` if
+    /// it disposed of the object, such as a connection that failed to flush.
+    pub fn new<F, Fut>(pool: Pool<P, T>, cleanup: F) -> (Self, ReturnWorker<P, T, F>)
+    where
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Option<T>>,
+    {
+        let pool = Arc::new(pool);
+        let (returns, queue) = unbounded_channel();
+        let worker = ReturnWorker {
+            pool: pool.clone(),
+            queue,
+            cleanup,
+        };
+        (AsyncReturnPool { pool, returns }, worker)
+    }
+
+    /// Gets an object from the pool.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get(&self) -> AsyncReturnGuard<P, T> {
+        AsyncReturnGuard {
+            obj: ManuallyDrop::new(self.pool.checkout()),
+            pool: self.pool.clone(),
+            returns: self.returns.clone(),
+        }
+    }
+
+    /// Returns the pool behind this wrapper.
+    pub fn pool(&self) -> &Arc<Pool<P, T>> {
+        &self.pool
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Clone for AsyncReturnPool<P, T> {
+    fn clone(&self) -> Self {
+        AsyncReturnPool {
+            pool: self.pool.clone(),
+            returns: self.returns.clone(),
+        }
+    }
+}
+
+impl<P: PoolAllocator<T> + fmt::Debug, T: fmt::Debug> fmt::Debug for AsyncReturnPool<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncReturnPool")
+            .field("pool", &self.pool)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The task cleaning up the objects returned to an [`AsyncReturnPool`].
+///
+/// Objects are cleaned up one at a time, in the order they were returned.
+pub struct ReturnWorker<P: PoolAllocator<T>, T, F> {
+    pool: Arc<Pool<P, T>>,
+    queue: UnboundedReceiver<T>,
+    cleanup: F,
+}
+
+impl<P: PoolAllocator<T>, T, F, Fut> ReturnWorker<P, T, F>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    /// Cleans up returned objects until the pool and all of its guards are
+    /// dropped.
+    pub async fn run(mut self) {
+        while let Some(obj) = self.queue.recv().await {
+            match (self.cleanup)(obj).await {
+                Some(obj) => {
+                    self.pool.recycle(obj);
+                }
+                None => self.pool.forget_one(),
+            }
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T, F> fmt::Debug for ReturnWorker<P, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReturnWorker")
+            .field("queued", &self.queue.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A guard over an object of an [`AsyncReturnPool`].
+///
+/// Dropping the guard queues the object for its [`ReturnWorker`]. If the
+/// worker is gone, the object is returned to the pool directly, without the
+/// async cleanup.
+pub struct AsyncReturnGuard<P: PoolAllocator<T>, T> {
+    obj: ManuallyDrop<T>,
+    pool: Arc<Pool<P, T>>,
+    returns: UnboundedSender<T>,
+}
+
+impl<P: PoolAllocator<T>, T> AsyncReturnGuard<P, T> {
+    /// Consumes the guard and returns the object, without returning it to the
+    /// pool.
+    pub fn into_inner(self) -> T {
+        let mut this = ManuallyDrop::new(self);
+        // Safety: the guard is never dropped, so each field is taken once.
+        let (obj, pool, returns) = unsafe {
+            (
+                ManuallyDrop::take(&mut this.obj),
+                ptr::read(&this.pool),
+                ptr::read(&this.returns),
+            )
+        };
+        drop(returns);
+        pool.forget_one();
+        obj
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Deref for AsyncReturnGuard<P, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.obj
+    }
+}
+
+impl<P: PoolAllocator<T>, T> DerefMut for AsyncReturnGuard<P, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.obj
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Drop for AsyncReturnGuard<P, T> {
+    fn drop(&mut self) {
+        // Safety: the object is moved out of the guard only once, here.
+        let obj = unsafe { ManuallyDrop::take(&mut self.obj) };
+        if let Err(returned) = self.returns.send(obj) {
+            self.pool.recycle(returned.0);
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for AsyncReturnGuard<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
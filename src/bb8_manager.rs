@@ -0,0 +1,76 @@
+use crate::{PoolAllocator, PoolError};
+use core::{fmt, future::Future, marker::PhantomData};
+
+/// A [`bb8::ManageConnection`] backed by a [`PoolAllocator`], so an existing
+/// allocator can be used to fill a [`bb8::Pool`].
+///
+/// New objects are created with [`PoolAllocator::allocate`], and objects are
+/// checked with [`PoolAllocator::is_valid`], failing with
+/// [`PoolError::Invalid`] when bb8 tests them on checkout. bb8 has no hook
+/// that runs before an idle object is handed out, so valid objects are reset
+/// with [`PoolAllocator::try_reset`] when they are returned instead, and
+/// objects that fail to reset are treated as broken.
+///
+/// Which idle object bb8 hands out next is chosen by bb8, and set with
+/// [`bb8::Builder::queue_strategy`]. [`bb8::QueueStrategy::Lifo`] reuses the
+/// most recently returned objects, which keeps a small hot set of connections
+/// alive and lets the rest time out, while the default
+/// [`bb8::QueueStrategy::Fifo`] spreads the load evenly across all of them.
+pub struct Bb8Manager<P: PoolAllocator<T>, T> {
+    allocator: P,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<P: PoolAllocator<T>, T> Bb8Manager<P, T> {
+    /// Creates a new Bb8Manager that uses `allocator` to manage objects.
+    pub fn new(allocator: P) -> Self {
+        Bb8Manager {
+            allocator,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the allocator of the manager.
+    #[inline]
+    pub fn allocator(&self) -> &P {
+        &self.allocator
+    }
+}
+
+impl<P, T> bb8::ManageConnection for Bb8Manager<P, T>
+where
+    P: PoolAllocator<T> + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    type Connection = T;
+    type Error = PoolError;
+
+    fn connect(&self) -> impl Future<Output = Result<T, PoolError>> + Send {
+        let mut obj = self.allocator.allocate();
+        if !self.allocator.is_warm(&obj) {
+            self.allocator.warm(&mut obj);
+        }
+        async move { Ok(obj) }
+    }
+
+    fn is_valid(&self, obj: &mut T) -> impl Future<Output = Result<(), PoolError>> + Send {
+        let result = if self.allocator.is_valid(obj) {
+            Ok(())
+        } else {
+            Err(PoolError::Invalid)
+        };
+        async move { result }
+    }
+
+    fn has_broken(&self, obj: &mut T) -> bool {
+        !self.allocator.is_valid(obj) || !self.allocator.try_reset(obj)
+    }
+}
+
+impl<P: PoolAllocator<T> + fmt::Debug, T> fmt::Debug for Bb8Manager<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bb8Manager")
+            .field("allocator", &self.allocator)
+            .finish()
+    }
+}
@@ -0,0 +1,83 @@
+use core::time::Duration;
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
+
+/// The limit on live objects of a pool, set by `Pool::with_max_live`.
+///
+/// The live objects themselves are counted by the stats of the pool. This
+/// only lets a get that is over the limit wait for the pool to change.
+#[derive(Debug)]
+pub(crate) struct LiveLimit {
+    max: usize,
+    changes: Mutex<usize>,
+    changed: Condvar,
+}
+
+impl LiveLimit {
+    pub(crate) fn new(max: usize) -> Self {
+        LiveLimit {
+            max,
+            changes: Mutex::new(0),
+            changed: Condvar::new(),
+        }
+    }
+
+    /// Returns the maximum number of live objects.
+    pub(crate) fn max(&self) -> usize {
+        self.max
+    }
+
+    fn lock(&self) -> MutexGuard<'_, usize> {
+        self.changes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Calls `take` until it returns an object, waiting for an object to be
+    /// returned to the pool or to leave it between calls, and gives up after
+    /// `timeout`.
+    ///
+    /// `take` runs without holding the lock, so it may return or drop
+    /// objects of the pool itself.
+    pub(crate) fn wait_for<R>(
+        &self,
+        timeout: Option<Duration>,
+        mut take: impl FnMut() -> Option<R>,
+    ) -> Option<R> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            let seen = *self.lock();
+            if let Some(obj) = take() {
+                return Some(obj);
+            }
+            let changes = self.lock();
+            match deadline {
+                None => drop(
+                    self.changed
+                        .wait_while(changes, |changes| *changes == seen)
+                        .unwrap_or_else(PoisonError::into_inner),
+                ),
+                Some(deadline) => {
+                    let left = deadline.checked_duration_since(Instant::now())?;
+                    let (changes, result) = self
+                        .changed
+                        .wait_timeout_while(changes, left, |changes| *changes == seen)
+                        .unwrap_or_else(PoisonError::into_inner);
+                    drop(changes);
+                    if result.timed_out() {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Wakes the waiting gets after an object was returned to the pool or
+    /// left it.
+    pub(crate) fn notify(&self) {
+        let mut changes = self.lock();
+        *changes = changes.wrapping_add(1);
+        drop(changes);
+        self.changed.notify_all();
+    }
+}
@@ -0,0 +1,388 @@
+#[cfg(all(feature = "linux", target_os = "linux"))]
+use crate::ResetPolicy;
+use crate::{LocalPool, Pool, PoolAllocator, RefGuard, RefLocalGuard};
+#[cfg(all(feature = "linux", target_os = "linux"))]
+use alloc::alloc::Layout;
+use alloc::{vec, vec::Vec};
+#[cfg(all(feature = "linux", target_os = "linux"))]
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    slice,
+};
+
+/// An allocator for fixed-length byte buffers.
+///
+/// New buffers are allocated zeroed, which uses the zeroed allocation path of
+/// the global allocator and is usually much cheaper than writing the zeros.
+/// Resetting a buffer restores its length but keeps its contents; use
+/// `get_zeroed` on [`Pool`] or [`LocalPool`] to check out a buffer that is
+/// guaranteed to be zeroed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferAllocator {
+    len: usize,
+    #[cfg(all(feature = "linux", target_os = "linux"))]
+    huge_pages: bool,
+    #[cfg(all(feature = "linux", target_os = "linux"))]
+    decommit_on_trim: bool,
+}
+
+impl BufferAllocator {
+    /// Creates a new BufferAllocator for buffers of `len` bytes.
+    pub const fn new(len: usize) -> Self {
+        BufferAllocator {
+            len,
+            #[cfg(all(feature = "linux", target_os = "linux"))]
+            huge_pages: false,
+            #[cfg(all(feature = "linux", target_os = "linux"))]
+            decommit_on_trim: false,
+        }
+    }
+
+    /// Asks the kernel to back new buffers with transparent huge pages.
+    ///
+    /// This reduces TLB pressure for multi-megabyte buffers. It only applies to
+    /// the huge page aligned parts of each buffer, and only when transparent
+    /// huge pages are enabled in `madvise` or `always` mode.
+    #[cfg(all(feature = "linux", target_os = "linux"))]
+    pub const fn with_huge_pages(mut self) -> Self {
+        self.huge_pages = true;
+        self
+    }
+
+    /// Keeps the buffers that a pool trims, returning their memory pages to
+    /// the operating system instead of dropping them.
+    ///
+    /// With this option, `shrink_to` and trimming on memory pressure work like
+    /// `decommit_idle` on the trimmed buffers: the memory is released, but the
+    /// buffers keep their place in the pool and their address ranges, and
+    /// their pages are faulted back in, zeroed, when they are used again.
+    #[cfg(all(feature = "linux", target_os = "linux"))]
+    pub const fn with_decommit_on_trim(mut self) -> Self {
+        self.decommit_on_trim = true;
+        self
+    }
+
+    /// Returns the length of the buffers created by this allocator.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if this allocator creates empty buffers.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl PoolAllocator<Vec<u8>> for BufferAllocator {
+    #[inline]
+    fn reset(&self, obj: &mut Vec<u8>) {
+        obj.resize(self.len, 0);
+    }
+
+    #[inline]
+    fn allocate(&self) -> Vec<u8> {
+        let buf = vec![0; self.len];
+        #[cfg(all(feature = "linux", target_os = "linux"))]
+        if self.huge_pages {
+            // Safety: the advice only changes how the buffer pages are backed.
+            unsafe { crate::linux::advise(buf.as_ptr(), buf.capacity(), libc::MADV_HUGEPAGE) };
+        }
+        buf
+    }
+
+    #[inline]
+    fn is_valid(&self, obj: &Vec<u8>) -> bool {
+        obj.capacity() >= self.len
+    }
+
+    #[cfg(all(feature = "linux", target_os = "linux"))]
+    fn on_trim(&self, obj: &mut Vec<u8>) -> bool {
+        if self.decommit_on_trim {
+            decommit(obj);
+        }
+        self.decommit_on_trim
+    }
+}
+
+/// Returns the memory pages of a buffer to the operating system.
+#[cfg(all(feature = "linux", target_os = "linux"))]
+fn decommit(buf: &mut Vec<u8>) {
+    // Safety: the buffer is exclusively borrowed and any contents of its
+    // bytes are valid.
+    unsafe { crate::linux::advise(buf.as_mut_ptr(), buf.capacity(), libc::MADV_DONTNEED) };
+}
+
+impl Pool<BufferAllocator, Vec<u8>> {
+    /// Gets a buffer from the pool whose contents are guaranteed to be zeroed.
+    ///
+    /// Recycled buffers are cleared in place, while new buffers are already
+    /// zeroed by the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_zeroed(&self) -> RefGuard<'_, BufferAllocator, Vec<u8>> {
+        let buf = match self.take_idle() {
+            Some(mut buf) => {
+                buf.fill(0);
+                buf
+            }
+            None => self.allocate_fallback(),
+        };
+        #[cfg(feature = "diagnostics")]
+        self.record_event(crate::PoolEventKind::Checkout);
+        #[cfg(feature = "tracing")]
+        self.trace_event(crate::trace::TraceEvent::Checkout);
+        RefGuard::new(buf, self)
+    }
+
+    /// Returns the memory pages of all idle buffers to the operating system,
+    /// while keeping the buffers and their address ranges in the pool.
+    ///
+    /// The pages are faulted back in, zeroed, when a buffer is used again.
+    /// This is useful to release the memory of a large pool during quiet
+    /// periods without dropping its buffers.
+    #[cfg(all(feature = "linux", target_os = "linux"))]
+    pub fn decommit_idle(&self) {
+        self.with_idle(|idle| idle.iter_mut().for_each(decommit))
+    }
+}
+
+impl LocalPool<BufferAllocator, Vec<u8>> {
+    /// Gets a buffer from the pool whose contents are guaranteed to be zeroed.
+    ///
+    /// Recycled buffers are cleared in place, while new buffers are already
+    /// zeroed by the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_zeroed(&self) -> RefLocalGuard<'_, BufferAllocator, Vec<u8>> {
+        let buf = match self.take_idle() {
+            Some(mut buf) => {
+                buf.fill(0);
+                buf
+            }
+            None => self.allocate_fallback(),
+        };
+        #[cfg(feature = "diagnostics")]
+        self.record_event(crate::PoolEventKind::Checkout);
+        #[cfg(feature = "tracing")]
+        self.trace_event(crate::trace::TraceEvent::Checkout);
+        RefLocalGuard::new(buf, self)
+    }
+
+    /// Returns the memory pages of all idle buffers to the operating system,
+    /// while keeping the buffers and their address ranges in the pool.
+    ///
+    /// The pages are faulted back in, zeroed, when a buffer is used again.
+    /// This is useful to release the memory of a large pool during quiet
+    /// periods without dropping its buffers.
+    #[cfg(all(feature = "linux", target_os = "linux"))]
+    pub fn decommit_idle(&self) {
+        self.with_idle(|idle| idle.iter_mut().for_each(decommit))
+    }
+}
+
+/// Size of the pages touched by `pretouched`. Touching every 4 KiB also
+/// touches every page on systems with larger pages.
+const TOUCH_STRIDE: usize = 4096;
+
+/// Writes one byte of every page of `buf`, so the pages are faulted in now
+/// instead of on first use.
+fn pretouch(buf: &mut [u8]) {
+    for i in (0..buf.len()).step_by(TOUCH_STRIDE) {
+        let byte: *mut u8 = &mut buf[i];
+        // Safety: the pointer comes from a mutable reference. The volatile
+        // write keeps the compiler from dropping the write of an unchanged
+        // value, which is what faults in a zeroed page.
+        unsafe { byte.write_volatile(byte.read_volatile()) };
+    }
+}
+
+impl<P: PoolAllocator<T>, T: AsMut<[u8]>> Pool<P, T> {
+    /// Touches every memory page of the idle buffers in the pool.
+    ///
+    /// Zeroed buffers are usually backed lazily by the operating system, so
+    /// the first write to each page stalls on a page fault. Chaining this
+    /// after [`Pool::new_prefilled`] or [`Pool::from_config`] takes those
+    /// faults up front instead of on the latency-critical path.
+    pub fn pretouched(self) -> Self {
+        self.with_idle(|idle| idle.iter_mut().for_each(|buf| pretouch(buf.as_mut())));
+        self
+    }
+}
+
+impl<P: PoolAllocator<T>, T: AsMut<[u8]>> LocalPool<P, T> {
+    /// Touches every memory page of the idle buffers in the pool.
+    ///
+    /// See [`Pool::pretouched`].
+    pub fn pretouched(self) -> Self {
+        self.with_idle(|idle| idle.iter_mut().for_each(|buf| pretouch(buf.as_mut())));
+        self
+    }
+}
+
+/// A fixed-length byte buffer for secrets, such as key material.
+///
+/// The buffer is locked into RAM with `mlock` so it is never written to swap,
+/// and its contents are wiped when it is reset or dropped. It is backed by
+/// whole pages of its own, so locking it never pins the memory of unrelated
+/// allocations and unlocking it never unlocks memory still used by another
+/// buffer.
+#[cfg(all(feature = "linux", target_os = "linux"))]
+pub struct SecureBuffer {
+    ptr: NonNull<u8>,
+    len: usize,
+    layout: Layout,
+    locked: bool,
+}
+
+// Safety: the buffer exclusively owns its memory, like a `Box<[u8]>`.
+#[cfg(all(feature = "linux", target_os = "linux"))]
+unsafe impl Send for SecureBuffer {}
+// Safety: shared references only allow reading the bytes.
+#[cfg(all(feature = "linux", target_os = "linux"))]
+unsafe impl Sync for SecureBuffer {}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+impl SecureBuffer {
+    /// Creates a new zeroed SecureBuffer of `len` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` rounded up to whole pages overflows `isize`.
+    pub fn new(len: usize) -> Self {
+        let page = crate::linux::page_size();
+        let size = len
+            .max(1)
+            .checked_next_multiple_of(page)
+            .expect("SecureBuffer length overflows");
+        let layout = Layout::from_size_align(size, page).expect("SecureBuffer length overflows");
+        // Safety: the layout has a non-zero size.
+        let ptr = unsafe { alloc::alloc::alloc_zeroed(layout) };
+        let Some(ptr) = NonNull::new(ptr) else {
+            alloc::alloc::handle_alloc_error(layout)
+        };
+        let mut buf = SecureBuffer {
+            ptr,
+            len,
+            layout,
+            locked: false,
+        };
+        buf.locked = crate::linux::lock(buf.pages());
+        buf
+    }
+
+    /// Returns the whole pages backing the buffer.
+    fn pages(&self) -> &[u8] {
+        // Safety: the allocation is `layout.size()` bytes long and zeroed
+        // when created.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.layout.size()) }
+    }
+
+    /// Returns `true` if the buffer is locked into RAM.
+    ///
+    /// Locking fails when the process exceeds its `RLIMIT_MEMLOCK` limit.
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    /// Overwrites the contents of the buffer with zeros.
+    pub fn wipe(&mut self) {
+        crate::linux::wipe(self);
+    }
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+impl Deref for SecureBuffer {
+    type Target = [u8];
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        // Safety: the first `len` bytes of the allocation are initialized.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+impl DerefMut for SecureBuffer {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Safety: the first `len` bytes of the allocation are initialized and
+        // exclusively borrowed.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+impl Drop for SecureBuffer {
+    fn drop(&mut self) {
+        self.wipe();
+        if self.locked {
+            crate::linux::unlock(self.pages());
+        }
+        // Safety: the memory was allocated in `new` with this layout.
+        unsafe { alloc::alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+    }
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+impl fmt::Debug for SecureBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecureBuffer")
+            .field("len", &self.len)
+            .field("locked", &self.locked)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An allocator for [`SecureBuffer`]s of a fixed length.
+///
+/// Buffers are wiped whenever the pool resets them. Under the default
+/// [`ResetPolicy::OnGet`], that only happens when a buffer is handed out
+/// again, so a returned secret stays in the idle buffer until then. Build the
+/// pool with `new_secure` on [`Pool`] or [`LocalPool`], which resets buffers
+/// with [`ResetPolicy::OnReturn`], so a secret is wiped as soon as its buffer
+/// is returned and never leaks from one user of the pool to the next.
+#[cfg(all(feature = "linux", target_os = "linux"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecureBufferAllocator {
+    len: usize,
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+impl SecureBufferAllocator {
+    /// Creates a new SecureBufferAllocator for buffers of `len` bytes.
+    pub const fn new(len: usize) -> Self {
+        SecureBufferAllocator { len }
+    }
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+impl PoolAllocator<SecureBuffer> for SecureBufferAllocator {
+    #[inline]
+    fn reset(&self, obj: &mut SecureBuffer) {
+        obj.wipe();
+    }
+
+    #[inline]
+    fn allocate(&self) -> SecureBuffer {
+        SecureBuffer::new(self.len)
+    }
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+impl Pool<SecureBufferAllocator, SecureBuffer> {
+    /// Creates a new pool of [`SecureBuffer`]s of `len` bytes with the given
+    /// capacity, which wipes buffers as soon as they are returned.
+    pub fn new_secure(capacity: usize, len: usize) -> Self {
+        Pool::new(capacity, SecureBufferAllocator::new(len))
+            .with_reset_policy(ResetPolicy::OnReturn)
+    }
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+impl LocalPool<SecureBufferAllocator, SecureBuffer> {
+    /// Creates a new pool of [`SecureBuffer`]s of `len` bytes with the given
+    /// capacity, which wipes buffers as soon as they are returned.
+    pub fn new_secure(capacity: usize, len: usize) -> Self {
+        LocalPool::new(capacity, SecureBufferAllocator::new(len))
+            .with_reset_policy(ResetPolicy::OnReturn)
+    }
+}
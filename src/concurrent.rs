@@ -1,25 +1,74 @@
-use crate::PoolAllocator;
-use alloc::{fmt, sync::Arc};
+#[cfg(feature = "log")]
+use crate::logging::{log_event, LogEvent};
+#[cfg(feature = "tracing")]
+use crate::trace::{trace_event, TraceEvent};
+use crate::{
+    allocator_cell::AllocatorCell,
+    queue::ArrayQueue,
+    stats::Stats,
+    storage::Storage,
+    sync::{AtomicUsize, Ordering},
+    LocalPool, ManualGuard, PoolAllocator, PoolConfig, PoolError, PoolHandle, PoolObserver,
+    PoolStats, ResetPolicy, ReturnStatus,
+};
+#[cfg(feature = "std")]
+use crate::{blocking::LiveLimit, HoldWatchdog, LongHeld, MissAlert, WatchedGuard};
+#[cfg(feature = "diagnostics")]
+use crate::{
+    DenyAlloc, DenyAllocGuard, EventLog, MissSite, MissSites, OwnerTags, PoolEvent, PoolEventKind,
+    TagUsage, TaggedGuard,
+};
+use alloc::{boxed::Box, fmt, sync::Arc, vec, vec::Vec};
+#[cfg(any(feature = "diagnostics", feature = "std"))]
+use core::panic::Location;
+#[cfg(feature = "std")]
+use core::time::Duration;
 use core::{
     hash::{Hash, Hasher},
     mem::{forget, MaybeUninit},
     ops::{Deref, DerefMut},
     ptr,
 };
-use crossbeam_queue::ArrayQueue;
+#[cfg(feature = "log")]
+use log::LevelFilter;
 
 /// A struct representing an object pool.
 ///
 /// This struct uses an allocator to create and manage objects, and stores them
-/// in an ArrayQueue.
+/// in lock-free segments that are allocated on demand up to the pool capacity.
 #[derive(Debug)]
 pub struct Pool<P: PoolAllocator<T>, T> {
-    allocator: P,
-    storage: ArrayQueue<T>,
+    allocator: AllocatorCell<P>,
+    storage: Storage<T>,
+    stats: Stats,
+    shed_threshold: AtomicUsize,
+    name: Option<&'static str>,
+    lazy_prefill: AtomicUsize,
+    prefill_batch: usize,
+    quarantine: Option<ArrayQueue<T>>,
+    drop_check: bool,
+    reset_policy: AtomicUsize,
+    observer: Option<Box<dyn PoolObserver<T> + Send + Sync>>,
+    #[cfg(feature = "diagnostics")]
+    deny_alloc: DenyAlloc,
+    #[cfg(feature = "diagnostics")]
+    miss_sites: MissSites,
+    #[cfg(feature = "diagnostics")]
+    owner_tags: OwnerTags,
+    #[cfg(feature = "diagnostics")]
+    events: EventLog,
+    #[cfg(feature = "std")]
+    miss_alert: Option<Box<MissAlert>>,
+    #[cfg(feature = "std")]
+    hold_watchdog: Option<Box<HoldWatchdog>>,
+    #[cfg(feature = "std")]
+    live_limit: Option<Box<LiveLimit>>,
+    #[cfg(feature = "log")]
+    log_level: LevelFilter,
 }
 
-// If T is Send it is safe to move object pool between threads
-unsafe impl<P: PoolAllocator<T>, T: Send> Send for Pool<P, T> {}
+// If T and the allocator are Send it is safe to move object pool between threads
+unsafe impl<P: PoolAllocator<T> + Send, T: Send> Send for Pool<P, T> {}
 
 impl<P: PoolAllocator<T>, T> Pool<P, T> {
     /// Creates a new Pool with a given size and allocator.
@@ -27,20 +76,120 @@ impl<P: PoolAllocator<T>, T> Pool<P, T> {
     /// This method immediately fills the pool with new objects created by the
     /// allocator.
     pub fn new_prefilled(pool_size: usize, allocator: P) -> Self {
-        let storage = ArrayQueue::new(pool_size);
-        for _ in 0..pool_size {
-            let _ = storage.push(allocator.allocate());
+        Self::new(pool_size, allocator).prefilled(pool_size)
+    }
+
+    /// Creates a new Pool tuned by `config`, with the given allocator.
+    pub fn from_config(config: &PoolConfig, allocator: P) -> Self {
+        let pool = Self::new(config.capacity, allocator)
+            .with_quarantine(config.quarantine)
+            .with_reset_policy(config.reset);
+        pool.update_config(config);
+        match config.prefill_batch {
+            Some(batch) => pool.with_lazy_prefill(config.prefill, batch),
+            None => pool.prefilled(config.prefill),
         }
-        Pool { allocator, storage }
+    }
+
+    fn prefilled(self, count: usize) -> Self {
+        self.prefill(count);
+        self
+    }
+
+    /// Creates a new pool without a capacity limit, so returned objects are
+    /// never dropped because the pool is full.
+    ///
+    /// This suits objects that are cheap to hold but expensive to create: the
+    /// pool keeps every object it ever handed out. Its storage grows in
+    /// segments as objects are returned and is only freed when the pool is
+    /// dropped. Its capacity is reported as `usize::MAX`.
+    pub fn new_unbounded(allocator: P) -> Self {
+        Self::new(usize::MAX, allocator)
     }
 
     /// Creates a new Object Pool with a given size and allocator.
     ///
     /// Unlike [`Self::new_prefilled`], this method does not immediately fill
     /// the pool with objects.
+    ///
+    /// A `pool_size` of zero creates a pass-through pool: every get allocates
+    /// a new object and every returned object is dropped.
+    ///
+    /// Storage for idle objects is only allocated once the first object is
+    /// returned, so a pool that never sees traffic costs no more than its own
+    /// struct.
     pub fn new(pool_size: usize, allocator: P) -> Self {
-        let storage = ArrayQueue::new(pool_size);
-        Pool { allocator, storage }
+        let storage = Storage::new(pool_size);
+        Pool {
+            allocator: AllocatorCell::new(allocator),
+            storage,
+            stats: Stats::default(),
+            shed_threshold: AtomicUsize::new(usize::MAX),
+            name: None,
+            lazy_prefill: AtomicUsize::new(0),
+            prefill_batch: 0,
+            quarantine: None,
+            drop_check: false,
+            reset_policy: AtomicUsize::new(ResetPolicy::OnGet as usize),
+            observer: None,
+            #[cfg(feature = "diagnostics")]
+            deny_alloc: DenyAlloc::default(),
+            #[cfg(feature = "diagnostics")]
+            miss_sites: MissSites::default(),
+            #[cfg(feature = "diagnostics")]
+            owner_tags: OwnerTags::default(),
+            #[cfg(feature = "diagnostics")]
+            events: EventLog::default(),
+            #[cfg(feature = "std")]
+            miss_alert: None,
+            #[cfg(feature = "std")]
+            hold_watchdog: None,
+            #[cfg(feature = "std")]
+            live_limit: None,
+            #[cfg(feature = "log")]
+            log_level: LevelFilter::Warn,
+        }
+    }
+
+    /// Creates a new Pool holding the given idle objects.
+    ///
+    /// Objects that do not fit in the pool are destroyed.
+    pub(crate) fn from_parts(
+        pool_size: usize,
+        allocator: P,
+        idle: impl Iterator<Item = T>,
+    ) -> Self {
+        let pool = Self::new(pool_size, allocator);
+        let mut idle = idle;
+        let (adopted, rejected) = pool.storage.push_many(&mut idle);
+        pool.stats.adopted(adopted);
+        rejected
+            .into_iter()
+            .chain(idle)
+            .for_each(|obj| pool.allocator.on_destroy(obj));
+        pool
+    }
+
+    /// Creates a new pool with a given size and allocator, holding the given
+    /// objects as its idle objects.
+    ///
+    /// Unlike [`Self::new_prefilled`], the objects are built by the caller,
+    /// for example with data loaded from disk. They are added like with
+    /// [`Self::put`], so objects that fail [`PoolAllocator::is_valid`] or do
+    /// not fit are destroyed instead.
+    pub fn with_objects(pool_size: usize, allocator: P, objs: Vec<T>) -> Self {
+        let mut pool = Self::new(pool_size, allocator);
+        pool.extend(objs);
+        pool
+    }
+
+    /// Creates a new pool holding the given objects, with room for exactly
+    /// as many objects, for example to restore objects from a snapshot.
+    ///
+    /// See [`Self::with_objects`].
+    pub fn from_iter_with(allocator: P, objs: impl IntoIterator<Item = T>) -> Self {
+        let objs: Vec<T> = objs.into_iter().collect();
+        Self::with_objects(objs.len(), allocator, objs)
     }
 
     /// Wraps the pool allocator with an atomic reference counter, enabling the
@@ -50,35 +199,995 @@ impl<P: PoolAllocator<T>, T> Pool<P, T> {
         Arc::new(self)
     }
 
+    /// Moves the pool to the heap and leaks it, like [`Box::leak`], returning
+    /// a `'static` reference to it.
+    ///
+    /// Guards from [`Self::get`] on the leaked pool are `'static`, so they
+    /// can be stored anywhere without the reference counting of
+    /// [`Self::get_rc`]. The pool is never dropped, so its idle objects are
+    /// never handed to [`PoolAllocator::on_destroy`].
+    pub fn leak(self) -> &'static Self {
+        Box::leak(Box::new(self))
+    }
+
+    /// Wraps the pool in a cheaply clonable [`PoolHandle`], whose
+    /// [`PoolHandle::get`] returns owned guards.
+    pub fn to_handle(self) -> PoolHandle<P, T> {
+        PoolHandle::new(self)
+    }
+
+    /// Sets the name of the pool, which is shown in its `Debug` output and
+    /// its [`PoolStats`].
+    ///
+    /// Names tell apart the metrics of pools of the same type. A name built
+    /// at runtime can be turned into a `&'static str` with `String::leak`.
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Returns the name of the pool, if it was given one.
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// Returns a reference to the allocator of the pool, for example to read
+    /// configuration or counters it carries.
+    #[inline]
+    pub fn allocator(&self) -> &P {
+        &self.allocator
+    }
+
+    /// Returns a mutable reference to the allocator of the pool.
+    ///
+    /// Objects that are already in the pool or checked out are not affected
+    /// by changes to the allocator until they are reset or validated again.
+    ///
+    /// The allocators replaced by [`Self::replace_allocator`] are freed here,
+    /// as nothing can still be using them.
+    #[inline]
+    pub fn allocator_mut(&mut self) -> &mut P {
+        self.allocator.get_mut()
+    }
+
+    /// Replaces the allocator of the pool while it is in use, and returns
+    /// the old allocator.
+    ///
+    /// This lets a configuration reload, such as a new buffer size, take
+    /// effect without rebuilding the pool or any handle to it. The idle
+    /// objects built by the old allocator are destroyed with its
+    /// [`PoolAllocator::on_destroy`], so no get hands them out again.
+    /// Objects that are checked out keep working, and are validated and
+    /// reset by the new allocator when they are returned, so an allocator
+    /// whose [`PoolAllocator::is_valid`] rejects the old objects makes sure
+    /// none of them is reused. Idle objects returned by other threads while
+    /// the allocator is swapped are destroyed as well.
+    ///
+    /// # Memory
+    ///
+    /// Other threads may still be using the old allocator, so the pool keeps
+    /// every allocator it replaced, and each call grows the pool by one
+    /// allocator. They are only freed when the pool is dropped or borrowed
+    /// exclusively through [`Self::allocator_mut`], so a pool whose
+    /// allocator is replaced regularly, such as on every configuration
+    /// reload, should call it whenever it is not shared, or is rebuilt from
+    /// time to time instead.
+    pub fn replace_allocator(&self, allocator: P) -> &P {
+        self.clear();
+        let old = self.allocator.replace(allocator);
+        self.clear();
+        old
+    }
+
+    /// Sets the level at which the pool logs notable events, such as dropping
+    /// objects that fail validation or do not fit, through the `log` crate.
+    ///
+    /// The default is [`LevelFilter::Warn`], and [`LevelFilter::Off`]
+    /// silences the pool.
+    #[cfg(feature = "log")]
+    pub fn with_log_level(mut self, level: LevelFilter) -> Self {
+        self.log_level = level;
+        self
+    }
+
+    /// Sets the load shedding threshold of the pool.
+    ///
+    /// Once `threshold` objects are checked out, [`Self::try_get`] and
+    /// [`Self::try_get_rc`] fail fast with [`PoolError::Overloaded`] instead of
+    /// handing out more objects. [`Self::get`] ignores the threshold.
+    pub fn with_shed_threshold(self, threshold: usize) -> Self {
+        self.shed_threshold.store(threshold, Ordering::Relaxed);
+        self
+    }
+
+    /// Installs an alert whose callback fires when the pool misses faster
+    /// than a given rate for a sustained time.
+    ///
+    /// See [`MissAlert`] for how the rate is measured.
+    #[cfg(feature = "std")]
+    pub fn with_miss_alert(mut self, alert: MissAlert) -> Self {
+        self.miss_alert = Some(Box::new(alert));
+        self
+    }
+
+    /// Installs a watchdog that flags guards returned by
+    /// [`Self::get_watched`] that are held for longer than its threshold.
+    ///
+    /// See [`HoldWatchdog`] for when held guards are checked.
+    #[cfg(feature = "std")]
+    pub fn with_hold_watchdog(mut self, watchdog: HoldWatchdog) -> Self {
+        self.hold_watchdog = Some(Box::new(watchdog));
+        self
+    }
+
+    /// Gets an object from the pool and watches how long it is held with the
+    /// watchdog installed by [`Self::with_hold_watchdog`].
+    ///
+    /// The call site of the get is recorded, so a guard that is held for too
+    /// long can be traced back to it. Without a watchdog, this behaves like
+    /// [`Self::get`].
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub fn get_watched(&self) -> WatchedGuard<'_, RefGuard<'_, P, T>> {
+        let location = Location::caller();
+        let guard = RefGuard::new(self.checkout(), self);
+        WatchedGuard::new(guard, self.hold_watchdog.as_deref(), location)
+    }
+
+    /// Returns the guards from [`Self::get_watched`] that have been held for
+    /// longer than the threshold of the watchdog, firing its callback for
+    /// those not reported yet.
+    #[cfg(feature = "std")]
+    pub fn long_held(&self) -> Vec<LongHeld> {
+        match &self.hold_watchdog {
+            Some(watchdog) => watchdog.scan(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Limits how many live objects the pool creates, counting both the idle
+    /// objects and those that are checked out.
+    ///
+    /// Once the pool has `max_live` objects and none of them is idle, a get
+    /// that would allocate a new object blocks until one is returned or
+    /// dropped instead, like a connection pool. This applies to every get
+    /// that can allocate, such as [`Self::get`], [`Self::with`] and
+    /// [`Self::acquire`], while [`Self::acquire_timeout`] gives up after a
+    /// deadline. Objects added to the pool without a get, such as by a
+    /// prefill or [`Self::put`], count toward the limit but are never
+    /// refused by it.
+    #[cfg(feature = "std")]
+    pub fn with_max_live(mut self, max_live: usize) -> Self {
+        self.live_limit = Some(Box::new(LiveLimit::new(max_live)));
+        self
+    }
+
+    /// Gets an object from the pool, blocking until one is returned if the
+    /// limit set by [`Self::with_max_live`] is reached.
+    ///
+    /// If the pool is empty and the limit allows it, a new object is created
+    /// using the allocator. This waits exactly like [`Self::get`], and is
+    /// kept as the explicit spelling of a blocking get.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn acquire(&self) -> RefGuard<'_, P, T> {
+        RefGuard::new(self.checkout(), self)
+    }
+
+    /// Gets an object from the pool like [`Self::acquire`], but gives up and
+    /// returns `None` if no object is returned within `timeout`.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn acquire_timeout(&self, timeout: Duration) -> Option<RefGuard<'_, P, T>> {
+        let obj = match (self.take_idle(), &self.live_limit) {
+            (Some(obj), _) => obj,
+            (None, Some(limit)) => self.allocate_within(limit, Some(timeout))?,
+            (None, None) => self.allocate_fallback(),
+        };
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Checkout, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        Some(RefGuard::new(obj, self))
+    }
+
+    /// Applies the settings of `config` that can change on a live pool.
+    ///
+    /// The capacity is changed like [`Self::resize`], the load shedding
+    /// threshold applies to the next [`Self::try_get`] on any thread, and the
+    /// reset policy applies to the next get or return. When the pool stops
+    /// resetting objects on get, its idle objects are reset right away with
+    /// [`Self::reset_idle`] so none of them is handed out without a reset,
+    /// although an object returned on another thread during the change may
+    /// still miss its reset.
+    ///
+    /// The prefill, prefill batch and quarantine only take effect when a
+    /// pool is built with [`Self::from_config`], so they are ignored here.
+    pub fn update_config(&self, config: &PoolConfig) {
+        if config.capacity != self.cap() {
+            self.resize(config.capacity);
+        }
+        self.shed_threshold.store(
+            config.shed_threshold.unwrap_or(usize::MAX),
+            Ordering::Relaxed,
+        );
+        let old = self
+            .reset_policy
+            .swap(config.reset as usize, Ordering::Relaxed);
+        if old == ResetPolicy::OnGet as usize && config.reset != ResetPolicy::OnGet {
+            self.reset_idle();
+        }
+    }
+
+    /// Defers prefilling the pool with `count` objects until a get first
+    /// finds the pool empty.
+    ///
+    /// Each get that finds the pool empty creates up to `batch` of the
+    /// remaining objects in addition to its own. Passing `count` as `batch`
+    /// prefills the pool on first use, while a smaller batch spreads the
+    /// warm-up cost over the first misses. A pool that is never used never
+    /// pays for it.
+    pub fn with_lazy_prefill(mut self, count: usize, batch: usize) -> Self {
+        self.lazy_prefill = AtomicUsize::new(count.min(self.cap()));
+        self.prefill_batch = batch.max(1);
+        self
+    }
+
+    /// Resets every idle object with [`PoolAllocator::try_reset`], returning
+    /// how many were reset.
+    ///
+    /// Objects that fail to reset are destroyed. Running this at a convenient
+    /// time, such as at startup or during quiet periods, pairs with
+    /// [`Self::with_deferred_reset`] to keep resets off both the get and the
+    /// return path.
+    pub fn reset_idle(&self) -> usize {
+        let mut reset = 0;
+        self.cycle_idle(|mut obj| {
+            if !self.allocator.try_reset(&mut obj) {
+                #[cfg(feature = "log")]
+                self.log_event(LogEvent::ResetFailed);
+                self.destroy(obj);
+                return None;
+            }
+            reset += 1;
+            Some(obj)
+        });
+        reset
+    }
+
+    /// Changes the capacity of the pool, destroying the idle objects that no
+    /// longer fit.
+    ///
+    /// Checked out objects stay valid and are returned to the resized pool,
+    /// so the recycling budget of a long-running pool can be tuned without
+    /// recreating it.
+    ///
+    /// Growing the pool allocates storage on demand as usual. Shrinking it
+    /// keeps the storage that is already allocated until the pool is dropped.
+    pub fn resize(&self, capacity: usize) {
+        self.storage.set_capacity(capacity);
+        self.drop_idle(capacity);
+    }
+
+    /// Tops the pool up to `count` idle objects, or to its capacity if that
+    /// is lower, returning how many objects were created.
+    ///
+    /// Unlike [`Self::new_prefilled`], this can warm the pool up at any time,
+    /// such as once the expected load is known.
+    pub fn prefill(&self, count: usize) -> usize {
+        let count = count.min(self.cap());
+        let mut created = 0;
+        // Bounded by `count` in case other threads keep taking objects out.
+        while created < count && self.storage.len() < count {
+            self.stats.allocated();
+            if let Err(obj) = self.storage.push(self.allocate()) {
+                self.destroy(obj);
+                break;
+            }
+            created += 1;
+        }
+        created
+    }
+
+    /// Warms the idle objects that are not [`PoolAllocator::is_warm`] yet,
+    /// returning how many were warmed.
+    ///
+    /// This lets the objects of a prefill finish becoming ready off the
+    /// critical path, for example on a background thread right after the
+    /// pool is built. Objects are taken out of the pool one at a time, so
+    /// gets running meanwhile still find the other idle objects, and warm a
+    /// cold object themselves if they take it first.
+    pub fn warm_idle(&self) -> usize {
+        let mut warmed = 0;
+        self.cycle_idle(|mut obj| {
+            if !self.allocator.is_warm(&obj) {
+                self.allocator.warm(&mut obj);
+                warmed += 1;
+            }
+            Some(obj)
+        });
+        warmed
+    }
+
+    /// Hands out the most recently returned idle object first, instead of
+    /// the one that has been idle the longest.
+    ///
+    /// The most recently used object is more likely to still be in the CPU
+    /// caches, which suits cache-sensitive work, while the default order
+    /// spreads use evenly over the objects. The idle objects are kept in a
+    /// stack guarded by a lock instead of the lock-free segments, so gets and
+    /// returns from many threads at once contend on it.
+    pub fn with_lifo(mut self) -> Self {
+        let storage = Storage::new_lifo(self.storage.capacity());
+        let mut idle = Vec::with_capacity(self.storage.len());
+        while let Some(obj) = self.storage.pop() {
+            idle.push(obj);
+        }
+        // The most recently returned object ends up on top.
+        storage.push_many(idle.into_iter());
+        self.storage = storage;
+        self
+    }
+
+    /// Keeps up to `limit` objects that fail validation in a quarantine
+    /// instead of destroying them, so they can be inspected with
+    /// [`Self::take_quarantined`].
+    ///
+    /// Once the quarantine is full, further invalid objects are destroyed as
+    /// usual. Quarantined objects no longer count as live objects of the pool.
+    pub fn with_quarantine(mut self, limit: usize) -> Self {
+        self.quarantine = (limit > 0).then(|| ArrayQueue::new(limit));
+        self
+    }
+
+    /// Installs an observer whose hooks are called as objects are allocated,
+    /// recycled, discarded or fail validation.
+    ///
+    /// See [`PoolObserver`] for when each hook runs.
+    pub fn with_observer(mut self, observer: impl PoolObserver<T> + Send + Sync + 'static) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// Sets when recycled objects are reset with
+    /// [`PoolAllocator::try_reset`], see [`ResetPolicy`].
+    pub fn with_reset_policy(mut self, policy: ResetPolicy) -> Self {
+        self.reset_policy = AtomicUsize::new(policy as usize);
+        self
+    }
+
+    /// Defers resetting recycled objects to [`Self::reset_idle`], like
+    /// [`ResetPolicy::Deferred`].
+    ///
+    /// Gets no longer call [`PoolAllocator::try_reset`], so neither getting
+    /// nor returning an object pays for resetting it. Objects returned since
+    /// the last call to `reset_idle` are handed out as they were returned.
+    pub fn with_deferred_reset(self) -> Self {
+        self.with_reset_policy(ResetPolicy::Deferred)
+    }
+
+    /// Makes dropping the pool panic if objects it handed out were never
+    /// returned, for example because their guards were leaked with
+    /// `mem::forget`.
+    ///
+    /// The idle objects are still destroyed before the panic. With the `std`
+    /// feature, the check is skipped if the thread is already panicking.
+    pub fn with_drop_check(mut self) -> Self {
+        self.drop_check = true;
+        self
+    }
+
+    /// Takes the objects held in quarantine, oldest first.
+    pub fn take_quarantined(&self) -> Vec<T> {
+        let mut objs = Vec::new();
+        if let Some(quarantine) = &self.quarantine {
+            while let Some(obj) = quarantine.pop() {
+                objs.push(obj);
+            }
+        }
+        objs
+    }
+
     /// Gets an object from the pool.
     ///
     /// If the pool is empty, a new object is created using the allocator.
-    pub fn get(&self) -> RefGuard<P, T> {
-        match self.storage.pop() {
-            Some(mut obj) => {
-                self.allocator.reset(&mut obj);
-                RefGuard::new(obj, self)
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get(&self) -> RefGuard<'_, P, T> {
+        RefGuard::new(self.checkout(), self)
+    }
+
+    /// Gets an object from the pool as a [`ManualGuard`], which must be
+    /// returned with [`Self::put_back`] instead of being dropped.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    ///
+    /// # Dropping the guard
+    ///
+    /// The guard holds no reference to the pool, so dropping it instead of
+    /// putting it back cannot tell the pool. In release builds the object is
+    /// dropped without [`PoolAllocator::on_destroy`], and the pool still
+    /// counts it as live for good: [`Self::stats`] reports it as checked out
+    /// forever and, under `with_max_live`, its place is never freed, so once
+    /// every place is lost this way gets block forever. Debug builds panic
+    /// when a guard is dropped to catch the mistake early.
+    #[inline(always)]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_manual(&self) -> ManualGuard<T> {
+        ManualGuard::new(self.checkout(), self as *const Self as usize)
+    }
+
+    /// Returns an object checked out with [`Self::get_manual`] to the pool.
+    ///
+    /// Unlike dropping a guard, this does not call
+    /// [`PoolAllocator::is_valid`], so the caller must only put back valid
+    /// objects. An object that does not fit in the pool is destroyed.
+    #[inline(always)]
+    pub fn put_back(&self, guard: ManualGuard<T>) {
+        let mut obj = guard.into_obj(self as *const Self as usize);
+        self.stats.returned(1);
+        if !self.reset_returned(&mut obj) {
+            #[cfg(feature = "log")]
+            self.log_event(LogEvent::ResetFailed);
+            return self.destroy(obj);
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_recycle(&obj);
+        }
+        match self.storage.push(obj) {
+            Ok(()) => {
+                #[cfg(feature = "diagnostics")]
+                self.events.record(PoolEventKind::Return, None);
+                #[cfg(feature = "tracing")]
+                self.trace_event(TraceEvent::Return);
+                #[cfg(feature = "std")]
+                self.notify_limit();
+            }
+            Err(obj) => {
+                #[cfg(feature = "log")]
+                self.log_event(LogEvent::Overflow(1));
+                self.stats.overflowed(1);
+                self.destroy(obj);
             }
-            None => RefGuard::new(self.allocator.allocate(), self),
         }
     }
 
+    /// Adds an object created outside the pool, such as one rebuilt from
+    /// network state, to the idle objects of the pool.
+    ///
+    /// The object is checked with [`PoolAllocator::is_valid`] first, and is
+    /// handed back if it is invalid or the pool is full. Under
+    /// [`ResetPolicy::OnReturn`], it is also reset and handed back if that
+    /// fails.
+    pub fn put(&self, mut obj: T) -> Result<(), T> {
+        if !self.allocator.is_valid(&obj) || !self.reset_returned(&mut obj) {
+            return Err(obj);
+        }
+        self.stats.adopted(1);
+        if let Err(obj) = self.storage.push(obj) {
+            self.released(1);
+            return Err(obj);
+        }
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Return, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Return);
+        #[cfg(feature = "std")]
+        self.notify_limit();
+        Ok(())
+    }
+
     /// Gets an object from the pool that holds an arc reference to the owning
     /// pool. Allocated objects are not as efficient as those allocated by
     /// [`Self::get`] method but they are easier to move as they are not limited
     /// by allocator lifetime directly.
     ///
     /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
     pub fn get_rc(self: Arc<Self>) -> RcGuard<P, T> {
-        match self.storage.pop() {
-            Some(mut obj) => {
-                self.allocator.reset(&mut obj);
-                RcGuard::new(obj, &self)
+        RcGuard::new(self.checkout(), &self)
+    }
+
+    /// Gets an object from the pool that holds an arc reference to the owning
+    /// pool, like [`Self::get_rc`] but cloning the reference internally, so
+    /// the caller keeps its own.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_owned(self: &Arc<Self>) -> RcGuard<P, T> {
+        RcGuard::new(self.checkout(), self)
+    }
+
+    /// Gets an object from the pool, failing with [`PoolError::Overloaded`]
+    /// if the load shedding threshold is reached.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_get(&self) -> Result<RefGuard<'_, P, T>, PoolError> {
+        self.check_load()?;
+        Ok(RefGuard::new(self.checkout(), self))
+    }
+
+    /// Gets an object from the pool that holds an arc reference to the owning
+    /// pool, failing with [`PoolError::Overloaded`] if the load shedding
+    /// threshold is reached.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_get_rc(self: Arc<Self>) -> Result<RcGuard<P, T>, PoolError> {
+        self.check_load()?;
+        Ok(RcGuard::new(self.checkout(), &self))
+    }
+
+    /// Runs `f` with an object from the pool and returns the object to the
+    /// pool afterwards.
+    ///
+    /// No guard is built, which makes this the leanest way to use an object
+    /// within a single scope. If `f` panics, the object is dropped instead of
+    /// being returned.
+    #[inline]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut obj = self.checkout();
+        let unwind = ReleaseOnUnwind(self);
+        let result = f(&mut obj);
+        forget(unwind);
+        self.recycle(obj);
+        result
+    }
+
+    /// Runs `f` with an object from the pool like [`Self::with`], failing
+    /// with [`PoolError::Overloaded`] if the load shedding threshold is
+    /// reached.
+    #[inline]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R, PoolError> {
+        self.check_load()?;
+        Ok(self.with(f))
+    }
+
+    /// Runs the async closure `f` with an object from the pool and returns the
+    /// object to the pool once the returned future completes.
+    ///
+    /// The object is held by a guard inside the future, so it is also
+    /// returned if the future is dropped before it completes.
+    pub async fn with_async<R>(&self, f: impl AsyncFnOnce(&mut T) -> R) -> R {
+        let mut obj = self.get();
+        f(&mut obj).await
+    }
+
+    /// Fails if the number of checked out objects reached the load shedding
+    /// threshold.
+    #[inline(always)]
+    fn check_load(&self) -> Result<(), PoolError> {
+        if self.stats.in_use(self.storage.len()) >= self.shed_threshold.load(Ordering::Relaxed) {
+            return Err(PoolError::Overloaded);
+        }
+        Ok(())
+    }
+
+    /// Gets an idle object from the pool, failing with [`PoolError::Empty`]
+    /// instead of allocating if there is none.
+    ///
+    /// This never calls [`PoolAllocator::allocate`], so code on a real-time
+    /// path can rely on a warmed up pool not allocating behind its back.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_or_fail(&self) -> Result<RefGuard<'_, P, T>, PoolError> {
+        let obj = self.take_idle().ok_or(PoolError::Empty)?;
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Checkout, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        Ok(RefGuard::new(obj, self))
+    }
+
+    /// Gets `n` objects from the pool as a [`GuardVec`].
+    ///
+    /// If the pool holds fewer than `n` idle objects, new objects are created
+    /// using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_many(&self, n: usize) -> GuardVec<'_, P, T> {
+        GuardVec {
+            objs: self.checkout_many(n),
+            pool: self,
+        }
+    }
+
+    /// Gets `n` objects from the pool as separate guards.
+    ///
+    /// The idle objects are reserved in a single atomic step, and new objects
+    /// are created using the allocator for the rest. Unlike the objects of
+    /// [`Self::get_many`], each guard can be moved and dropped on its own;
+    /// [`Self::return_batch`] returns them together again.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_batch(&self, n: usize) -> Vec<RefGuard<'_, P, T>> {
+        let mut guards = Vec::with_capacity(n);
+        for obj in self.checkout_many(n) {
+            guards.push(RefGuard::new(obj, self));
+        }
+        guards
+    }
+
+    /// Returns the objects of many guards to the pool in a single batch,
+    /// making them available to other threads in one step instead of one
+    /// step per object.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a guard belongs to another pool.
+    pub fn return_batch<'a>(&'a self, guards: impl IntoIterator<Item = RefGuard<'a, P, T>>) {
+        let mut batch = GuardVec::new(self);
+        for guard in guards {
+            batch.push(guard);
+        }
+    }
+
+    /// Gets `n` objects from the pool, or none at all.
+    ///
+    /// The objects are reserved in a single atomic step, so this either takes
+    /// `n` idle objects or, if fewer than `n` are idle, returns `None` without
+    /// taking any. It only allocates to replace objects that fail to reset.
+    pub fn try_get_n(&self, n: usize) -> Option<GuardVec<'_, P, T>> {
+        if !self.storage.reserve(n) {
+            return None;
+        }
+        self.stats.hit(n);
+        let objs = (0..n).map(|_| self.take_reserved()).collect();
+        Some(GuardVec { objs, pool: self })
+    }
+
+    /// Gets exactly `N` objects from the pool as an array of guards, or none
+    /// at all.
+    ///
+    /// Like [`Self::try_get_n`], this either takes `N` idle objects in a
+    /// single atomic step or returns `None` without taking any, but the guards
+    /// live on the stack instead of in a `Vec`.
+    pub fn get_array<const N: usize>(&self) -> Option<[RefGuard<'_, P, T>; N]> {
+        if !self.storage.reserve(N) {
+            return None;
+        }
+        self.stats.hit(N);
+        Some(core::array::from_fn(|_| {
+            RefGuard::new(self.take_reserved(), self)
+        }))
+    }
+
+    /// Takes `n` objects out of the pool, reserving the idle ones in a single
+    /// step and allocating the rest.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    fn checkout_many(&self, n: usize) -> Vec<T> {
+        let idle = self.storage.reserve_up_to(n);
+        self.stats.hit(idle);
+        let mut objs = Vec::with_capacity(n);
+        for _ in 0..idle {
+            objs.push(self.take_reserved());
+        }
+        for _ in idle..n {
+            objs.push(self.checkout());
+        }
+        objs
+    }
+
+    /// Takes an idle object reserved with `Storage::reserve` and resets it,
+    /// replacing it with a new object if it fails to reset.
+    fn take_reserved(&self) -> T {
+        let mut obj = self.storage.pop_reserved();
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Checkout, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        if self.reset_policy() != ResetPolicy::OnGet || self.allocator.try_reset(&mut obj) {
+            return self.warmed(obj);
+        }
+        #[cfg(feature = "log")]
+        self.log_event(LogEvent::ResetFailed);
+        self.destroy(obj);
+        self.stats.allocated();
+        self.warmed(self.allocate())
+    }
+
+    /// Takes an idle object out of the pool and resets it, or allocates a new
+    /// object if the pool is empty.
+    #[inline(always)]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub(crate) fn checkout(&self) -> T {
+        let obj = match self.take_idle() {
+            Some(obj) => obj,
+            None => self.allocate_fallback(),
+        };
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Checkout, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        obj
+    }
+
+    /// Takes an idle object out of the pool and resets it, destroying the
+    /// idle objects that fail to reset.
+    #[inline(always)]
+    pub(crate) fn take_idle(&self) -> Option<T> {
+        loop {
+            let mut obj = self.storage.pop()?;
+            if self.reset_policy() != ResetPolicy::OnGet || self.allocator.try_reset(&mut obj) {
+                self.stats.hit(1);
+                return Some(self.warmed(obj));
+            }
+            #[cfg(feature = "log")]
+            self.log_event(LogEvent::ResetFailed);
+            self.destroy(obj);
+        }
+    }
+
+    /// Returns when recycled objects are reset.
+    #[inline(always)]
+    fn reset_policy(&self) -> ResetPolicy {
+        ResetPolicy::from_usize(self.reset_policy.load(Ordering::Relaxed))
+    }
+
+    /// Resets a returned object if the pool resets objects on return,
+    /// returning whether it is ready to be stored.
+    #[inline(always)]
+    fn reset_returned(&self, obj: &mut T) -> bool {
+        self.reset_policy() != ResetPolicy::OnReturn || self.allocator.try_reset(obj)
+    }
+
+    /// Warms `obj` if the allocator reports that it is not ready yet.
+    #[inline(always)]
+    fn warmed(&self, mut obj: T) -> T {
+        if !self.allocator.is_warm(&obj) {
+            self.allocator.warm(&mut obj);
+        }
+        obj
+    }
+
+    /// Creates a new object with the allocator, leaving the accounting to the
+    /// caller.
+    #[inline]
+    fn allocate(&self) -> T {
+        let obj = self.allocator.allocate();
+        if let Some(observer) = &self.observer {
+            observer.on_allocate(&obj);
+        }
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Allocate);
+        obj
+    }
+
+    /// Hands an object to [`PoolAllocator::on_destroy`], leaving the
+    /// accounting to the caller.
+    #[inline]
+    fn dispose(&self, obj: T) {
+        if let Some(observer) = &self.observer {
+            observer.on_discard(&obj);
+        }
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Discard);
+        self.allocator.on_destroy(obj);
+    }
+
+    /// Destroys the object in `slot` and replaces it with one checked out of
+    /// the pool, freeing its live slot before the replacement is waited for.
+    pub(crate) fn regenerate(&self, slot: &mut MaybeUninit<T>) {
+        // The slot is empty until the replacement is written.
+        let abort = AbortOnUnwind;
+        // Safety: the slot holds an object, and is refilled below.
+        self.destroy(unsafe { slot.assume_init_read() });
+        slot.write(self.checkout());
+        forget(abort);
+    }
+
+    /// Destroys an object that is leaving the pool for good.
+    #[cold]
+    fn destroy(&self, obj: T) {
+        self.dispose(obj);
+        self.released(1);
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Drop, None);
+    }
+
+    /// Records objects that left the pool for good, making room for a get
+    /// waiting on the limit set by `with_max_live`.
+    #[inline]
+    pub(crate) fn released(&self, n: usize) {
+        self.stats.released(n);
+        #[cfg(feature = "std")]
+        self.notify_limit();
+    }
+
+    /// Wakes the gets waiting on the live object limit, if the pool has one.
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    fn notify_limit(&self) {
+        if let Some(limit) = &self.live_limit {
+            limit.notify();
+        }
+    }
+
+    /// Records an object that left the pool for good without being
+    /// destroyed by it.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn forget_one(&self) {
+        self.released(1);
+    }
+
+    /// Quarantines an object that failed validation, or destroys it if the
+    /// quarantine is full or disabled, leaving the accounting to the caller.
+    #[cold]
+    fn discard_invalid(&self, obj: T) -> ReturnStatus {
+        self.stats.invalid();
+        if let Some(observer) = &self.observer {
+            observer.on_validation_failure(&obj);
+        }
+        let obj = match &self.quarantine {
+            Some(quarantine) => match quarantine.push(obj) {
+                Ok(()) => return ReturnStatus::Quarantined,
+                Err(obj) => obj,
+            },
+            None => obj,
+        };
+        #[cfg(feature = "log")]
+        self.log_event(LogEvent::Invalid);
+        self.dispose(obj);
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Drop, None);
+        ReturnStatus::DroppedInvalid
+    }
+
+    /// Logs a notable event of the pool at its log level.
+    #[cfg(feature = "log")]
+    #[cold]
+    fn log_event(&self, event: LogEvent) {
+        log_event::<T>(self.log_level, self.name, event);
+    }
+
+    /// Emits a trace level event of the pool.
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub(crate) fn trace_event(&self, event: TraceEvent) {
+        trace_event::<T>(self.name, event);
+    }
+
+    /// Records an untagged event in the event log of the pool.
+    #[cfg(feature = "diagnostics")]
+    #[inline]
+    pub(crate) fn record_event(&self, kind: PoolEventKind) {
+        self.events.record(kind, None);
+    }
+
+    /// Allocates a new object because the pool had no idle object to recycle.
+    #[cold]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub(crate) fn allocate_fallback(&self) -> T {
+        #[cfg(feature = "std")]
+        if let Some(limit) = &self.live_limit {
+            // Without a timeout, the wait only ends with an object.
+            return self.allocate_within(limit, None).unwrap();
+        }
+        self.stats.missed();
+        self.allocate_missed(
+            #[cfg(feature = "diagnostics")]
+            Location::caller(),
+        )
+    }
+
+    /// Waits until the pool has an idle object or room under `limit` for a
+    /// new one, giving up after `timeout`.
+    #[cfg(feature = "std")]
+    #[cold]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    fn allocate_within(&self, limit: &LiveLimit, timeout: Option<Duration>) -> Option<T> {
+        #[cfg(feature = "diagnostics")]
+        let caller = Location::caller();
+        limit.wait_for(timeout, || match self.take_idle() {
+            Some(obj) => Some(obj),
+            None => self.stats.try_missed(limit.max()).then(|| {
+                self.allocate_missed(
+                    #[cfg(feature = "diagnostics")]
+                    caller,
+                )
+            }),
+        })
+    }
+
+    /// Creates a new object for a get that found the pool empty, after the
+    /// miss has been counted.
+    fn allocate_missed(
+        &self,
+        #[cfg(feature = "diagnostics")] caller: &'static Location<'static>,
+    ) -> T {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.deny_alloc.check();
+            self.miss_sites.record(caller);
+            self.events.record(PoolEventKind::Allocate, None);
+        }
+        #[cfg(feature = "std")]
+        if let Some(alert) = &self.miss_alert {
+            alert.record();
+        }
+        if self.lazy_prefill.load(Ordering::Relaxed) != 0 {
+            self.run_lazy_prefill();
+        }
+        self.warmed(self.allocate())
+    }
+
+    /// Creates the next batch of objects of a deferred prefill.
+    #[cold]
+    fn run_lazy_prefill(&self) {
+        let Ok(left) =
+            self.lazy_prefill
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| {
+                    (left != 0).then(|| left - left.min(self.prefill_batch))
+                })
+        else {
+            return;
+        };
+        for _ in 0..left.min(self.prefill_batch) {
+            self.stats.allocated();
+            if let Err(obj) = self.storage.push(self.allocate()) {
+                self.destroy(obj);
+                break;
             }
-            None => RcGuard::new(self.allocator.allocate(), &self),
         }
     }
 
+    /// Marks a region of code in which this pool must not allocate new
+    /// objects.
+    ///
+    /// Until the returned guard is dropped, a get that finds the pool empty
+    /// panics instead of falling back to [`PoolAllocator::allocate`]. The
+    /// region applies to every thread using the pool.
+    #[cfg(feature = "diagnostics")]
+    pub fn deny_alloc(&self) -> DenyAllocGuard<'_> {
+        self.deny_alloc.enter()
+    }
+
+    /// Returns the call sites of gets that found the pool empty and had to
+    /// allocate a new object, ordered by the number of misses.
+    ///
+    /// Only the first few dozen distinct call sites are recorded.
+    #[cfg(feature = "diagnostics")]
+    pub fn miss_sites(&self) -> Vec<MissSite> {
+        self.miss_sites.snapshot()
+    }
+
+    /// Gets an object from the pool and attributes it to an owner `tag`,
+    /// such as the name of a subsystem.
+    ///
+    /// The tag is kept on the returned guard and counted in
+    /// [`Self::tag_usage`] until the guard is dropped.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg(feature = "diagnostics")]
+    #[track_caller]
+    pub fn get_tagged(&self, tag: &'static str) -> TaggedGuard<'_, RefGuard<'_, P, T>> {
+        let obj = match self.take_idle() {
+            Some(obj) => obj,
+            None => self.allocate_fallback(),
+        };
+        self.events.record(PoolEventKind::Checkout, Some(tag));
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        TaggedGuard::new(RefGuard::new(obj, self), tag, &self.owner_tags)
+    }
+
+    /// Returns how many objects are checked out under each owner tag given
+    /// to [`Self::get_tagged`], ordered by the number of objects in use.
+    ///
+    /// Only the first few dozen distinct tags are recorded.
+    #[cfg(feature = "diagnostics")]
+    pub fn tag_usage(&self) -> Vec<TagUsage> {
+        self.owner_tags.snapshot()
+    }
+
+    /// Returns the most recent events of the pool, such as checkouts and
+    /// returns, oldest first.
+    ///
+    /// The last few hundred events are kept, which helps with finding out
+    /// what led to a pool running dry. Events can also be dumped from a panic
+    /// hook, as the log is readable while the pool is in use.
+    #[cfg(feature = "diagnostics")]
+    pub fn recent_events(&self) -> Vec<PoolEvent> {
+        self.events.snapshot()
+    }
+
     /// Gets the number of objects currently in the pool.
     ///
     /// Returns the length of the internal storage, indicating the number of
@@ -87,6 +1196,22 @@ impl<P: PoolAllocator<T>, T> Pool<P, T> {
         self.storage.len()
     }
 
+    /// Returns `true` if the pool holds no idle objects.
+    pub fn is_empty(&self) -> bool {
+        self.storage.is_empty()
+    }
+
+    /// Returns `true` if the pool holds as many idle objects as its capacity,
+    /// so that returned objects would be dropped.
+    pub fn is_full(&self) -> bool {
+        self.spare_capacity() == 0
+    }
+
+    /// Returns how many more idle objects the pool can hold.
+    pub fn spare_capacity(&self) -> usize {
+        self.cap().saturating_sub(self.storage.len())
+    }
+
     /// Gets the capacity of the pool.
     ///
     /// Returns the maximum number of objects that the pool can hold. This does
@@ -95,6 +1220,456 @@ impl<P: PoolAllocator<T>, T> Pool<P, T> {
     pub fn cap(&self) -> usize {
         self.storage.capacity()
     }
+
+    /// Returns a snapshot of the state of the pool.
+    ///
+    /// The counters are updated without synchronizing with each other, so a
+    /// snapshot taken while other threads use the pool is approximate.
+    pub fn stats(&self) -> PoolStats {
+        self.stats
+            .snapshot(self.name, self.storage.len(), self.storage.capacity())
+    }
+
+    /// Resets the cumulative counters of [`Self::stats`], `allocated`, `hits`,
+    /// `misses`, `returns`, `dropped_invalid` and `dropped_full`, to zero.
+    ///
+    /// The counters describing the current state of the pool, such as the
+    /// number of live and idle objects, are not affected.
+    pub fn reset_stats(&self) {
+        self.stats.reset();
+    }
+
+    /// Returns the number of objects that are checked out of the pool and
+    /// not returned yet.
+    ///
+    /// Unlike [`Self::len`], which counts idle objects, this counts the
+    /// objects held by guards, so a number that keeps growing points at
+    /// guards that are never dropped. Objects taken out for good with
+    /// `into_inner` or `leak` are not counted.
+    pub fn outstanding(&self) -> usize {
+        self.stats.in_use(self.storage.len())
+    }
+
+    /// Returns the fraction of the objects created by the pool that are
+    /// checked out, see [`PoolStats::utilization`].
+    pub fn utilization(&self) -> f64 {
+        self.stats().utilization()
+    }
+
+    /// Returns the fraction of the capacity that is filled with idle objects,
+    /// see [`PoolStats::fill_ratio`].
+    pub fn fill_ratio(&self) -> f64 {
+        self.stats().fill_ratio()
+    }
+
+    /// Returns an iterator over copies of the idle objects in the pool.
+    ///
+    /// This is a debugging aid to inspect what is sitting in the pool without
+    /// checking objects out. See [`Self::peek_idle`] for how the snapshot is
+    /// taken.
+    pub fn iter_idle(&self) -> vec::IntoIter<T>
+    where
+        T: Clone,
+    {
+        self.peek_idle(usize::MAX).into_iter()
+    }
+
+    /// Creates a new pool with a clone of the allocator and clones of the
+    /// idle objects of this pool.
+    ///
+    /// This lets several pools, for example one per shard, be spawned from a
+    /// single warmed template pool instead of each warming up on its own.
+    pub fn duplicate(&self) -> Self
+    where
+        P: Clone,
+        T: Clone,
+    {
+        let idle = self.peek_idle(usize::MAX);
+        Self::from_parts(self.cap(), self.allocator.clone(), idle.into_iter())
+    }
+
+    /// Destroys idle objects with [`PoolAllocator::on_destroy`] until at most
+    /// `count` are left, returning how many were destroyed.
+    ///
+    /// After a burst of traffic, this releases the memory of the objects the
+    /// pool no longer needs, while keeping its capacity for the next burst.
+    ///
+    /// Objects are destroyed in the order gets would hand them out, so the
+    /// objects kept are the ones the pool would reuse last. That order only
+    /// follows the order of returns within each block of the storage, so the
+    /// objects kept are not necessarily the most recently returned ones. A
+    /// pool built [`Self::with_lifo`] destroys the objects returned longest
+    /// ago first, and keeps the most recently returned ones.
+    ///
+    /// Objects that [`PoolAllocator::on_trim`] keeps stay in the pool after
+    /// releasing their memory, and are not counted.
+    pub fn shrink_to(&self, count: usize) -> usize {
+        self.trim_idle(count)
+    }
+
+    /// Destroys every idle object in the pool with
+    /// [`PoolAllocator::on_destroy`], releasing their memory.
+    pub fn clear(&self) {
+        self.drop_idle(0);
+    }
+
+    /// Keeps only the idle objects for which `f` returns `true`, destroying
+    /// the others, and returns how many were destroyed.
+    ///
+    /// This flushes idle objects that no longer match a configuration changed
+    /// at runtime. Checked out objects are not affected.
+    pub fn retain(&self, mut f: impl FnMut(&T) -> bool) -> usize {
+        let mut destroyed = 0;
+        self.cycle_idle(|obj| {
+            if f(&obj) {
+                return Some(obj);
+            }
+            destroyed += 1;
+            self.destroy(obj);
+            None
+        });
+        destroyed
+    }
+
+    /// Checks every idle object with [`PoolAllocator::is_valid`] again and
+    /// discards the objects that fail, returning how many were discarded.
+    ///
+    /// Failing objects are handled like invalid returns, so they are
+    /// quarantined if the pool has room in its quarantine.
+    pub fn purge_invalid(&self) -> usize {
+        let mut purged = 0;
+        self.cycle_idle(|obj| {
+            if self.allocator.is_valid(&obj) {
+                return Some(obj);
+            }
+            purged += 1;
+            self.released(1);
+            self.discard_invalid(obj);
+            None
+        });
+        purged
+    }
+
+    /// Takes the idle objects out of the pool, in the order they would be
+    /// handed out, for example to hand them to another subsystem at shutdown.
+    ///
+    /// The objects are taken as the iterator advances, up to the number of
+    /// idle objects when this is called, and belong to the caller from then
+    /// on, so [`PoolAllocator::on_destroy`] is not called for them.
+    pub fn drain(&self) -> impl Iterator<Item = T> + '_ {
+        (0..self.len()).map_while(move |_| {
+            let obj = self.storage.pop()?;
+            self.released(1);
+            Some(obj)
+        })
+    }
+
+    /// Moves the idle objects of `other` into this pool and drops `other`,
+    /// returning how many objects were moved.
+    ///
+    /// Each object is added like with [`Self::put`]. Objects that are
+    /// invalid or do not fit are destroyed by the allocator of `other`, like
+    /// the rest of `other` when it is dropped. This folds temporary pools,
+    /// such as per-core shards, back into a long-lived pool.
+    pub fn absorb<Q: PoolAllocator<T>>(&self, other: Pool<Q, T>) -> usize {
+        let mut absorbed = 0;
+        for obj in other.drain() {
+            match self.put(obj) {
+                Ok(()) => absorbed += 1,
+                Err(obj) => other.dispose(obj),
+            }
+        }
+        absorbed
+    }
+
+    /// Moves up to `n` idle objects into a new [`LocalPool`] with room for
+    /// `n` objects and a clone of the allocator.
+    ///
+    /// A worker thread can take a private, already warm slice of a shared
+    /// pool at startup this way. When it is done, it can hand the objects
+    /// back by iterating over the local pool and passing them to
+    /// [`Self::put`].
+    pub fn split_off(&self, n: usize) -> LocalPool<P, T>
+    where
+        P: Clone,
+    {
+        let idle = self.drain().take(n).collect();
+        LocalPool::with_objects(n, self.allocator.clone(), idle)
+    }
+
+    /// Returns copies of the next `n` idle objects in the pool, in the order
+    /// they would be handed out.
+    ///
+    /// The concurrent storage cannot be iterated in place, so the idle objects
+    /// are briefly taken out of the pool, cloned, and pushed back in their
+    /// original order. Gets from other threads during the snapshot may allocate
+    /// new objects instead of recycling.
+    pub fn peek_idle(&self, n: usize) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.with_idle(|idle| idle.iter().take(n).cloned().collect())
+    }
+
+    /// Returns an object to the pool, destroying it if it is invalid or does
+    /// not fit.
+    #[inline]
+    pub(crate) fn recycle(&self, mut obj: T) -> ReturnStatus {
+        self.stats.returned(1);
+        if !self.allocator.is_valid(&obj) {
+            self.released(1);
+            return self.discard_invalid(obj);
+        }
+        if !self.reset_returned(&mut obj) {
+            #[cfg(feature = "log")]
+            self.log_event(LogEvent::ResetFailed);
+            self.destroy(obj);
+            return ReturnStatus::DroppedInvalid;
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_recycle(&obj);
+        }
+        match self.storage.push(obj) {
+            Ok(()) => {
+                #[cfg(feature = "diagnostics")]
+                self.events.record(PoolEventKind::Return, None);
+                #[cfg(feature = "tracing")]
+                self.trace_event(TraceEvent::Return);
+                #[cfg(feature = "std")]
+                self.notify_limit();
+                ReturnStatus::Returned
+            }
+            Err(obj) => {
+                #[cfg(feature = "log")]
+                self.log_event(LogEvent::Overflow(1));
+                self.stats.overflowed(1);
+                self.destroy(obj);
+                ReturnStatus::DroppedFull
+            }
+        }
+    }
+
+    /// Returns valid objects to the pool in a single batch, destroying the
+    /// invalid ones and those that do not fit.
+    fn recycle_many(&self, objs: impl ExactSizeIterator<Item = T>) {
+        let returned = objs.len();
+        self.stats.returned(returned);
+        let mut valid = objs.filter_map(|mut obj| {
+            if !self.allocator.is_valid(&obj) {
+                self.discard_invalid(obj);
+                return None;
+            }
+            if !self.reset_returned(&mut obj) {
+                #[cfg(feature = "log")]
+                self.log_event(LogEvent::ResetFailed);
+                self.dispose(obj);
+                #[cfg(feature = "diagnostics")]
+                self.events.record(PoolEventKind::Drop, None);
+                return None;
+            }
+            if let Some(observer) = &self.observer {
+                observer.on_recycle(&obj);
+            }
+            Some(obj)
+        });
+        let (pushed, rejected) = self.storage.push_many(&mut valid);
+        let mut overflow = 0;
+        rejected.into_iter().chain(valid).for_each(|obj| {
+            overflow += 1;
+            self.dispose(obj);
+            #[cfg(feature = "diagnostics")]
+            self.events.record(PoolEventKind::Drop, None);
+        });
+        #[cfg(feature = "log")]
+        if overflow != 0 {
+            self.log_event(LogEvent::Overflow(overflow));
+        }
+        self.stats.overflowed(overflow);
+        self.released(returned - pushed);
+        #[cfg(feature = "diagnostics")]
+        for _ in 0..pushed {
+            self.events.record(PoolEventKind::Return, None);
+        }
+        #[cfg(feature = "tracing")]
+        for _ in 0..pushed {
+            self.trace_event(TraceEvent::Return);
+        }
+    }
+
+    /// Trims idle objects from the cold end of the storage until at most
+    /// `keep` are left, returning how many were destroyed.
+    ///
+    /// Objects that the allocator keeps with [`PoolAllocator::on_trim`] are
+    /// pushed back once the trimming is done, so each is trimmed once.
+    pub(crate) fn trim_idle(&self, keep: usize) -> usize {
+        let mut kept = Vec::new();
+        let mut dropped = 0;
+        while self.storage.len() > keep {
+            let Some(mut obj) = self.storage.pop_cold() else {
+                break;
+            };
+            if self.allocator.on_trim(&mut obj) {
+                kept.push(obj);
+                continue;
+            }
+            self.dispose(obj);
+            dropped += 1;
+        }
+        self.released(dropped);
+        for obj in kept {
+            if let Err(obj) = self.storage.push(obj) {
+                self.destroy(obj);
+            }
+        }
+        dropped
+    }
+
+    /// Destroys idle objects until at most `keep` are left, returning how
+    /// many were destroyed.
+    pub(crate) fn drop_idle(&self, keep: usize) -> usize {
+        let mut dropped = 0;
+        while self.storage.len() > keep {
+            match self.storage.pop_cold() {
+                Some(obj) => self.dispose(obj),
+                None => break,
+            }
+            dropped += 1;
+        }
+        self.released(dropped);
+        dropped
+    }
+
+    /// Passes each idle object to `f` and pushes back the objects it returns
+    /// in their original order, destroying those that no longer fit.
+    ///
+    /// Objects are taken out of the pool one at a time, except from a LIFO
+    /// pool, which would hand the pushed back object out again, so it is
+    /// emptied first and refilled from the bottom.
+    fn cycle_idle(&self, mut f: impl FnMut(T) -> Option<T>) {
+        let put_back = |obj| {
+            if let Some(obj) = f(obj) {
+                if let Err(obj) = self.storage.push(obj) {
+                    self.destroy(obj);
+                }
+            }
+        };
+        let idle = (0..self.storage.len()).map_while(|_| self.storage.pop());
+        if self.storage.is_lifo() {
+            idle.collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .for_each(put_back);
+        } else {
+            idle.for_each(put_back);
+        }
+    }
+
+    /// Takes all idle objects out of the pool, passes them to `f` and pushes
+    /// them back in their original order.
+    pub(crate) fn with_idle<R>(&self, f: impl FnOnce(&mut [T]) -> R) -> R {
+        let mut idle = Vec::with_capacity(self.storage.len());
+        while let Some(obj) = self.storage.pop() {
+            idle.push(obj);
+        }
+        let result = f(&mut idle);
+        if self.storage.is_lifo() {
+            idle.reverse();
+        }
+        let taken = idle.len();
+        let mut idle = idle.into_iter();
+        let (pushed, rejected) = self.storage.push_many(&mut idle);
+        rejected
+            .into_iter()
+            .chain(idle)
+            .for_each(|obj| self.dispose(obj));
+        self.released(taken - pushed);
+        result
+    }
+}
+
+/// Counts the object of a [`Pool::with`] call as released if its closure
+/// panics and the object is dropped during unwinding.
+struct ReleaseOnUnwind<'a, P: PoolAllocator<T>, T>(&'a Pool<P, T>);
+
+/// Destroys the idle objects in the order they would have been handed out,
+/// followed by the quarantined objects, oldest first.
+impl<P: PoolAllocator<T>, T> Drop for Pool<P, T> {
+    fn drop(&mut self) {
+        let outstanding = self.outstanding();
+        #[cfg(feature = "log")]
+        if outstanding != 0 {
+            self.log_event(LogEvent::Leaked(outstanding));
+        }
+        while let Some(obj) = self.storage.pop() {
+            self.dispose(obj);
+        }
+        if let Some(quarantine) = &self.quarantine {
+            while let Some(obj) = quarantine.pop() {
+                self.dispose(obj);
+            }
+        }
+        if self.drop_check && outstanding != 0 {
+            outstanding_on_drop(outstanding);
+        }
+    }
+}
+
+/// Consumes the pool and iterates over its idle objects in the order they
+/// would have been handed out, for example to move the warm objects into
+/// another pool at shutdown.
+///
+/// The objects belong to the caller, so [`PoolAllocator::on_destroy`] is not
+/// called for them. Quarantined objects are destroyed with the pool.
+impl<P: PoolAllocator<T>, T> IntoIterator for Pool<P, T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.drain().collect::<Vec<_>>().into_iter()
+    }
+}
+
+/// Adds already constructed objects to the idle objects of the pool, such as
+/// objects migrated from another pool.
+///
+/// Each object is added like with [`Pool::put`], and the objects it hands
+/// back, because they are invalid or the pool is full, are destroyed.
+impl<P: PoolAllocator<T>, T> Extend<T> for Pool<P, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, objs: I) {
+        for obj in objs {
+            if let Err(obj) = self.put(obj) {
+                self.dispose(obj);
+            }
+        }
+    }
+}
+
+/// Aborts the process if dropped during unwinding, for code that would leave
+/// a guard without an object if it panicked halfway.
+pub(crate) struct AbortOnUnwind;
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        // A panic while unwinding aborts.
+        panic!("pool panicked while replacing a guarded object");
+    }
+}
+
+/// Panics because a pool with a drop check was dropped while `outstanding`
+/// objects were never returned.
+#[cold]
+pub(crate) fn outstanding_on_drop(outstanding: usize) {
+    #[cfg(feature = "std")]
+    if std::thread::panicking() {
+        return;
+    }
+    panic!("pool dropped while {outstanding} of its objects were never returned");
+}
+
+impl<P: PoolAllocator<T>, T> Drop for ReleaseOnUnwind<'_, P, T> {
+    fn drop(&mut self) {
+        self.0.released(1);
+    }
 }
 
 /// A struct representing a guard over an object in the pool.
@@ -109,19 +1684,49 @@ pub struct RefGuard<'a, P: PoolAllocator<T>, T> {
 impl<'a, P: PoolAllocator<T>, T> RefGuard<'a, P, T> {
     /// Creates a new Guard for an object and a reference to the pool it
     /// belongs to.
-    fn new(obj: T, pool: &'a Pool<P, T>) -> Self {
+    pub(crate) fn new(obj: T, pool: &'a Pool<P, T>) -> Self {
         RefGuard {
             obj: MaybeUninit::new(obj),
             pool,
         }
     }
 
+    /// Destroys the held object and replaces it with another object from the
+    /// pool, for error paths that leave the object in an unknown state.
+    ///
+    /// The held object is destroyed before the replacement is checked out, so
+    /// it doesn't count against a live object limit while the replacement is
+    /// waited for. The replacement is recycled from the idle objects of the
+    /// pool, or newly allocated if there are none.
+    ///
+    /// # Panics
+    ///
+    /// The process is aborted if the allocator panics while the object is
+    /// replaced, because the guard would be left without an object.
+    pub fn regenerate(&mut self) {
+        self.pool.regenerate(&mut self.obj);
+    }
+
     /// Consumes the guard and returns the object, without returning it to the
     /// pool.
     ///
     /// This method should be used with caution, as it leads to objects not
     /// being returned to the pool.
     pub fn into_inner(self) -> T {
+        self.pool.released(1);
+        self.take()
+    }
+
+    /// Consumes the guard and returns the object to the pool, reporting
+    /// whether it was stored for reuse or dropped.
+    pub fn return_strict(self) -> ReturnStatus {
+        let pool = self.pool;
+        pool.recycle(self.take())
+    }
+
+    /// Consumes the guard and returns the object, leaving the accounting of
+    /// the pool to the caller.
+    fn take(self) -> T {
         let obj = unsafe { self.obj.as_ptr().read() };
         forget(self);
         obj
@@ -149,16 +1754,9 @@ impl<'a, P: PoolAllocator<T>, T> DerefMut for RefGuard<'a, P, T> {
 /// dropped, unless the object fails validation.
 impl<'a, P: PoolAllocator<T>, T> Drop for RefGuard<'a, P, T> {
     fn drop(&mut self) {
-        if self.pool.allocator.is_valid(self.deref()) {
-            let _ = self
-                .pool
-                .storage
-                .push(unsafe { ptr::read(self.obj.as_mut_ptr()) });
-        } else {
-            unsafe {
-                ptr::drop_in_place(self.obj.as_mut_ptr());
-            }
-        }
+        // Safety: the object is moved out of the guard only once, here.
+        self.pool
+            .recycle(unsafe { ptr::read(self.obj.as_mut_ptr()) });
     }
 }
 
@@ -231,10 +1829,89 @@ impl<'a, P: PoolAllocator<T>, T> AsRef<T> for RefGuard<'a, P, T> {
     }
 }
 
+/// A container of objects checked out from the same pool.
+///
+/// The objects are accessed as a slice and are returned to the pool together
+/// when the container is dropped, which makes them available to other threads
+/// in one step instead of one step per object.
+pub struct GuardVec<'a, P: PoolAllocator<T>, T> {
+    objs: Vec<T>,
+    pool: &'a Pool<P, T>,
+}
+
+impl<'a, P: PoolAllocator<T>, T> GuardVec<'a, P, T> {
+    /// Creates an empty container for objects of the given pool.
+    pub fn new(pool: &'a Pool<P, T>) -> Self {
+        GuardVec {
+            objs: Vec::new(),
+            pool,
+        }
+    }
+
+    /// Moves the object of a guard into the container.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the guard belongs to another pool.
+    pub fn push(&mut self, guard: RefGuard<'a, P, T>) {
+        assert!(
+            ptr::eq(guard.pool, self.pool),
+            "guard belongs to another pool"
+        );
+        self.objs.push(guard.take());
+    }
+
+    /// Removes the last object from the container and returns it as a guard.
+    pub fn pop(&mut self) -> Option<RefGuard<'a, P, T>> {
+        let obj = self.objs.pop()?;
+        Some(RefGuard::new(obj, self.pool))
+    }
+
+    /// Consumes the container and returns the objects, without returning
+    /// them to the pool.
+    pub fn into_inner(mut self) -> Vec<T> {
+        self.pool.released(self.objs.len());
+        core::mem::take(&mut self.objs)
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Deref for GuardVec<'_, P, T> {
+    type Target = [T];
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        &self.objs
+    }
+}
+
+impl<P: PoolAllocator<T>, T> DerefMut for GuardVec<'_, P, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.objs
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Drop for GuardVec<'_, P, T> {
+    fn drop(&mut self) {
+        self.pool.recycle_many(self.objs.drain(..));
+    }
+}
+
+impl<P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for GuardVec<'_, P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
 /// A struct representing a guard over an object in the pool.
 ///
 /// This struct ensures that the object is returned to the pool when it is
 /// dropped.
+///
+/// Unlike [`RefGuard`], this guard owns a reference to its pool, so it is
+/// `'static` whenever `P` and `T` are, and it is `Send` whenever `T` is `Send`
+/// and `P` is `Send + Sync`. It can be moved into `std::thread::spawn`,
+/// `tokio::task::spawn_blocking` or `tokio::spawn`, and the object is returned
+/// to the pool by whichever thread drops the guard.
 pub struct RcGuard<P: PoolAllocator<T>, T> {
     obj: MaybeUninit<T>,
     pool: Arc<Pool<P, T>>,
@@ -250,18 +1927,57 @@ impl<P: PoolAllocator<T>, T> RcGuard<P, T> {
         }
     }
 
+    /// Destroys the held object and replaces it with another object from the
+    /// pool, for error paths that leave the object in an unknown state.
+    ///
+    /// The held object is destroyed before the replacement is checked out, so
+    /// it doesn't count against a live object limit while the replacement is
+    /// waited for. The replacement is recycled from the idle objects of the
+    /// pool, or newly allocated if there are none.
+    ///
+    /// # Panics
+    ///
+    /// The process is aborted if the allocator panics while the object is
+    /// replaced, because the guard would be left without an object.
+    pub fn regenerate(&mut self) {
+        self.pool.regenerate(&mut self.obj);
+    }
+
     /// Consumes the guard and returns the object, without returning it to the
     /// pool.
     ///
     /// This method should be used with caution, as it leads to objects not
     /// being returned to the pool.
     pub fn into_inner(mut self) -> T {
+        self.pool.released(1);
         let obj = unsafe { self.obj.as_ptr().read() };
         // Drop the arc reference
         unsafe { ptr::drop_in_place(&mut self.pool) }
         forget(self);
         obj
     }
+
+    /// Consumes the guard and returns the object to the pool, reporting
+    /// whether it was stored for reuse or dropped.
+    pub fn return_strict(mut self) -> ReturnStatus {
+        let obj = unsafe { self.obj.as_ptr().read() };
+        let status = self.pool.recycle(obj);
+        // Drop the arc reference
+        unsafe { ptr::drop_in_place(&mut self.pool) }
+        forget(self);
+        status
+    }
+
+    /// Consumes the guard and leaks the object, returning a mutable reference
+    /// to it, like [`Box::leak`].
+    ///
+    /// The object is detached from the pool first, like with
+    /// [`Self::into_inner`], so the pool can still be dropped once its other
+    /// references are gone. This is intended for one-time setup objects that
+    /// live for the rest of the program.
+    pub fn leak<'a>(self) -> &'a mut T {
+        Box::leak(Box::new(self.into_inner()))
+    }
 }
 
 impl<P: PoolAllocator<T>, T> Deref for RcGuard<P, T> {
@@ -284,16 +2000,9 @@ impl<P: PoolAllocator<T>, T> DerefMut for RcGuard<P, T> {
 /// dropped, unless the object fails validation.
 impl<P: PoolAllocator<T>, T> Drop for RcGuard<P, T> {
     fn drop(&mut self) {
-        if self.pool.allocator.is_valid(self.deref()) {
-            let _ = self
-                .pool
-                .storage
-                .push(unsafe { ptr::read(self.obj.as_mut_ptr()) });
-        } else {
-            unsafe {
-                ptr::drop_in_place(self.obj.as_mut_ptr());
-            }
-        }
+        // Safety: the object is moved out of the guard only once, here.
+        self.pool
+            .recycle(unsafe { ptr::read(self.obj.as_mut_ptr()) });
     }
 }
 
@@ -0,0 +1,87 @@
+/// The tuning of a pool, for keeping it in the configuration of an
+/// application instead of hard-coding it where the pool is built.
+///
+/// With the `serde` feature, the config can be deserialized. Only `capacity`
+/// is required, the other fields default to a pool built with `new`.
+///
+/// Pools are built from a config with `Pool::from_config` and
+/// `LocalPool::from_config`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(deny_unknown_fields)
+)]
+#[non_exhaustive]
+pub struct PoolConfig {
+    /// Maximum number of idle objects the pool can hold.
+    pub capacity: usize,
+    /// Number of objects the pool is filled with, capped at the capacity.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub prefill: usize,
+    /// If set, the prefill is deferred until the pool is first found empty
+    /// and done in batches of this size, as with `with_lazy_prefill`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub prefill_batch: Option<usize>,
+    /// The load shedding threshold, as set by `with_shed_threshold`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub shed_threshold: Option<usize>,
+    /// How many objects failing validation are kept for inspection, as set by
+    /// `with_quarantine`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub quarantine: usize,
+    /// When recycled objects are reset, as set by `with_reset_policy`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reset: ResetPolicy,
+}
+
+impl PoolConfig {
+    /// Creates a config for a pool of the given capacity, with every other
+    /// setting left at its default.
+    pub const fn new(capacity: usize) -> Self {
+        PoolConfig {
+            capacity,
+            prefill: 0,
+            prefill_batch: None,
+            shed_threshold: None,
+            quarantine: 0,
+            reset: ResetPolicy::OnGet,
+        }
+    }
+}
+
+/// When a pool resets its recycled objects with
+/// [`PoolAllocator::try_reset`](crate::PoolAllocator::try_reset).
+///
+/// Set with `with_reset_policy` on [`crate::Pool`] and [`crate::LocalPool`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ResetPolicy {
+    /// Idle objects are reset as they are checked out, so only objects that
+    /// are used again pay for it.
+    #[default]
+    OnGet,
+    /// Objects are reset as they are returned, keeping the cost off the get
+    /// path. An object that fails to reset is dropped, and `return_strict`
+    /// reports it as [`ReturnStatus::DroppedInvalid`](crate::ReturnStatus).
+    OnReturn,
+    /// Objects are only reset by `reset_idle`, so neither getting nor
+    /// returning an object pays for it, and a pool that never calls
+    /// `reset_idle` skips resetting entirely.
+    Deferred,
+}
+
+impl ResetPolicy {
+    /// Returns the policy stored in an atomic as `policy as usize`.
+    pub(crate) const fn from_usize(policy: usize) -> Self {
+        match policy {
+            0 => ResetPolicy::OnGet,
+            1 => ResetPolicy::OnReturn,
+            _ => ResetPolicy::Deferred,
+        }
+    }
+}
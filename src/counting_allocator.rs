@@ -0,0 +1,159 @@
+use crate::PoolAllocator;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// A snapshot of the calls recorded by a [`CountingAllocator`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocatorCounts {
+    /// Number of calls to [`PoolAllocator::allocate`].
+    pub allocations: usize,
+    /// Number of calls to [`PoolAllocator::reset`].
+    pub resets: usize,
+    /// Number of calls to [`PoolAllocator::is_valid`].
+    pub validations: usize,
+    /// Number of calls to [`PoolAllocator::is_valid`] that returned `false`.
+    pub validation_failures: usize,
+    /// Number of calls to [`PoolAllocator::try_reset`] that returned `false`.
+    pub reset_failures: usize,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    allocations: AtomicUsize,
+    resets: AtomicUsize,
+    validations: AtomicUsize,
+    validation_failures: AtomicUsize,
+    reset_failures: AtomicUsize,
+}
+
+/// A shared handle to the counters of a [`CountingAllocator`].
+///
+/// The handle stays usable after the allocator has been moved into a pool.
+#[derive(Debug, Clone)]
+pub struct AllocatorCounter {
+    counters: Arc<Counters>,
+}
+
+impl AllocatorCounter {
+    /// Returns a snapshot of the calls recorded so far.
+    pub fn counts(&self) -> AllocatorCounts {
+        let counters = &self.counters;
+        AllocatorCounts {
+            allocations: counters.allocations.load(Ordering::Relaxed),
+            resets: counters.resets.load(Ordering::Relaxed),
+            validations: counters.validations.load(Ordering::Relaxed),
+            validation_failures: counters.validation_failures.load(Ordering::Relaxed),
+            reset_failures: counters.reset_failures.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Resets all recorded counters to zero.
+    pub fn reset(&self) {
+        let counters = &self.counters;
+        counters.allocations.store(0, Ordering::Relaxed);
+        counters.resets.store(0, Ordering::Relaxed);
+        counters.validations.store(0, Ordering::Relaxed);
+        counters.validation_failures.store(0, Ordering::Relaxed);
+        counters.reset_failures.store(0, Ordering::Relaxed);
+    }
+}
+
+/// An allocator wrapper that counts the calls made to the wrapped allocator.
+///
+/// This is useful when debugging pool behavior, for example to check how many
+/// objects were freshly allocated instead of being recycled from the pool.
+/// Use [`Self::counter`] to keep a handle to the counters before moving the
+/// allocator into a [`crate::Pool`] or [`crate::LocalPool`].
+#[derive(Debug, Default)]
+pub struct CountingAllocator<A> {
+    inner: A,
+    counters: Arc<Counters>,
+}
+
+impl<A> CountingAllocator<A> {
+    /// Creates a new CountingAllocator wrapping the given allocator.
+    pub fn new(inner: A) -> Self {
+        CountingAllocator {
+            inner,
+            counters: Arc::default(),
+        }
+    }
+
+    /// Returns a handle to the counters of this allocator.
+    pub fn counter(&self) -> AllocatorCounter {
+        AllocatorCounter {
+            counters: self.counters.clone(),
+        }
+    }
+
+    /// Returns a snapshot of the calls recorded so far.
+    pub fn counts(&self) -> AllocatorCounts {
+        self.counter().counts()
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the wrapped allocator.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+impl<A: PoolAllocator<T>, T> PoolAllocator<T> for CountingAllocator<A> {
+    #[inline]
+    fn reset(&self, obj: &mut T) {
+        self.counters.resets.fetch_add(1, Ordering::Relaxed);
+        self.inner.reset(obj)
+    }
+
+    #[inline]
+    fn allocate(&self) -> T {
+        self.counters.allocations.fetch_add(1, Ordering::Relaxed);
+        self.inner.allocate()
+    }
+
+    #[inline]
+    fn is_valid(&self, obj: &T) -> bool {
+        self.counters.validations.fetch_add(1, Ordering::Relaxed);
+        let valid = self.inner.is_valid(obj);
+        if !valid {
+            self.counters
+                .validation_failures
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        valid
+    }
+
+    #[inline]
+    fn try_reset(&self, obj: &mut T) -> bool {
+        self.counters.resets.fetch_add(1, Ordering::Relaxed);
+        let reset = self.inner.try_reset(obj);
+        if !reset {
+            self.counters.reset_failures.fetch_add(1, Ordering::Relaxed);
+        }
+        reset
+    }
+
+    #[inline]
+    fn on_destroy(&self, obj: T) {
+        self.inner.on_destroy(obj)
+    }
+
+    #[inline]
+    fn on_trim(&self, obj: &mut T) -> bool {
+        self.inner.on_trim(obj)
+    }
+
+    #[inline]
+    fn warm(&self, obj: &mut T) {
+        self.inner.warm(obj)
+    }
+
+    #[inline]
+    fn is_warm(&self, obj: &T) -> bool {
+        self.inner.is_warm(obj)
+    }
+}
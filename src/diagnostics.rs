@@ -0,0 +1,451 @@
+use alloc::{boxed::Box, vec::Vec};
+#[cfg(feature = "std")]
+use core::sync::atomic::AtomicU64;
+use core::{
+    cmp::Reverse,
+    fmt,
+    ops::{Deref, DerefMut},
+    panic::Location,
+    ptr,
+    sync::atomic::{fence, AtomicPtr, AtomicUsize, Ordering},
+    time::Duration,
+};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// Tracks the regions of code in which a pool must not allocate.
+#[derive(Debug, Default)]
+pub(crate) struct DenyAlloc {
+    depth: AtomicUsize,
+}
+
+impl DenyAlloc {
+    pub(crate) fn enter(&self) -> DenyAllocGuard<'_> {
+        self.depth.fetch_add(1, Ordering::Relaxed);
+        DenyAllocGuard { state: self }
+    }
+
+    #[inline]
+    pub(crate) fn check(&self) {
+        if self.depth.load(Ordering::Relaxed) != 0 {
+            panic!("pool had to allocate a new object inside a deny_alloc region");
+        }
+    }
+}
+
+/// A guard marking a region of code in which a pool must not allocate.
+///
+/// Created by `deny_alloc` on [`crate::Pool`] and [`crate::LocalPool`]. The
+/// region ends when the guard is dropped. Regions can be nested.
+#[derive(Debug)]
+#[must_use = "the region ends as soon as the guard is dropped"]
+pub struct DenyAllocGuard<'a> {
+    state: &'a DenyAlloc,
+}
+
+impl Drop for DenyAllocGuard<'_> {
+    fn drop(&mut self) {
+        self.state.depth.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Number of distinct call sites recorded by [`MissSites`].
+const MISS_SITE_SLOTS: usize = 64;
+
+/// A lock-free table counting pool misses per call site.
+#[derive(Debug)]
+pub(crate) struct MissSites {
+    sites: [AtomicPtr<Location<'static>>; MISS_SITE_SLOTS],
+    misses: [AtomicUsize; MISS_SITE_SLOTS],
+}
+
+impl Default for MissSites {
+    fn default() -> Self {
+        MissSites {
+            sites: [const { AtomicPtr::new(ptr::null_mut()) }; MISS_SITE_SLOTS],
+            misses: [const { AtomicUsize::new(0) }; MISS_SITE_SLOTS],
+        }
+    }
+}
+
+impl MissSites {
+    pub(crate) fn record(&self, location: &'static Location<'static>) {
+        let new = location as *const Location<'static> as *mut Location<'static>;
+        for (site, misses) in self.sites.iter().zip(&self.misses) {
+            let mut current = site.load(Ordering::Acquire);
+            if current.is_null() {
+                match site.compare_exchange(current, new, Ordering::AcqRel, Ordering::Acquire) {
+                    Ok(_) => current = new,
+                    Err(actual) => current = actual,
+                }
+            }
+            // Safety: non-null slots only ever hold `&'static Location` pointers.
+            if current == new || unsafe { *current == *location } {
+                misses.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<MissSite> {
+        let mut sites: Vec<MissSite> = self
+            .sites
+            .iter()
+            .zip(&self.misses)
+            .filter_map(|(site, misses)| {
+                let location = site.load(Ordering::Acquire);
+                // Safety: non-null slots only ever hold `&'static Location` pointers.
+                unsafe { location.as_ref() }.map(|location| MissSite {
+                    location,
+                    misses: misses.load(Ordering::Relaxed),
+                })
+            })
+            .collect();
+        sites.sort_by_key(|site| Reverse(site.misses));
+        sites
+    }
+}
+
+/// A call site of a get that had to allocate a new object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissSite {
+    /// The location of the get call.
+    pub location: &'static Location<'static>,
+    /// The number of gets at this location that found the pool empty.
+    pub misses: usize,
+}
+
+/// Number of distinct tags recorded by [`OwnerTags`].
+const OWNER_TAG_SLOTS: usize = 64;
+
+/// Slot states of [`OwnerTags`].
+const SLOT_EMPTY: usize = 0;
+const SLOT_CLAIMED: usize = 1;
+const SLOT_READY: usize = 2;
+
+/// A lock-free table counting checkouts per owner tag.
+#[derive(Debug)]
+pub(crate) struct OwnerTags {
+    states: [AtomicUsize; OWNER_TAG_SLOTS],
+    tags: [AtomicPtr<u8>; OWNER_TAG_SLOTS],
+    lens: [AtomicUsize; OWNER_TAG_SLOTS],
+    in_use: [AtomicUsize; OWNER_TAG_SLOTS],
+    checkouts: [AtomicUsize; OWNER_TAG_SLOTS],
+}
+
+impl Default for OwnerTags {
+    fn default() -> Self {
+        OwnerTags {
+            states: [const { AtomicUsize::new(SLOT_EMPTY) }; OWNER_TAG_SLOTS],
+            tags: [const { AtomicPtr::new(ptr::null_mut()) }; OWNER_TAG_SLOTS],
+            lens: [const { AtomicUsize::new(0) }; OWNER_TAG_SLOTS],
+            in_use: [const { AtomicUsize::new(0) }; OWNER_TAG_SLOTS],
+            checkouts: [const { AtomicUsize::new(0) }; OWNER_TAG_SLOTS],
+        }
+    }
+}
+
+impl OwnerTags {
+    /// Returns the tag stored in a ready slot.
+    fn tag(&self, slot: usize) -> &'static str {
+        let ptr = self.tags[slot].load(Ordering::Relaxed);
+        let len = self.lens[slot].load(Ordering::Relaxed);
+        // Safety: ready slots hold the parts of a `&'static str`, written
+        // before the slot was marked ready.
+        unsafe { core::str::from_utf8_unchecked(core::slice::from_raw_parts(ptr, len)) }
+    }
+
+    /// Counts a checkout for `tag`, returning its slot, or `None` if the
+    /// table is full.
+    pub(crate) fn check_out(&self, tag: &'static str) -> Option<usize> {
+        for slot in 0..OWNER_TAG_SLOTS {
+            let mut state = self.states[slot].load(Ordering::Acquire);
+            if state == SLOT_EMPTY
+                && self.states[slot]
+                    .compare_exchange(
+                        SLOT_EMPTY,
+                        SLOT_CLAIMED,
+                        Ordering::Acquire,
+                        Ordering::Acquire,
+                    )
+                    .is_ok()
+            {
+                self.tags[slot].store(tag.as_ptr() as *mut u8, Ordering::Relaxed);
+                self.lens[slot].store(tag.len(), Ordering::Relaxed);
+                self.states[slot].store(SLOT_READY, Ordering::Release);
+                state = SLOT_READY;
+            }
+            while state == SLOT_CLAIMED {
+                core::hint::spin_loop();
+                state = self.states[slot].load(Ordering::Acquire);
+            }
+            if state == SLOT_READY && self.tag(slot) == tag {
+                self.in_use[slot].fetch_add(1, Ordering::Relaxed);
+                self.checkouts[slot].fetch_add(1, Ordering::Relaxed);
+                return Some(slot);
+            }
+        }
+        None
+    }
+
+    /// Counts the return of an object checked out under `slot`.
+    pub(crate) fn check_in(&self, slot: Option<usize>) {
+        if let Some(slot) = slot {
+            self.in_use[slot].fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<TagUsage> {
+        let mut usage: Vec<TagUsage> = (0..OWNER_TAG_SLOTS)
+            .filter(|&slot| self.states[slot].load(Ordering::Acquire) == SLOT_READY)
+            .map(|slot| TagUsage {
+                tag: self.tag(slot),
+                in_use: self.in_use[slot].load(Ordering::Relaxed),
+                checkouts: self.checkouts[slot].load(Ordering::Relaxed),
+            })
+            .collect();
+        usage.sort_by_key(|usage| Reverse(usage.in_use));
+        usage
+    }
+}
+
+/// The objects of a pool checked out under an owner tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TagUsage {
+    /// The tag given to `get_tagged`.
+    pub tag: &'static str,
+    /// The number of objects currently checked out under the tag.
+    pub in_use: usize,
+    /// The number of checkouts under the tag.
+    pub checkouts: usize,
+}
+
+/// A guard over a pooled object that is attributed to an owner tag.
+///
+/// Created by `get_tagged` on [`crate::Pool`] and [`crate::LocalPool`]. The
+/// object is returned to the pool when the guard is dropped, like the guard
+/// it wraps, and the tag shows up in the `tag_usage` breakdown of the pool
+/// until then.
+pub struct TaggedGuard<'a, G> {
+    guard: G,
+    tag: &'static str,
+    slot: Option<usize>,
+    tags: &'a OwnerTags,
+}
+
+impl<'a, G> TaggedGuard<'a, G> {
+    pub(crate) fn new(guard: G, tag: &'static str, tags: &'a OwnerTags) -> Self {
+        TaggedGuard {
+            guard,
+            tag,
+            slot: tags.check_out(tag),
+            tags,
+        }
+    }
+
+    /// Returns the owner tag of the object.
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+}
+
+impl<G: Deref> Deref for TaggedGuard<'_, G> {
+    type Target = G::Target;
+
+    #[inline(always)]
+    fn deref(&self) -> &G::Target {
+        &self.guard
+    }
+}
+
+impl<G: DerefMut> DerefMut for TaggedGuard<'_, G> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut G::Target {
+        &mut self.guard
+    }
+}
+
+impl<G> Drop for TaggedGuard<'_, G> {
+    fn drop(&mut self) {
+        self.tags.check_in(self.slot);
+    }
+}
+
+impl<G: fmt::Debug> fmt::Debug for TaggedGuard<'_, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaggedGuard")
+            .field("tag", &self.tag)
+            .field("object", &self.guard)
+            .finish()
+    }
+}
+
+/// Number of recent events kept by [`EventLog`].
+const EVENT_SLOTS: usize = 256;
+
+/// Stamps of an [`EventSlot`] that hold no event.
+const STAMP_EMPTY: usize = 0;
+const STAMP_BUSY: usize = usize::MAX;
+
+/// A slot of [`EventLog`], guarded by a sequence lock on its stamp.
+#[derive(Debug)]
+struct EventSlot {
+    stamp: AtomicUsize,
+    kind: AtomicUsize,
+    tag: AtomicPtr<u8>,
+    tag_len: AtomicUsize,
+    #[cfg(feature = "std")]
+    nanos: AtomicU64,
+}
+
+/// A lock-free ring of the most recent events of a pool.
+///
+/// Writers racing for the same slot drop their event instead of waiting, so
+/// the log may miss events under heavy contention but never blocks.
+#[derive(Debug)]
+pub(crate) struct EventLog {
+    next: AtomicUsize,
+    slots: Box<[EventSlot]>,
+    #[cfg(feature = "std")]
+    epoch: Instant,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        EventLog {
+            next: AtomicUsize::new(0),
+            slots: (0..EVENT_SLOTS)
+                .map(|_| EventSlot {
+                    stamp: AtomicUsize::new(STAMP_EMPTY),
+                    kind: AtomicUsize::new(0),
+                    tag: AtomicPtr::new(ptr::null_mut()),
+                    tag_len: AtomicUsize::new(0),
+                    #[cfg(feature = "std")]
+                    nanos: AtomicU64::new(0),
+                })
+                .collect(),
+            #[cfg(feature = "std")]
+            epoch: Instant::now(),
+        }
+    }
+}
+
+impl EventLog {
+    #[inline]
+    pub(crate) fn record(&self, kind: PoolEventKind, tag: Option<&'static str>) {
+        let seq = self.next.fetch_add(1, Ordering::Relaxed);
+        let slot = &self.slots[seq % EVENT_SLOTS];
+        let stamp = slot.stamp.load(Ordering::Relaxed);
+        if stamp == STAMP_BUSY
+            || slot
+                .stamp
+                .compare_exchange(stamp, STAMP_BUSY, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+        {
+            return;
+        }
+        fence(Ordering::Release);
+        let (ptr, len) = tag.map_or((ptr::null(), 0), |tag| (tag.as_ptr(), tag.len()));
+        slot.kind.store(kind as usize, Ordering::Relaxed);
+        slot.tag.store(ptr as *mut u8, Ordering::Relaxed);
+        slot.tag_len.store(len, Ordering::Relaxed);
+        #[cfg(feature = "std")]
+        slot.nanos
+            .store(self.epoch.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        // Sequence numbers are stored off by one, so no event has the empty
+        // stamp, and wrap before reaching the busy stamp.
+        let stamp = (seq % (STAMP_BUSY - 1)) + 1;
+        slot.stamp.store(stamp, Ordering::Release);
+    }
+
+    /// Reads the event held by `slot`, or `None` if it is empty or being
+    /// written.
+    fn read(slot: &EventSlot) -> Option<PoolEvent> {
+        let stamp = slot.stamp.load(Ordering::Acquire);
+        if stamp == STAMP_EMPTY || stamp == STAMP_BUSY {
+            return None;
+        }
+        let kind = slot.kind.load(Ordering::Relaxed);
+        let ptr = slot.tag.load(Ordering::Relaxed);
+        let len = slot.tag_len.load(Ordering::Relaxed);
+        #[cfg(feature = "std")]
+        let elapsed = Some(Duration::from_nanos(slot.nanos.load(Ordering::Relaxed)));
+        #[cfg(not(feature = "std"))]
+        let elapsed = None;
+        fence(Ordering::Acquire);
+        if slot.stamp.load(Ordering::Relaxed) != stamp {
+            return None;
+        }
+        // Safety: the stamp did not change while the fields were read, so
+        // they were written together by `record`, from a `&'static str`.
+        let tag = (!ptr.is_null()).then(|| unsafe {
+            core::str::from_utf8_unchecked(core::slice::from_raw_parts(ptr, len))
+        });
+        Some(PoolEvent {
+            seq: stamp - 1,
+            kind: PoolEventKind::from_usize(kind),
+            tag,
+            elapsed,
+        })
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<PoolEvent> {
+        let mut events: Vec<PoolEvent> = self.slots.iter().filter_map(Self::read).collect();
+        events.sort_by_key(|event| event.seq);
+        events
+    }
+}
+
+/// What happened in a [`PoolEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoolEventKind {
+    /// An object was handed out by a get.
+    Checkout,
+    /// A new object was created because the pool was empty.
+    Allocate,
+    /// An object was returned to the pool for reuse.
+    Return,
+    /// An object left the pool for good, because it failed validation or
+    /// did not fit in the pool.
+    Drop,
+}
+
+impl PoolEventKind {
+    fn from_usize(kind: usize) -> Self {
+        match kind {
+            0 => PoolEventKind::Checkout,
+            1 => PoolEventKind::Allocate,
+            2 => PoolEventKind::Return,
+            _ => PoolEventKind::Drop,
+        }
+    }
+}
+
+/// An event recorded in the event log of a pool.
+///
+/// Returned by `recent_events` on [`crate::Pool`] and [`crate::LocalPool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolEvent {
+    /// The position of the event in the history of the pool.
+    pub seq: usize,
+    /// What happened.
+    pub kind: PoolEventKind,
+    /// The owner tag of the checkout, for checkouts made with `get_tagged`.
+    pub tag: Option<&'static str>,
+    /// The time since the pool was created, only recorded with the `std`
+    /// feature.
+    pub elapsed: Option<Duration>,
+}
+
+impl fmt::Display for PoolEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {:?}", self.seq, self.kind)?;
+        if let Some(tag) = self.tag {
+            write!(f, " [{tag}]")?;
+        }
+        if let Some(elapsed) = self.elapsed {
+            write!(f, " at {elapsed:?}")?;
+        }
+        Ok(())
+    }
+}
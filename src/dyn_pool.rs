@@ -0,0 +1,165 @@
+use crate::{Pool, PoolAllocator, PoolHandle, PoolStats, RcGuard};
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    any::{type_name, Any},
+    fmt,
+};
+
+/// A pool whose allocator and object types are erased.
+trait ErasedPool: Send + Sync {
+    fn get(self: Arc<Self>) -> Box<dyn ErasedGuard>;
+    fn stats(&self) -> PoolStats;
+    fn type_name(&self) -> &'static str;
+}
+
+impl<P, T> ErasedPool for Pool<P, T>
+where
+    P: PoolAllocator<T> + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    fn get(self: Arc<Self>) -> Box<dyn ErasedGuard> {
+        Box::new(self.get_rc())
+    }
+
+    fn stats(&self) -> PoolStats {
+        Pool::stats(self)
+    }
+
+    fn type_name(&self) -> &'static str {
+        type_name::<T>()
+    }
+}
+
+/// A guard whose object type is erased.
+trait ErasedGuard: Send {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<P, T> ErasedGuard for RcGuard<P, T>
+where
+    P: PoolAllocator<T> + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        &**self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        &mut **self
+    }
+}
+
+/// A cheaply clonable handle to a shared [`Pool`] whose allocator and object
+/// types are erased.
+///
+/// Pools of different types can be kept together as `DynPool`s, for example
+/// in a map keyed by name. Their [`DynGuard`]s give access to the object
+/// through downcasting when its concrete type is needed:
+///
+/// ```
+/// use opool::{BufferAllocator, DynPool, Pool};
+///
+/// let pools = [
+///     DynPool::new(Pool::new(4, BufferAllocator::new(64))),
+///     DynPool::new(Pool::new(4, BufferAllocator::new(1024))),
+/// ];
+/// let guard = pools[1].get();
+/// assert_eq!(guard.downcast_ref::<Vec<u8>>().map(Vec::len), Some(1024));
+/// assert!(guard.downcast_ref::<String>().is_none());
+/// ```
+#[derive(Clone)]
+pub struct DynPool {
+    pool: Arc<dyn ErasedPool>,
+}
+
+impl DynPool {
+    /// Creates a new type-erased handle owning the given pool.
+    pub fn new<P, T>(pool: Pool<P, T>) -> Self
+    where
+        P: PoolAllocator<T> + Send + Sync + 'static,
+        T: Send + 'static,
+    {
+        Arc::new(pool).into()
+    }
+
+    /// Gets an object from the pool.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    pub fn get(&self) -> DynGuard {
+        DynGuard {
+            guard: self.pool.clone().get(),
+        }
+    }
+
+    /// Returns a snapshot of the stats of the pool.
+    pub fn stats(&self) -> PoolStats {
+        self.pool.stats()
+    }
+
+    /// Returns the name of the type of the pooled objects.
+    pub fn type_name(&self) -> &'static str {
+        self.pool.type_name()
+    }
+}
+
+impl fmt::Debug for DynPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynPool")
+            .field("type_name", &self.type_name())
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+impl<P, T> From<Arc<Pool<P, T>>> for DynPool
+where
+    P: PoolAllocator<T> + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    fn from(pool: Arc<Pool<P, T>>) -> Self {
+        DynPool { pool }
+    }
+}
+
+impl<P, T> From<PoolHandle<P, T>> for DynPool
+where
+    P: PoolAllocator<T> + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    fn from(handle: PoolHandle<P, T>) -> Self {
+        handle.into_inner().into()
+    }
+}
+
+/// A guard for an object checked out of a [`DynPool`].
+///
+/// The object is returned to its pool when the guard is dropped. Its
+/// concrete type is recovered with [`Self::downcast_ref`] and
+/// [`Self::downcast_mut`].
+pub struct DynGuard {
+    guard: Box<dyn ErasedGuard>,
+}
+
+impl DynGuard {
+    /// Returns whether the object is of type `T`.
+    pub fn is<T: Any>(&self) -> bool {
+        self.guard.as_any().is::<T>()
+    }
+
+    /// Returns a reference to the object if it is of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.guard.as_any().downcast_ref()
+    }
+
+    /// Returns a mutable reference to the object if it is of type `T`.
+    pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+        self.guard.as_any_mut().downcast_mut()
+    }
+}
+
+impl fmt::Debug for DynGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynGuard").finish_non_exhaustive()
+    }
+}
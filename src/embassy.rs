@@ -0,0 +1,113 @@
+use crate::{Pool, PoolAllocator, PoolError, RefGuard};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+use embassy_sync::{
+    blocking_mutex::raw::RawMutex,
+    semaphore::{GreedySemaphore, Semaphore, SemaphoreReleaser},
+};
+
+/// A pool that limits how many objects can be checked out at once, for
+/// embassy based async firmware.
+///
+/// Once the limit is reached, [`Self::get`] waits until an object is returned,
+/// so tasks can await buffer availability instead of polling in a loop. The
+/// waiters are woken through an embassy [`GreedySemaphore`] guarded by the
+/// raw mutex `M`, so the pool works with any embassy executor and needs no
+/// allocation beyond the pool itself.
+pub struct EmbassyPool<M: RawMutex, P: PoolAllocator<T>, T> {
+    pool: Pool<P, T>,
+    permits: GreedySemaphore<M>,
+}
+
+impl<M: RawMutex, P: PoolAllocator<T>, T> EmbassyPool<M, P, T> {
+    /// Creates a new EmbassyPool that hands out at most `max_live` objects of
+    /// `pool` at once.
+    pub fn new(pool: Pool<P, T>, max_live: usize) -> Self {
+        EmbassyPool {
+            pool,
+            permits: GreedySemaphore::new(max_live),
+        }
+    }
+
+    /// Gets an object from the pool, waiting until one is available if the
+    /// limit is reached.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    pub async fn get(&self) -> EmbassyGuard<'_, M, P, T> {
+        let permit = match self.permits.acquire(1).await {
+            Ok(permit) => permit,
+            Err(never) => match never {},
+        };
+        EmbassyGuard {
+            guard: self.pool.get(),
+            _permit: permit,
+        }
+    }
+
+    /// Gets an object from the pool, failing with [`PoolError::Overloaded`]
+    /// instead of waiting if the limit is reached.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_get(&self) -> Result<EmbassyGuard<'_, M, P, T>, PoolError> {
+        let permit = self.permits.try_acquire(1).ok_or(PoolError::Overloaded)?;
+        Ok(EmbassyGuard {
+            guard: self.pool.get(),
+            _permit: permit,
+        })
+    }
+
+    /// Returns the pool behind this limit.
+    pub fn pool(&self) -> &Pool<P, T> {
+        &self.pool
+    }
+}
+
+impl<M: RawMutex, P: PoolAllocator<T> + fmt::Debug, T: fmt::Debug> fmt::Debug
+    for EmbassyPool<M, P, T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmbassyPool")
+            .field("pool", &self.pool)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A guard over an object of an [`EmbassyPool`].
+///
+/// Dropping the guard returns the object to the pool and wakes a task waiting
+/// in [`EmbassyPool::get`].
+pub struct EmbassyGuard<'a, M: RawMutex, P: PoolAllocator<T>, T> {
+    guard: RefGuard<'a, P, T>,
+    _permit: SemaphoreReleaser<'a, GreedySemaphore<M>>,
+}
+
+impl<M: RawMutex, P: PoolAllocator<T>, T> EmbassyGuard<'_, M, P, T> {
+    /// Consumes the guard and returns the object, without returning it to the
+    /// pool. Its place in the limit is freed.
+    pub fn into_inner(self) -> T {
+        self.guard.into_inner()
+    }
+}
+
+impl<M: RawMutex, P: PoolAllocator<T>, T> Deref for EmbassyGuard<'_, M, P, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<M: RawMutex, P: PoolAllocator<T>, T> DerefMut for EmbassyGuard<'_, M, P, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<M: RawMutex, P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for EmbassyGuard<'_, M, P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
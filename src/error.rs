@@ -0,0 +1,30 @@
+use core::fmt;
+
+/// An error returned by the fallible pool methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum PoolError {
+    /// The number of objects checked out of the pool reached its load
+    /// shedding threshold.
+    Overloaded,
+    /// A pooled object failed validation by its allocator.
+    Invalid,
+    /// A tenant of a shared pool holds as many objects as its quota allows.
+    QuotaExceeded,
+    /// The pool had no idle object and was not allowed to allocate one.
+    Empty,
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PoolError::Overloaded => f.write_str("pool is overloaded"),
+            PoolError::Invalid => f.write_str("pooled object failed validation"),
+            PoolError::QuotaExceeded => f.write_str("tenant quota exceeded"),
+            PoolError::Empty => f.write_str("pool has no idle object"),
+        }
+    }
+}
+
+impl core::error::Error for PoolError {}
@@ -0,0 +1,167 @@
+use crate::PoolAllocator;
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// When a [`FaultInjectingAllocator`] injects a failure into an operation.
+///
+/// Calls are counted per operation, starting at 1.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Fault {
+    /// Never fail.
+    #[default]
+    Never,
+    /// Fail each call with the given probability, between `0.0` and `1.0`.
+    Rate(f64),
+    /// Fail every n-th call.
+    Every(usize),
+    /// Fail only the n-th call.
+    OnCall(usize),
+}
+
+impl Fault {
+    fn strikes(&self, call: usize, random: u64) -> bool {
+        match *self {
+            Fault::Never => false,
+            Fault::Rate(rate) => (random as f64) < rate * u64::MAX as f64,
+            Fault::Every(n) => n != 0 && call % n == 0,
+            Fault::OnCall(n) => call == n,
+        }
+    }
+}
+
+/// An allocator wrapper that injects failures into the wrapped allocator.
+///
+/// An injected validation failure makes [`PoolAllocator::is_valid`] return
+/// `false`, so the object is dropped instead of returned to the pool, and an
+/// injected reset failure makes [`PoolAllocator::try_reset`] return `false`,
+/// so the object is dropped instead of handed out. Injected allocation
+/// failures, and reset failures of direct calls to [`PoolAllocator::reset`],
+/// panic, which exercises the unwind paths of the code using the pool.
+///
+/// Random failures come from a small generator seeded with
+/// [`Self::with_seed`], so a run can be reproduced as long as the calls happen
+/// in the same order.
+#[derive(Debug, Default)]
+pub struct FaultInjectingAllocator<A> {
+    inner: A,
+    allocate: Fault,
+    reset: Fault,
+    validate: Fault,
+    allocate_calls: AtomicUsize,
+    reset_calls: AtomicUsize,
+    validate_calls: AtomicUsize,
+    rng: AtomicU64,
+}
+
+impl<A> FaultInjectingAllocator<A> {
+    /// Creates a new FaultInjectingAllocator wrapping the given allocator,
+    /// without any fault configured.
+    pub fn new(inner: A) -> Self {
+        FaultInjectingAllocator {
+            inner,
+            allocate: Fault::Never,
+            reset: Fault::Never,
+            validate: Fault::Never,
+            allocate_calls: AtomicUsize::new(0),
+            reset_calls: AtomicUsize::new(0),
+            validate_calls: AtomicUsize::new(0),
+            rng: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets the seed of the generator used by [`Fault::Rate`].
+    pub fn with_seed(self, seed: u64) -> Self {
+        self.rng.store(seed, Ordering::Relaxed);
+        self
+    }
+
+    /// Sets when calls to [`PoolAllocator::allocate`] fail.
+    pub fn fail_allocate(mut self, fault: Fault) -> Self {
+        self.allocate = fault;
+        self
+    }
+
+    /// Sets when calls to [`PoolAllocator::reset`] fail.
+    pub fn fail_reset(mut self, fault: Fault) -> Self {
+        self.reset = fault;
+        self
+    }
+
+    /// Sets when calls to [`PoolAllocator::is_valid`] fail.
+    pub fn fail_validate(mut self, fault: Fault) -> Self {
+        self.validate = fault;
+        self
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the wrapped allocator.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    fn strikes(&self, fault: &Fault, calls: &AtomicUsize) -> bool {
+        let call = calls.fetch_add(1, Ordering::Relaxed) + 1;
+        if *fault == Fault::Never {
+            return false;
+        }
+        // splitmix64
+        let mut z = self
+            .rng
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        fault.strikes(call, z ^ (z >> 31))
+    }
+}
+
+impl<A: PoolAllocator<T>, T> PoolAllocator<T> for FaultInjectingAllocator<A> {
+    #[inline]
+    fn reset(&self, obj: &mut T) {
+        if self.strikes(&self.reset, &self.reset_calls) {
+            panic!("FaultInjectingAllocator: injected reset failure");
+        }
+        self.inner.reset(obj)
+    }
+
+    #[inline]
+    fn allocate(&self) -> T {
+        if self.strikes(&self.allocate, &self.allocate_calls) {
+            panic!("FaultInjectingAllocator: injected allocation failure");
+        }
+        self.inner.allocate()
+    }
+
+    #[inline]
+    fn is_valid(&self, obj: &T) -> bool {
+        !self.strikes(&self.validate, &self.validate_calls) && self.inner.is_valid(obj)
+    }
+
+    #[inline]
+    fn try_reset(&self, obj: &mut T) -> bool {
+        !self.strikes(&self.reset, &self.reset_calls) && self.inner.try_reset(obj)
+    }
+
+    #[inline]
+    fn on_destroy(&self, obj: T) {
+        self.inner.on_destroy(obj)
+    }
+
+    #[inline]
+    fn on_trim(&self, obj: &mut T) -> bool {
+        self.inner.on_trim(obj)
+    }
+
+    #[inline]
+    fn warm(&self, obj: &mut T) {
+        self.inner.warm(obj)
+    }
+
+    #[inline]
+    fn is_warm(&self, obj: &T) -> bool {
+        self.inner.is_warm(obj)
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{
+    GuardVec, LocalGuardVec, LocalPool, Pool, PoolAllocator, PoolStats, RcGuard, RcLocalGuard,
+    RefGuard, RefLocalGuard,
+};
+use defmt::{Format, Formatter};
+
+impl Format for PoolStats {
+    fn format(&self, f: Formatter<'_>) {
+        if let Some(name) = self.name {
+            defmt::write!(f, "{=str}: ", name);
+        }
+        defmt::write!(
+            f,
+            "in use {}/{} ({=f64}%), idle {}/{} ({=f64}%), allocated {}",
+            self.in_use(),
+            self.live,
+            self.utilization() * 100.0,
+            self.idle,
+            self.capacity,
+            self.fill_ratio() * 100.0,
+            self.allocated,
+        )
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Format for Pool<P, T> {
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "Pool {{ {} }}", self.stats())
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Format for LocalPool<P, T> {
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "LocalPool {{ {} }}", self.stats())
+    }
+}
+
+macro_rules! format_guard {
+    ($($guard:ident $(<$lt:lifetime>)?),* $(,)?) => {$(
+        impl<$($lt,)? P: PoolAllocator<T>, T: Format> Format for $guard<$($lt,)? P, T> {
+            fn format(&self, f: Formatter<'_>) {
+                Format::format(&**self, f)
+            }
+        }
+    )*};
+}
+
+format_guard!(
+    RefGuard<'a>,
+    RcGuard,
+    GuardVec<'a>,
+    RefLocalGuard<'a>,
+    RcLocalGuard,
+    LocalGuardVec<'a>,
+);
+
+#[cfg(feature = "embassy")]
+impl<M: embassy_sync::blocking_mutex::raw::RawMutex, P: PoolAllocator<T>, T> Format
+    for crate::EmbassyPool<M, P, T>
+{
+    fn format(&self, f: Formatter<'_>) {
+        defmt::write!(f, "EmbassyPool {{ {} }}", self.pool().stats())
+    }
+}
+
+#[cfg(feature = "embassy")]
+impl<M: embassy_sync::blocking_mutex::raw::RawMutex, P: PoolAllocator<T>, T: Format> Format
+    for crate::EmbassyGuard<'_, M, P, T>
+{
+    fn format(&self, f: Formatter<'_>) {
+        Format::format(&**self, f)
+    }
+}
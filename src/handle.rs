@@ -0,0 +1,80 @@
+use crate::{Pool, PoolAllocator, PoolError, RcGuard};
+use alloc::sync::Arc;
+use core::ops::Deref;
+
+/// A cheaply clonable handle to a shared [`Pool`].
+///
+/// All clones of a handle share the same pool. The handle is `Send + Sync`
+/// whenever the pool can be shared between threads, so it can be stored
+/// directly in application state and other places that require `Clone`.
+///
+/// Objects are obtained with [`Self::get`], which returns owned [`RcGuard`]s.
+/// The handle dereferences to the pool, so the rest of the [`Pool`] methods
+/// can be called on it directly.
+#[derive(Debug)]
+pub struct PoolHandle<P: PoolAllocator<T>, T> {
+    pool: Arc<Pool<P, T>>,
+}
+
+impl<P: PoolAllocator<T>, T> PoolHandle<P, T> {
+    /// Creates a new handle owning the given pool.
+    pub fn new(pool: Pool<P, T>) -> Self {
+        PoolHandle {
+            pool: Arc::new(pool),
+        }
+    }
+
+    /// Gets an object from the pool.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get(&self) -> RcGuard<P, T> {
+        self.pool.get_owned()
+    }
+
+    /// Gets an object from the pool, failing with [`PoolError::Overloaded`]
+    /// if the load shedding threshold is reached.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_get(&self) -> Result<RcGuard<P, T>, PoolError> {
+        self.pool.clone().try_get_rc()
+    }
+
+    /// Returns the shared pool behind this handle.
+    pub fn pool(&self) -> &Arc<Pool<P, T>> {
+        &self.pool
+    }
+
+    /// Consumes the handle and returns the shared pool behind it.
+    pub fn into_inner(self) -> Arc<Pool<P, T>> {
+        self.pool
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Clone for PoolHandle<P, T> {
+    fn clone(&self) -> Self {
+        PoolHandle {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Deref for PoolHandle<P, T> {
+    type Target = Pool<P, T>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Pool<P, T> {
+        &self.pool
+    }
+}
+
+impl<P: PoolAllocator<T>, T> From<Pool<P, T>> for PoolHandle<P, T> {
+    fn from(pool: Pool<P, T>) -> Self {
+        Self::new(pool)
+    }
+}
+
+impl<P: PoolAllocator<T>, T> From<Arc<Pool<P, T>>> for PoolHandle<P, T> {
+    fn from(pool: Arc<Pool<P, T>>) -> Self {
+        PoolHandle { pool }
+    }
+}
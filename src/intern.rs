@@ -0,0 +1,200 @@
+use alloc::sync::Arc;
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+use std::{
+    collections::HashSet,
+    sync::{Mutex, PoisonError},
+};
+
+/// The number of entries below which the pool never purges.
+const MIN_PURGE_LEN: usize = 32;
+
+/// A pool that deduplicates equal objects.
+///
+/// [`Self::get_or_intern`] returns an [`Interned`] handle to the one canonical
+/// instance of a value, storing a copy of the value first if the pool does not
+/// hold an equal one yet. Instances that are no longer referenced by any
+/// handle are reclaimed by [`Self::purge`], which also runs automatically
+/// whenever the number of stored instances doubles.
+///
+/// ```
+/// use opool::InternPool;
+///
+/// let pool = InternPool::new();
+/// let a = pool.get_or_intern(&String::from("hello"));
+/// let b = pool.intern(String::from("hello"));
+/// assert!(a.ptr_eq(&b));
+/// assert_eq!(pool.len(), 1);
+/// ```
+pub struct InternPool<T> {
+    inner: Mutex<Inner<T>>,
+}
+
+struct Inner<T> {
+    set: HashSet<Arc<T>>,
+    purge_len: usize,
+}
+
+impl<T: Hash + Eq> InternPool<T> {
+    /// Creates a new, empty InternPool.
+    pub fn new() -> Self {
+        InternPool {
+            inner: Mutex::new(Inner {
+                set: HashSet::new(),
+                purge_len: MIN_PURGE_LEN,
+            }),
+        }
+    }
+
+    /// Returns a handle to the canonical instance equal to `value`, cloning
+    /// `value` into the pool if there is none yet.
+    pub fn get_or_intern(&self, value: &T) -> Interned<T>
+    where
+        T: Clone,
+    {
+        let mut inner = self.lock();
+        if let Some(obj) = inner.set.get(value) {
+            return Interned(obj.clone());
+        }
+        inner.insert(Arc::new(value.clone()))
+    }
+
+    /// Returns a handle to the canonical instance equal to `value`, moving
+    /// `value` into the pool if there is none yet.
+    pub fn intern(&self, value: T) -> Interned<T> {
+        let mut inner = self.lock();
+        if let Some(obj) = inner.set.get(&value) {
+            return Interned(obj.clone());
+        }
+        inner.insert(Arc::new(value))
+    }
+
+    /// Drops the instances that are no longer referenced by any handle,
+    /// returning how many were dropped.
+    pub fn purge(&self) -> usize {
+        self.lock().purge()
+    }
+
+    /// Returns the number of instances stored in the pool, including those
+    /// that are unreferenced but not purged yet.
+    pub fn len(&self) -> usize {
+        self.lock().set.len()
+    }
+
+    /// Returns `true` if the pool stores no instances.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner<T>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Hash + Eq> Inner<T> {
+    fn insert(&mut self, obj: Arc<T>) -> Interned<T> {
+        if self.set.len() >= self.purge_len {
+            self.purge();
+            self.purge_len = (self.set.len() * 2).max(MIN_PURGE_LEN);
+        }
+        self.set.insert(obj.clone());
+        Interned(obj)
+    }
+
+    fn purge(&mut self) -> usize {
+        let len = self.set.len();
+        self.set.retain(|obj| Arc::strong_count(obj) > 1);
+        len - self.set.len()
+    }
+}
+
+impl<T: Hash + Eq> Default for InternPool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for InternPool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self
+            .inner
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set
+            .len();
+        f.debug_struct("InternPool").field("len", &len).finish()
+    }
+}
+
+/// A shared handle to a canonical instance stored in an [`InternPool`].
+///
+/// Handles of the same pool that compare equal point to the same instance, so
+/// [`Self::ptr_eq`] can be used as a cheap equality check between them.
+pub struct Interned<T>(Arc<T>);
+
+impl<T> Interned<T> {
+    /// Returns `true` if both handles point to the same instance.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T> Clone for Interned<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Interned(self.0.clone())
+    }
+}
+
+impl<T> Deref for Interned<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> Borrow<T> for Interned<T> {
+    fn borrow(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for Interned<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: PartialEq> PartialEq for Interned<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr_eq(other) || *self.0 == *other.0
+    }
+}
+
+impl<T: Eq> Eq for Interned<T> {}
+
+impl<T: Hash> Hash for Interned<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (*self.0).hash(state)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (*self.0).fmt(f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (*self.0).fmt(f)
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{PoolAllocator, PoolHandle, RcGuard};
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use std::sync::Mutex;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// A [`tower_layer::Layer`] that checks out an object from a pool for every
+/// request.
+///
+/// The object is inserted into the request extensions as a [`Pooled`] and is
+/// returned to the pool as soon as the response future completes or is
+/// dropped, even if the handler kept a clone of the [`Pooled`] around.
+#[derive(Debug)]
+pub struct PoolLayer<P: PoolAllocator<T>, T> {
+    pool: PoolHandle<P, T>,
+}
+
+impl<P: PoolAllocator<T>, T> PoolLayer<P, T> {
+    /// Creates a new PoolLayer that checks out objects from `pool`.
+    pub fn new(pool: impl Into<PoolHandle<P, T>>) -> Self {
+        PoolLayer { pool: pool.into() }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Clone for PoolLayer<P, T> {
+    fn clone(&self) -> Self {
+        PoolLayer {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<S, P: PoolAllocator<T>, T> Layer<S> for PoolLayer<P, T> {
+    type Service = PoolService<S, P, T>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PoolService {
+            inner,
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+/// The service produced by [`PoolLayer`].
+#[derive(Debug)]
+pub struct PoolService<S, P: PoolAllocator<T>, T> {
+    inner: S,
+    pool: PoolHandle<P, T>,
+}
+
+impl<S: Clone, P: PoolAllocator<T>, T> Clone for PoolService<S, P, T> {
+    fn clone(&self) -> Self {
+        PoolService {
+            inner: self.inner.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<S, B, P, T> Service<http::Request<B>> for PoolService<S, P, T>
+where
+    S: Service<http::Request<B>>,
+    P: PoolAllocator<T> + Send + Sync + 'static,
+    T: Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = PoolFuture<S::Future, P, T>;
+
+    #[inline]
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let pooled = Pooled {
+            slot: Arc::new(Mutex::new(Some(self.pool.get()))),
+        };
+        req.extensions_mut().insert(pooled.clone());
+        PoolFuture {
+            inner: self.inner.call(req),
+            pooled,
+        }
+    }
+}
+
+/// An object checked out by [`PoolLayer`] for the duration of a request.
+///
+/// Handlers find it in the request extensions. Once the response future
+/// completes, the object is returned to the pool and [`Self::with`] returns
+/// `None`.
+pub struct Pooled<P: PoolAllocator<T>, T> {
+    slot: Arc<Mutex<Option<RcGuard<P, T>>>>,
+}
+
+impl<P: PoolAllocator<T>, T> Pooled<P, T> {
+    /// Runs `f` with the pooled object, or returns `None` if the object was
+    /// already returned to the pool.
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let mut slot = self.slot.lock().unwrap_or_else(|e| e.into_inner());
+        slot.as_mut().map(|guard| f(guard))
+    }
+
+    /// Returns the object to the pool.
+    fn release(&self) {
+        let guard = self.slot.lock().unwrap_or_else(|e| e.into_inner()).take();
+        drop(guard);
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Clone for Pooled<P, T> {
+    fn clone(&self) -> Self {
+        Pooled {
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for Pooled<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_tuple("Pooled");
+        match self.slot.try_lock() {
+            Ok(slot) => d.field(&slot.as_deref()),
+            Err(_) => d.field(&format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+/// The response future of [`PoolService`].
+///
+/// It returns the request's pooled object to the pool when it completes or is
+/// dropped.
+pub struct PoolFuture<F, P: PoolAllocator<T>, T> {
+    inner: F,
+    pooled: Pooled<P, T>,
+}
+
+impl<F: Future, P: PoolAllocator<T>, T> Future for PoolFuture<F, P, T> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `inner` is never moved out of the pinned future.
+        let this = unsafe { self.get_unchecked_mut() };
+        let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+        let output = ready!(inner.poll(cx));
+        this.pooled.release();
+        Poll::Ready(output)
+    }
+}
+
+impl<F, P: PoolAllocator<T>, T> Drop for PoolFuture<F, P, T> {
+    fn drop(&mut self) {
+        self.pooled.release();
+    }
+}
+
+impl<F, P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for PoolFuture<F, P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolFuture")
+            .field("pooled", &self.pooled)
+            .finish_non_exhaustive()
+    }
+}
@@ -2,10 +2,132 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs, missing_debug_implementations)]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+#[cfg(feature = "std")]
+mod alert;
+mod allocator_cell;
+#[cfg(feature = "tokio")]
+mod async_observer;
+#[cfg(feature = "tokio")]
+mod async_return;
+#[cfg(feature = "bb8")]
+mod bb8_manager;
+#[cfg(feature = "std")]
+mod blocking;
+mod buffer;
 mod concurrent;
+mod config;
+mod counting_allocator;
+#[cfg(feature = "diagnostics")]
+mod diagnostics;
+mod dyn_pool;
+#[cfg(feature = "embassy")]
+mod embassy;
+mod error;
+#[cfg(feature = "fault-injection")]
+mod fault_injection;
+#[cfg(feature = "defmt")]
+mod format;
+mod handle;
+#[cfg(feature = "std")]
+mod intern;
+#[cfg(feature = "tower")]
+mod layer;
+#[cfg(feature = "tokio")]
+mod limited;
+#[cfg(all(feature = "linux", target_os = "linux"))]
+mod linux;
+#[cfg(feature = "std")]
+mod local_key;
+#[cfg(feature = "log")]
+mod logging;
+mod manual;
+mod noop;
+mod object_pool;
+mod observer;
+#[cfg(feature = "rayon")]
+mod par;
+mod policy;
 mod pool_allocator;
+mod pressure;
+#[cfg(all(feature = "pressure", target_os = "linux"))]
+mod psi;
+mod queue;
+#[cfg(feature = "std")]
+mod quota;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+mod sharded;
+mod stats;
+mod status;
+mod storage;
+mod sync;
+mod template;
+#[cfg(feature = "test-util")]
+mod test_util;
 mod thread_local;
+#[cfg(feature = "tracing")]
+mod trace;
+mod uninit;
+#[cfg(feature = "std")]
+mod watchdog;
+mod weak;
+#[cfg(feature = "std")]
+mod window;
 
+#[cfg(feature = "std")]
+pub use alert::*;
+#[cfg(feature = "tokio")]
+pub use async_observer::*;
+#[cfg(feature = "tokio")]
+pub use async_return::*;
+#[cfg(feature = "bb8")]
+pub use bb8_manager::*;
+pub use buffer::*;
 pub use concurrent::*;
+pub use config::*;
+pub use counting_allocator::*;
+#[cfg(feature = "diagnostics")]
+pub use diagnostics::*;
+pub use dyn_pool::*;
+#[cfg(feature = "embassy")]
+pub use embassy::*;
+pub use error::*;
+#[cfg(feature = "fault-injection")]
+pub use fault_injection::*;
+pub use handle::*;
+#[cfg(feature = "std")]
+pub use intern::*;
+#[cfg(feature = "tower")]
+pub use layer::*;
+#[cfg(feature = "tokio")]
+pub use limited::*;
+#[cfg(feature = "std")]
+pub use local_key::*;
+pub use manual::*;
+pub use noop::*;
+pub use object_pool::*;
+pub use observer::*;
+pub use policy::*;
 pub use pool_allocator::*;
+pub use pressure::*;
+#[cfg(all(feature = "pressure", target_os = "linux"))]
+pub use psi::*;
+#[cfg(feature = "std")]
+pub use quota::*;
+#[cfg(feature = "std")]
+pub use sharded::*;
+pub use stats::*;
+pub use status::*;
+pub use template::*;
+#[cfg(feature = "test-util")]
+pub use test_util::*;
 pub use thread_local::*;
+pub use uninit::*;
+#[cfg(feature = "std")]
+pub use watchdog::*;
+pub use weak::*;
+#[cfg(feature = "std")]
+pub use window::*;
@@ -0,0 +1,132 @@
+use crate::{Pool, PoolAllocator, PoolError, RcGuard};
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// A pool that limits how many objects can be checked out at once, for async
+/// code.
+///
+/// Once the limit is reached, [`Self::get`] waits until an object is returned.
+/// Waiters are queued fairly by a [`tokio::sync::Semaphore`], so dropping a
+/// pending `get` future gives up its place in the queue without losing a
+/// permit.
+///
+/// Clones share the same pool and limit.
+pub struct LimitedPool<P: PoolAllocator<T>, T> {
+    pool: Arc<Pool<P, T>>,
+    permits: Arc<Semaphore>,
+}
+
+impl<P: PoolAllocator<T>, T> LimitedPool<P, T> {
+    /// Creates a new LimitedPool that hands out at most `max_live` objects of
+    /// `pool` at once.
+    pub fn new(pool: Pool<P, T>, max_live: usize) -> Self {
+        LimitedPool {
+            pool: Arc::new(pool),
+            permits: Arc::new(Semaphore::new(max_live)),
+        }
+    }
+
+    /// Gets an object from the pool, waiting until one is available if the
+    /// limit is reached.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    pub async fn get(&self) -> LimitedGuard<P, T> {
+        let permit = self
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the semaphore is never closed");
+        LimitedGuard {
+            guard: self.pool.clone().get_rc(),
+            _permit: permit,
+        }
+    }
+
+    /// Gets an object from the pool, failing with [`PoolError::Overloaded`]
+    /// instead of waiting if the limit is reached.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_get(&self) -> Result<LimitedGuard<P, T>, PoolError> {
+        let permit = self
+            .permits
+            .clone()
+            .try_acquire_owned()
+            .map_err(|_| PoolError::Overloaded)?;
+        Ok(LimitedGuard {
+            guard: self.pool.clone().get_rc(),
+            _permit: permit,
+        })
+    }
+
+    /// Returns how many more objects can be checked out before the limit is
+    /// reached.
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    /// Returns the pool behind this limit.
+    pub fn pool(&self) -> &Arc<Pool<P, T>> {
+        &self.pool
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Clone for LimitedPool<P, T> {
+    fn clone(&self) -> Self {
+        LimitedPool {
+            pool: self.pool.clone(),
+            permits: self.permits.clone(),
+        }
+    }
+}
+
+impl<P: PoolAllocator<T> + fmt::Debug, T: fmt::Debug> fmt::Debug for LimitedPool<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LimitedPool")
+            .field("pool", &self.pool)
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+/// A guard over an object of a [`LimitedPool`].
+///
+/// Dropping the guard returns the object to the pool and frees its place in
+/// the limit.
+pub struct LimitedGuard<P: PoolAllocator<T>, T> {
+    guard: RcGuard<P, T>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl<P: PoolAllocator<T>, T> LimitedGuard<P, T> {
+    /// Consumes the guard and returns the object, without returning it to the
+    /// pool. Its place in the limit is freed.
+    pub fn into_inner(self) -> T {
+        self.guard.into_inner()
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Deref for LimitedGuard<P, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<P: PoolAllocator<T>, T> DerefMut for LimitedGuard<P, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for LimitedGuard<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,58 @@
+use core::ffi::c_void;
+
+/// Returns the page size of the system.
+pub(crate) fn page_size() -> usize {
+    // Safety: sysconf has no preconditions.
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size > 0 {
+        size as usize
+    } else {
+        4096
+    }
+}
+
+/// Gives the kernel advice about the whole pages inside the given memory
+/// range, ignoring partial pages at both ends.
+///
+/// Advice is best-effort, so errors reported by the kernel are ignored.
+///
+/// # Safety
+///
+/// `ptr` must point to an allocation of at least `len` bytes. If `advice`
+/// discards the contents of the pages, the memory must be exclusively borrowed
+/// and valid when zeroed.
+pub(crate) unsafe fn advise(ptr: *const u8, len: usize, advice: libc::c_int) {
+    let page = page_size();
+    let start = (ptr as usize + page - 1) & !(page - 1);
+    let end = (ptr as usize + len) & !(page - 1);
+    if start < end {
+        libc::madvise(start as *mut c_void, end - start, advice);
+    }
+}
+
+/// Locks the pages holding the given memory range into RAM.
+///
+/// Returns `false` if the kernel refused, for example because the process hit
+/// its `RLIMIT_MEMLOCK` limit.
+pub(crate) fn lock(buf: &[u8]) -> bool {
+    // Safety: the range is a valid allocation, locking does not modify it.
+    buf.is_empty() || unsafe { libc::mlock(buf.as_ptr() as *const c_void, buf.len()) == 0 }
+}
+
+/// Unlocks the pages holding the given memory range.
+pub(crate) fn unlock(buf: &[u8]) {
+    if !buf.is_empty() {
+        // Safety: the range is a valid allocation, unlocking does not modify it.
+        unsafe { libc::munlock(buf.as_ptr() as *const c_void, buf.len()) };
+    }
+}
+
+/// Overwrites the given memory with zeros in a way the compiler cannot
+/// optimize away.
+pub(crate) fn wipe(buf: &mut [u8]) {
+    for byte in buf.iter_mut() {
+        // Safety: the pointer comes from a valid mutable reference.
+        unsafe { core::ptr::write_volatile(byte, 0) };
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
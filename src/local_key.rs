@@ -0,0 +1,49 @@
+use crate::{LocalPool, PoolAllocator};
+use std::thread::LocalKey;
+
+/// Declares a thread-local [`LocalPool`].
+///
+/// The pool is created lazily on first use in each thread, with the given
+/// allocator and capacity. Objects are borrowed from it with
+/// [`LocalPoolKey::with_get`].
+///
+/// ```
+/// use opool::{local_pool, BufferAllocator, LocalPoolKey};
+///
+/// local_pool!(static BUFS: Vec<u8> = BufferAllocator::new(4096); cap = 32);
+///
+/// let len = BUFS.with_get(|buf| buf.len());
+/// assert_eq!(len, 4096);
+/// ```
+///
+/// The allocator is stored as a `Box<dyn PoolAllocator<T>>`, so its concrete
+/// type does not have to be spelled out in the declaration.
+#[macro_export]
+macro_rules! local_pool {
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty = $alloc:expr; cap = $cap:expr $(;)?) => {
+        ::std::thread_local! {
+            $(#[$attr])*
+            $vis static $name: $crate::LocalPool<
+                ::std::boxed::Box<dyn $crate::PoolAllocator<$t>>,
+                $t,
+            > = $crate::LocalPool::new(
+                $cap,
+                ::std::boxed::Box::new($alloc) as ::std::boxed::Box<dyn $crate::PoolAllocator<$t>>,
+            );
+        }
+    };
+}
+
+/// Extension methods for thread-local pools declared with [`local_pool!`].
+pub trait LocalPoolKey<T> {
+    /// Gets an object from the current thread's pool and passes it to `f`.
+    ///
+    /// The object is returned to the pool once `f` returns.
+    fn with_get<R>(&'static self, f: impl FnOnce(&mut T) -> R) -> R;
+}
+
+impl<P: PoolAllocator<T>, T> LocalPoolKey<T> for LocalKey<LocalPool<P, T>> {
+    fn with_get<R>(&'static self, f: impl FnOnce(&mut T) -> R) -> R {
+        self.with(|pool| f(&mut pool.get()))
+    }
+}
@@ -0,0 +1,41 @@
+use core::{any::type_name, fmt};
+use log::LevelFilter;
+
+/// A notable pool event reported through the `log` crate.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum LogEvent {
+    /// An object failed [`PoolAllocator::is_valid`](crate::PoolAllocator::is_valid).
+    Invalid,
+    /// An idle object failed
+    /// [`PoolAllocator::try_reset`](crate::PoolAllocator::try_reset).
+    ResetFailed,
+    /// Returned objects were dropped because the pool was full.
+    Overflow(usize),
+    /// The pool was dropped while objects it handed out were never returned
+    /// or released, which means their guards were leaked.
+    Leaked(usize),
+}
+
+impl fmt::Display for LogEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogEvent::Invalid => f.write_str("dropped an object that failed validation"),
+            LogEvent::ResetFailed => f.write_str("dropped an idle object that failed to reset"),
+            LogEvent::Overflow(n) => write!(f, "dropped {n} returned object(s) because it is full"),
+            LogEvent::Leaked(n) => write!(f, "was dropped with {n} object(s) never returned"),
+        }
+    }
+}
+
+/// Logs `event` of a pool of `T` at `level`, naming the pool by its name or,
+/// for unnamed pools, by the type of its objects.
+#[cold]
+pub(crate) fn log_event<T>(level: LevelFilter, name: Option<&'static str>, event: LogEvent) {
+    let Some(level) = level.to_level() else {
+        return;
+    };
+    match name {
+        Some(name) => log::log!(target: "opool", level, "pool `{name}` {event}"),
+        None => log::log!(target: "opool", level, "pool of `{}` {event}", type_name::<T>()),
+    }
+}
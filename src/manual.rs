@@ -0,0 +1,86 @@
+use core::{
+    fmt,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+/// A guard over a pooled object that is not returned to the pool on drop.
+///
+/// It is obtained with `get_manual` on [`Pool`](crate::Pool) or
+/// [`LocalPool`](crate::LocalPool) and must be handed back with the
+/// `put_back` method of the same pool. Without a drop implementation the
+/// compiler does not need to track whether the guard is still alive, which
+/// gives tighter code in hot loops.
+///
+/// A guard that is dropped instead of being put back drops its object, but
+/// the pool keeps counting it as live, which in a pool with a live object
+/// limit permanently takes up one place; debug builds panic to catch the
+/// mistake.
+#[must_use = "a ManualGuard must be returned with `put_back`"]
+pub struct ManualGuard<T> {
+    obj: T,
+    #[cfg(debug_assertions)]
+    pool: usize,
+}
+
+impl<T> ManualGuard<T> {
+    /// Creates a new ManualGuard for an object checked out of the pool at
+    /// address `pool`.
+    #[inline(always)]
+    pub(crate) fn new(obj: T, pool: usize) -> Self {
+        #[cfg(not(debug_assertions))]
+        let _ = pool;
+        ManualGuard {
+            obj,
+            #[cfg(debug_assertions)]
+            pool,
+        }
+    }
+
+    /// Takes the object out of a guard that is being put back into the pool at
+    /// address `pool`.
+    #[inline(always)]
+    pub(crate) fn into_obj(self, pool: usize) -> T {
+        #[cfg(debug_assertions)]
+        assert_eq!(self.pool, pool, "ManualGuard put back into another pool");
+        #[cfg(not(debug_assertions))]
+        let _ = pool;
+        let guard = ManuallyDrop::new(self);
+        // Safety: the guard is never used or dropped after the object is read.
+        unsafe { ptr::read(&guard.obj) }
+    }
+}
+
+impl<T> Deref for ManualGuard<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.obj
+    }
+}
+
+impl<T> DerefMut for ManualGuard<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.obj
+    }
+}
+
+#[cfg(debug_assertions)]
+impl<T> Drop for ManualGuard<T> {
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+        panic!("ManualGuard dropped without being put back into its pool");
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ManualGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.obj.fmt(f)
+    }
+}
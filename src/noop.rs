@@ -0,0 +1,80 @@
+use crate::{ObjectPool, PoolAllocator};
+use core::ops::{Deref, DerefMut};
+
+/// A pool that does not pool anything.
+///
+/// Every get allocates a new object with the allocator and every returned
+/// object is dropped. Use it in place of another [`ObjectPool`] to turn
+/// pooling off, or to stub pooling out in tests.
+#[derive(Debug, Clone, Default)]
+pub struct NoopPool<P> {
+    allocator: P,
+}
+
+impl<P> NoopPool<P> {
+    /// Creates a new NoopPool with the given allocator.
+    pub fn new(allocator: P) -> Self {
+        NoopPool { allocator }
+    }
+
+    /// Gets a newly allocated object.
+    pub fn get<T>(&self) -> NoopGuard<T>
+    where
+        P: PoolAllocator<T>,
+    {
+        let mut obj = self.allocator.allocate();
+        if !self.allocator.is_warm(&obj) {
+            self.allocator.warm(&mut obj);
+        }
+        NoopGuard(obj)
+    }
+}
+
+impl<P: PoolAllocator<T>, T> ObjectPool<T> for NoopPool<P> {
+    type Guard<'a>
+        = NoopGuard<T>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    fn get(&self) -> Self::Guard<'_> {
+        NoopPool::get(self)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        0
+    }
+
+    #[inline(always)]
+    fn cap(&self) -> usize {
+        0
+    }
+}
+
+/// The guard returned by [`NoopPool`], which drops its object when dropped.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NoopGuard<T>(T);
+
+impl<T> NoopGuard<T> {
+    /// Consumes the guard and returns the object.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for NoopGuard<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for NoopGuard<T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
@@ -0,0 +1,154 @@
+use crate::{LocalPool, Pool, PoolAllocator, PoolHandle, RcGuard, RefGuard, RefLocalGuard};
+use core::ops::DerefMut;
+
+/// A trait implemented by all object pools in this crate.
+///
+/// Writing code against this trait instead of a concrete pool type lets an
+/// application swap the pool implementation, for example for a
+/// [`NoopPool`](crate::NoopPool) to turn pooling off, without changing its
+/// call sites.
+pub trait ObjectPool<T> {
+    /// The guard returned by [`Self::get`], giving access to the object until
+    /// it is dropped.
+    type Guard<'a>: DerefMut<Target = T>
+    where
+        Self: 'a;
+
+    /// Gets an object from the pool.
+    fn get(&self) -> Self::Guard<'_>;
+
+    /// Returns the number of idle objects in the pool.
+    fn len(&self) -> usize;
+
+    /// Returns the maximum number of idle objects the pool can hold.
+    fn cap(&self) -> usize;
+
+    /// Returns `true` if the pool holds no idle objects.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the pool cannot hold any more idle objects.
+    fn is_full(&self) -> bool {
+        self.spare_capacity() == 0
+    }
+
+    /// Returns how many more idle objects the pool can hold.
+    fn spare_capacity(&self) -> usize {
+        self.cap().saturating_sub(self.len())
+    }
+}
+
+impl<P: PoolAllocator<T>, T> ObjectPool<T> for Pool<P, T> {
+    type Guard<'a>
+        = RefGuard<'a, P, T>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    fn get(&self) -> Self::Guard<'_> {
+        Pool::get(self)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        Pool::len(self)
+    }
+
+    #[inline(always)]
+    fn cap(&self) -> usize {
+        Pool::cap(self)
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        Pool::is_empty(self)
+    }
+
+    #[inline(always)]
+    fn is_full(&self) -> bool {
+        Pool::is_full(self)
+    }
+
+    #[inline(always)]
+    fn spare_capacity(&self) -> usize {
+        Pool::spare_capacity(self)
+    }
+}
+
+impl<P: PoolAllocator<T>, T> ObjectPool<T> for LocalPool<P, T> {
+    type Guard<'a>
+        = RefLocalGuard<'a, P, T>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    fn get(&self) -> Self::Guard<'_> {
+        LocalPool::get(self)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        LocalPool::len(self)
+    }
+
+    #[inline(always)]
+    fn cap(&self) -> usize {
+        LocalPool::cap(self)
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        LocalPool::is_empty(self)
+    }
+
+    #[inline(always)]
+    fn is_full(&self) -> bool {
+        LocalPool::is_full(self)
+    }
+
+    #[inline(always)]
+    fn spare_capacity(&self) -> usize {
+        LocalPool::spare_capacity(self)
+    }
+}
+
+impl<P: PoolAllocator<T>, T> ObjectPool<T> for PoolHandle<P, T> {
+    type Guard<'a>
+        = RcGuard<P, T>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    fn get(&self) -> Self::Guard<'_> {
+        PoolHandle::get(self)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.pool().len()
+    }
+
+    #[inline(always)]
+    fn cap(&self) -> usize {
+        self.pool().cap()
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        self.pool().is_empty()
+    }
+
+    #[inline(always)]
+    fn is_full(&self) -> bool {
+        self.pool().is_full()
+    }
+
+    #[inline(always)]
+    fn spare_capacity(&self) -> usize {
+        self.pool().spare_capacity()
+    }
+}
@@ -0,0 +1,69 @@
+use core::fmt;
+
+/// Hooks called by a pool as its objects come and go, for plugging in custom
+/// logging or metrics.
+///
+/// An observer is installed with `with_observer` on [`crate::Pool`] and
+/// [`crate::LocalPool`]. Every hook has an empty default, so an observer only
+/// implements the events it cares about. Hooks run inline on the thread that
+/// triggered the event, on the hot path for [`Self::on_recycle`], so they
+/// should be cheap.
+///
+/// ```
+/// use opool::{Pool, PoolAllocator, PoolObserver};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// struct Alloc;
+///
+/// impl PoolAllocator<Vec<u8>> for Alloc {
+///     fn allocate(&self) -> Vec<u8> {
+///         Vec::with_capacity(64)
+///     }
+/// }
+///
+/// struct Allocations(Arc<AtomicUsize>);
+///
+/// impl PoolObserver<Vec<u8>> for Allocations {
+///     fn on_allocate(&self, _obj: &Vec<u8>) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+///
+/// let allocations = Arc::new(AtomicUsize::new(0));
+/// let pool = Pool::new(4, Alloc).with_observer(Allocations(allocations.clone()));
+/// drop(pool.get());
+/// drop(pool.get());
+/// assert_eq!(allocations.load(Ordering::Relaxed), 1);
+/// ```
+pub trait PoolObserver<T> {
+    /// Called when the pool creates a new object with its allocator.
+    fn on_allocate(&self, _obj: &T) {}
+
+    /// Called when a returned object passes validation and is handed back to
+    /// the pool for reuse.
+    ///
+    /// If the pool turns out to be full, [`Self::on_discard`] follows.
+    fn on_recycle(&self, _obj: &T) {}
+
+    /// Called when the pool destroys an object, right before handing it to
+    /// [`PoolAllocator::on_destroy`](crate::PoolAllocator::on_destroy).
+    fn on_discard(&self, _obj: &T) {}
+
+    /// Called when a returned object fails
+    /// [`PoolAllocator::is_valid`](crate::PoolAllocator::is_valid), before it
+    /// is quarantined or discarded.
+    fn on_validation_failure(&self, _obj: &T) {}
+}
+
+impl<T> fmt::Debug for dyn PoolObserver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PoolObserver")
+    }
+}
+
+impl<T> fmt::Debug for dyn PoolObserver<T> + Send + Sync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PoolObserver")
+    }
+}
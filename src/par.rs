@@ -0,0 +1,40 @@
+use crate::{Pool, PoolAllocator};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+impl<P: PoolAllocator<T> + Send + Sync, T: Send> Pool<P, T> {
+    /// Maps `f` over `iter` in parallel, passing each call an object from the
+    /// pool.
+    ///
+    /// An object is checked out once per rayon job instead of once per item,
+    /// and is reused for every item of that job before being returned. Use
+    /// [`rayon::iter::IndexedParallelIterator::with_min_len`] on `iter` to make
+    /// jobs larger if checkouts are still too frequent.
+    pub fn par_map_with<'a, I, F, R>(
+        &'a self,
+        iter: I,
+        f: F,
+    ) -> impl ParallelIterator<Item = R> + 'a
+    where
+        I: IntoParallelIterator,
+        I::Iter: 'a,
+        F: Fn(&mut T, I::Item) -> R + Sync + Send + 'a,
+        R: Send,
+    {
+        iter.into_par_iter()
+            .map_init(|| self.get(), move |obj, item| f(obj, item))
+    }
+
+    /// Calls `f` on every item of `iter` in parallel, passing each call an
+    /// object from the pool.
+    ///
+    /// Like [`Self::par_map_with`], an object is checked out once per rayon
+    /// job instead of once per item.
+    pub fn par_for_each_with<I, F>(&self, iter: I, f: F)
+    where
+        I: IntoParallelIterator,
+        F: Fn(&mut T, I::Item) + Sync + Send,
+    {
+        iter.into_par_iter()
+            .for_each_init(|| self.get(), |obj, item| f(obj, item))
+    }
+}
@@ -0,0 +1,173 @@
+use crate::PoolAllocator;
+use core::{fmt, marker::PhantomData};
+
+/// A set of allocator behaviors selected at compile time.
+///
+/// A policy is used through [`PolicyAllocator`], so it only controls whether
+/// the hooks of the wrapped allocator run and what happens when they fail.
+/// Its settings are associated constants, so the allocator calls a policy
+/// skips compile away instead of being checked on every get and return.
+///
+/// When objects are reset and the order in which idle objects are handed out
+/// are settings of the pool itself, chosen at runtime with
+/// `with_reset_policy` and [`crate::Pool::with_lifo`], and are not covered
+/// by a policy.
+pub trait PoolPolicy {
+    /// Whether resetting a recycled object calls the wrapped allocator.
+    ///
+    /// Turning this off skips [`PoolAllocator::try_reset`] for objects whose
+    /// state never needs clearing. The pool still decides when the reset
+    /// happens.
+    const RESET: bool = true;
+    /// Whether returned objects are checked with [`PoolAllocator::is_valid`].
+    const VALIDATE: bool = true;
+    /// Whether an object failing validation or reset is a bug.
+    ///
+    /// A strict policy panics instead of silently dropping the object.
+    const STRICT: bool = false;
+}
+
+/// The policy matching the behavior of an allocator used on its own: objects
+/// are reset and validated, and failures drop the object.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultPolicy;
+
+impl PoolPolicy for DefaultPolicy {}
+
+/// A policy that resets objects but never validates them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UncheckedPolicy;
+
+impl PoolPolicy for UncheckedPolicy {
+    const VALIDATE: bool = false;
+}
+
+/// A policy that panics when an object fails validation or reset, for
+/// catching objects that are returned in a bad state.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictPolicy;
+
+impl PoolPolicy for StrictPolicy {
+    const STRICT: bool = true;
+}
+
+/// An allocator wrapper that applies the policy `S` to the wrapped allocator.
+///
+/// ```
+/// use opool::{Pool, PolicyAllocator, PoolAllocator, UncheckedPolicy};
+///
+/// struct Alloc;
+///
+/// impl PoolAllocator<Vec<u8>> for Alloc {
+///     fn allocate(&self) -> Vec<u8> {
+///         Vec::new()
+///     }
+///
+///     fn is_valid(&self, obj: &Vec<u8>) -> bool {
+///         obj.capacity() < 4096
+///     }
+/// }
+///
+/// let pool = Pool::new(16, PolicyAllocator::<_, UncheckedPolicy>::new(Alloc));
+/// pool.get().reserve(8192);
+/// assert_eq!(pool.len(), 1);
+/// ```
+pub struct PolicyAllocator<A, S: PoolPolicy = DefaultPolicy> {
+    inner: A,
+    _policy: PhantomData<fn() -> S>,
+}
+
+impl<A, S: PoolPolicy> PolicyAllocator<A, S> {
+    /// Creates a new PolicyAllocator wrapping the given allocator.
+    pub const fn new(inner: A) -> Self {
+        PolicyAllocator {
+            inner,
+            _policy: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the wrapped allocator.
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+}
+
+impl<A: Default, S: PoolPolicy> Default for PolicyAllocator<A, S> {
+    fn default() -> Self {
+        Self::new(A::default())
+    }
+}
+
+impl<A: fmt::Debug, S: PoolPolicy> fmt::Debug for PolicyAllocator<A, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolicyAllocator")
+            .field("inner", &self.inner)
+            .field("reset", &S::RESET)
+            .field("validate", &S::VALIDATE)
+            .field("strict", &S::STRICT)
+            .finish()
+    }
+}
+
+impl<A: PoolAllocator<T>, S: PoolPolicy, T> PoolAllocator<T> for PolicyAllocator<A, S> {
+    #[inline]
+    fn reset(&self, obj: &mut T) {
+        if S::RESET {
+            self.inner.reset(obj)
+        }
+    }
+
+    #[inline]
+    fn allocate(&self) -> T {
+        self.inner.allocate()
+    }
+
+    #[inline]
+    fn is_valid(&self, obj: &T) -> bool {
+        if !S::VALIDATE {
+            return true;
+        }
+        let valid = self.inner.is_valid(obj);
+        if S::STRICT && !valid {
+            panic!("pooled object failed validation under a strict policy");
+        }
+        valid
+    }
+
+    #[inline]
+    fn try_reset(&self, obj: &mut T) -> bool {
+        if !S::RESET {
+            return true;
+        }
+        let reset = self.inner.try_reset(obj);
+        if S::STRICT && !reset {
+            panic!("pooled object failed to reset under a strict policy");
+        }
+        reset
+    }
+
+    #[inline]
+    fn on_destroy(&self, obj: T) {
+        self.inner.on_destroy(obj)
+    }
+
+    #[inline]
+    fn on_trim(&self, obj: &mut T) -> bool {
+        self.inner.on_trim(obj)
+    }
+
+    #[inline]
+    fn warm(&self, obj: &mut T) {
+        self.inner.warm(obj)
+    }
+
+    #[inline]
+    fn is_warm(&self, obj: &T) -> bool {
+        self.inner.is_warm(obj)
+    }
+}
@@ -1,8 +1,44 @@
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+
 /// A trait defining the interface for a pool allocator.
 ///
 /// This trait provides methods for resetting and creating new objects,
 /// as well as validating objects before they are stored back in the object
 /// pool.
+///
+/// Pooled objects do not have to be `'static`. An allocator can hand out
+/// objects that borrow from an arena or configuration owned elsewhere, and the
+/// pool is then bounded by that borrow:
+///
+/// ```
+/// use opool::{Pool, PoolAllocator};
+///
+/// struct Config {
+///     delimiter: u8,
+/// }
+///
+/// struct Splitter<'a> {
+///     config: &'a Config,
+///     fields: Vec<usize>,
+/// }
+///
+/// struct SplitterAllocator<'a>(&'a Config);
+///
+/// impl<'a> PoolAllocator<Splitter<'a>> for SplitterAllocator<'a> {
+///     fn allocate(&self) -> Splitter<'a> {
+///         Splitter { config: self.0, fields: Vec::new() }
+///     }
+///
+///     fn reset(&self, obj: &mut Splitter<'a>) {
+///         obj.fields.clear();
+///     }
+/// }
+///
+/// let config = Config { delimiter: b',' };
+/// let pool = Pool::new(4, SplitterAllocator(&config));
+/// let splitter = pool.get();
+/// assert_eq!(splitter.config.delimiter, b',');
+/// ```
 pub trait PoolAllocator<T> {
     /// Resets the state of an object to its initial state if necessary.
     ///
@@ -23,4 +59,109 @@ pub trait PoolAllocator<T> {
     fn is_valid(&self, _obj: &T) -> bool {
         true
     }
+
+    /// Resets an object like [`Self::reset`], returning `false` if the object
+    /// could not be reset.
+    ///
+    /// Objects that fail to reset are destroyed instead of being handed out.
+    /// By default, this method calls [`Self::reset`] and returns true.
+    /// Override it when resetting can fail.
+    #[inline(always)]
+    fn try_reset(&self, obj: &mut T) -> bool {
+        self.reset(obj);
+        true
+    }
+
+    /// Destroys an object that the pool discards.
+    ///
+    /// This is called with objects that fail validation or reset, that do not
+    /// fit in the pool, or that are trimmed from it. When a pool is dropped,
+    /// it is called with each idle object in the order they would have been
+    /// handed out, then with each quarantined object, oldest first. By
+    /// default, this method drops the object.
+    #[inline(always)]
+    fn on_destroy(&self, _obj: T) {}
+
+    /// Releases the memory held by an idle object that the pool trims,
+    /// returning `true` to keep the object in the pool instead of
+    /// destroying it.
+    ///
+    /// Pools call this from `shrink_to` and when trimming on memory pressure,
+    /// for objects whose memory can be handed back to the operating system
+    /// without giving up the object itself. By default, this method returns
+    /// false, so trimmed objects are destroyed with [`Self::on_destroy`].
+    #[inline(always)]
+    fn on_trim(&self, _obj: &mut T) -> bool {
+        false
+    }
+
+    /// Finishes preparing an object created by [`Self::allocate`].
+    ///
+    /// Allocators whose objects are cheap to create but expensive to make
+    /// ready, such as connections that still need a handshake, can split the
+    /// work: `allocate` does the cheap part so prefilling returns quickly, and
+    /// this method does the rest. Pools call it on objects that are not
+    /// [`Self::is_warm`] when handing them out, or earlier from `warm_idle`
+    /// off the critical path. By default, this method does nothing.
+    #[inline(always)]
+    fn warm(&self, _obj: &mut T) {}
+
+    /// Returns whether an object is ready to be handed out, or still needs
+    /// [`Self::warm`].
+    ///
+    /// By default, this method always returns true.
+    #[inline(always)]
+    fn is_warm(&self, _obj: &T) -> bool {
+        true
+    }
 }
+
+/// Implements [`PoolAllocator`] for a pointer type by delegating to the
+/// allocator it points to, so one allocator can be shared by several pools.
+macro_rules! delegate_allocator {
+    ($($ptr:ty),*) => {$(
+        impl<T, P: PoolAllocator<T> + ?Sized> PoolAllocator<T> for $ptr {
+            #[inline(always)]
+            fn reset(&self, obj: &mut T) {
+                (**self).reset(obj)
+            }
+
+            #[inline(always)]
+            fn allocate(&self) -> T {
+                (**self).allocate()
+            }
+
+            #[inline(always)]
+            fn is_valid(&self, obj: &T) -> bool {
+                (**self).is_valid(obj)
+            }
+
+            #[inline(always)]
+            fn try_reset(&self, obj: &mut T) -> bool {
+                (**self).try_reset(obj)
+            }
+
+            #[inline(always)]
+            fn on_destroy(&self, obj: T) {
+                (**self).on_destroy(obj)
+            }
+
+            #[inline(always)]
+            fn on_trim(&self, obj: &mut T) -> bool {
+                (**self).on_trim(obj)
+            }
+
+            #[inline(always)]
+            fn warm(&self, obj: &mut T) {
+                (**self).warm(obj)
+            }
+
+            #[inline(always)]
+            fn is_warm(&self, obj: &T) -> bool {
+                (**self).is_warm(obj)
+            }
+        }
+    )*};
+}
+
+delegate_allocator!(&P, Box<P>, Rc<P>, Arc<P>);
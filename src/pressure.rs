@@ -0,0 +1,35 @@
+use crate::{Pool, PoolAllocator};
+use alloc::{boxed::Box, sync::Arc};
+
+/// A source of memory pressure notifications.
+///
+/// See `PsiWatcher`, behind the `pressure` feature, for an implementation
+/// based on Linux pressure stall information.
+pub trait MemoryPressure {
+    /// Registers a callback to run every time memory pressure is reported.
+    fn on_pressure(&self, callback: Box<dyn Fn() + Send + Sync>);
+}
+
+impl<P, T> Pool<P, T>
+where
+    P: PoolAllocator<T> + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    /// Trims all idle objects of the pool every time `listener` reports
+    /// memory pressure.
+    ///
+    /// Objects are destroyed unless [`PoolAllocator::on_trim`] keeps them
+    /// after releasing their memory.
+    ///
+    /// This keeps pools from holding on to memory the system needs, at the
+    /// cost of allocating again once the pressure is over. The listener only
+    /// keeps a weak reference to the pool, so it does not keep the pool alive.
+    pub fn trim_on_pressure(self: &Arc<Self>, listener: &impl MemoryPressure) {
+        let pool = Arc::downgrade(self);
+        listener.on_pressure(Box::new(move || {
+            if let Some(pool) = pool.upgrade() {
+                pool.trim_idle(0);
+            }
+        }));
+    }
+}
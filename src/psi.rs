@@ -0,0 +1,129 @@
+use crate::MemoryPressure;
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    fmt,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    os::fd::AsRawFd,
+    path::Path,
+    sync::{Mutex, PoisonError},
+    thread::{self, JoinHandle},
+};
+
+type Callbacks = Mutex<Vec<Box<dyn Fn() + Send + Sync>>>;
+
+/// How often the watcher thread checks whether it should stop.
+const POLL_INTERVAL_MS: libc::c_int = 200;
+
+/// Watches Linux pressure stall information (PSI) for memory pressure.
+///
+/// The watcher registers a PSI trigger and waits for it on a background
+/// thread, running the registered callbacks every time the kernel reports
+/// that tasks stalled on memory for longer than the configured threshold. The
+/// thread stops when the watcher is dropped.
+///
+/// Creating triggers may require privileges, depending on the kernel version
+/// and the window.
+pub struct PsiWatcher {
+    callbacks: Arc<Callbacks>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PsiWatcher {
+    /// Watches system-wide memory pressure, reporting pressure when tasks
+    /// stall on memory for 150ms within a 2s window.
+    pub fn new() -> io::Result<Self> {
+        Self::with_trigger(
+            "/proc/pressure/memory",
+            Duration::from_millis(150),
+            Duration::from_secs(2),
+        )
+    }
+
+    /// Watches the memory pressure file at `path`, such as the
+    /// `memory.pressure` file of a cgroup, reporting pressure when some tasks
+    /// stall on memory for `stall` within `window`.
+    pub fn with_trigger(
+        path: impl AsRef<Path>,
+        stall: Duration,
+        window: Duration,
+    ) -> io::Result<Self> {
+        let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+        let trigger = std::format!("some {} {}\0", stall.as_micros(), window.as_micros());
+        file.write_all(trigger.as_bytes())?;
+
+        let callbacks = Arc::new(Callbacks::default());
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread = thread::Builder::new().name("opool-psi".into()).spawn({
+            let callbacks = callbacks.clone();
+            let stop = stop.clone();
+            move || {
+                let mut fds = libc::pollfd {
+                    fd: file.as_raw_fd(),
+                    events: libc::POLLPRI,
+                    revents: 0,
+                };
+                while !stop.load(Ordering::Relaxed) {
+                    // Safety: `fds` points to one valid pollfd, and the file
+                    // outlives the call.
+                    let ready = unsafe { libc::poll(&mut fds, 1, POLL_INTERVAL_MS) };
+                    if ready < 0 {
+                        if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                            continue;
+                        }
+                        break;
+                    }
+                    if fds.revents & libc::POLLERR != 0 {
+                        break;
+                    }
+                    if fds.revents & libc::POLLPRI != 0 {
+                        let callbacks = callbacks.lock().unwrap_or_else(PoisonError::into_inner);
+                        for callback in callbacks.iter() {
+                            callback();
+                        }
+                    }
+                }
+            }
+        })?;
+        Ok(PsiWatcher {
+            callbacks,
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl MemoryPressure for PsiWatcher {
+    fn on_pressure(&self, callback: Box<dyn Fn() + Send + Sync>) {
+        self.callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(callback);
+    }
+}
+
+impl Drop for PsiWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl fmt::Debug for PsiWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let callbacks = self
+            .callbacks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        f.debug_struct("PsiWatcher")
+            .field("callbacks", &callbacks.len())
+            .finish()
+    }
+}
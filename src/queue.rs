@@ -0,0 +1,119 @@
+//! The bounded queue behind the concurrent pool.
+//!
+//! With the `crossbeam` feature, which is enabled by default, this is the
+//! lock-free `ArrayQueue` of crossbeam-queue. Without it, the pool falls back
+//! to a queue guarded by a lock, a [`std::sync::Mutex`] with the `std` feature
+//! or a spinlock otherwise, so the crate builds without any dependency.
+//!
+//! The same lock guards the stack of pools that hand out their most recently
+//! returned objects first.
+
+#[cfg(feature = "crossbeam")]
+pub(crate) use crossbeam_queue::ArrayQueue;
+
+#[cfg(not(feature = "crossbeam"))]
+pub(crate) use locked::ArrayQueue;
+
+#[cfg(not(feature = "crossbeam"))]
+mod locked {
+    use super::Lock;
+    use alloc::collections::VecDeque;
+    use core::fmt;
+
+    /// A bounded FIFO queue guarded by a lock, with the interface of
+    /// `crossbeam_queue::ArrayQueue` used by the pool.
+    pub(crate) struct ArrayQueue<T> {
+        items: Lock<VecDeque<T>>,
+        capacity: usize,
+    }
+
+    impl<T> ArrayQueue<T> {
+        pub(crate) fn new(capacity: usize) -> Self {
+            ArrayQueue {
+                items: Lock::new(VecDeque::with_capacity(capacity)),
+                capacity,
+            }
+        }
+
+        pub(crate) fn push(&self, obj: T) -> Result<(), T> {
+            self.items.with(|items| {
+                if items.len() == self.capacity {
+                    return Err(obj);
+                }
+                items.push_back(obj);
+                Ok(())
+            })
+        }
+
+        pub(crate) fn pop(&self) -> Option<T> {
+            self.items.with(VecDeque::pop_front)
+        }
+
+        pub(crate) fn len(&self) -> usize {
+            self.items.with(|items| items.len())
+        }
+    }
+
+    impl<T> fmt::Debug for ArrayQueue<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("ArrayQueue")
+                .field("len", &self.len())
+                .field("capacity", &self.capacity)
+                .finish()
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) struct Lock<T>(std::sync::Mutex<T>);
+
+#[cfg(feature = "std")]
+impl<T> Lock<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Lock(std::sync::Mutex::new(value))
+    }
+
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self
+            .0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner))
+    }
+}
+
+/// A spinlock for `no_std` targets. It is only held for a single queue or stack
+/// operation, so waiting threads spin for a short time.
+#[cfg(not(feature = "std"))]
+pub(crate) struct Lock<T> {
+    locked: core::sync::atomic::AtomicBool,
+    value: core::cell::UnsafeCell<T>,
+}
+
+// Safety: the value is only accessed while the lock is held.
+#[cfg(not(feature = "std"))]
+unsafe impl<T: Send> Sync for Lock<T> {}
+
+#[cfg(not(feature = "std"))]
+impl<T> Lock<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Lock {
+            locked: core::sync::atomic::AtomicBool::new(false),
+            value: core::cell::UnsafeCell::new(value),
+        }
+    }
+
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        use core::sync::atomic::Ordering;
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        // Safety: the lock is held until the store below.
+        let result = f(unsafe { &mut *self.value.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+}
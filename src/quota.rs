@@ -0,0 +1,184 @@
+use crate::{Pool, PoolAllocator, PoolError, RcGuard};
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    hash::Hash,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+/// A pool shared by several tenants, each limited in how many objects it can
+/// check out at once.
+///
+/// Every checkout is attributed to a tenant key. Tenants without a quota set
+/// with [`Self::set_quota`] get the default quota of the pool. Checkouts over
+/// a tenant's quota fail with [`PoolError::QuotaExceeded`], so one busy tenant
+/// cannot take every object of the pool, and [`Self::tenant_stats`] reports
+/// the usage of each tenant.
+pub struct QuotaPool<K, P: PoolAllocator<T>, T> {
+    pool: Arc<Pool<P, T>>,
+    default_quota: usize,
+    tenants: Mutex<HashMap<K, Arc<Tenant>>>,
+}
+
+/// The quota and counters of a tenant.
+#[derive(Debug)]
+struct Tenant {
+    quota: AtomicUsize,
+    in_use: AtomicUsize,
+    checkouts: AtomicUsize,
+    rejections: AtomicUsize,
+}
+
+impl<K: Hash + Eq + Clone, P: PoolAllocator<T>, T> QuotaPool<K, P, T> {
+    /// Creates a new QuotaPool that lets each tenant check out at most
+    /// `default_quota` objects of `pool` at once.
+    pub fn new(pool: Pool<P, T>, default_quota: usize) -> Self {
+        QuotaPool {
+            pool: Arc::new(pool),
+            default_quota,
+            tenants: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets how many objects `tenant` can check out at once.
+    ///
+    /// Objects the tenant already holds are not taken back if the new quota
+    /// is lower.
+    pub fn set_quota(&self, tenant: K, quota: usize) {
+        self.tenant(&tenant).quota.store(quota, Ordering::Relaxed);
+    }
+
+    /// Gets an object from the pool on behalf of `tenant`, failing with
+    /// [`PoolError::QuotaExceeded`] if the tenant holds as many objects as
+    /// its quota allows.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_get(&self, tenant: &K) -> Result<QuotaGuard<P, T>, PoolError> {
+        let tenant = self.tenant(tenant);
+        let quota = tenant.quota.load(Ordering::Relaxed);
+        let claimed = tenant
+            .in_use
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |in_use| {
+                (in_use < quota).then_some(in_use + 1)
+            });
+        if claimed.is_err() {
+            tenant.rejections.fetch_add(1, Ordering::Relaxed);
+            return Err(PoolError::QuotaExceeded);
+        }
+        tenant.checkouts.fetch_add(1, Ordering::Relaxed);
+        Ok(QuotaGuard {
+            guard: self.pool.clone().get_rc(),
+            _slot: TenantSlot(tenant),
+        })
+    }
+
+    /// Returns the usage of `tenant`, or `None` if it never checked out an
+    /// object and has no quota set.
+    pub fn tenant_stats(&self, tenant: &K) -> Option<TenantStats> {
+        let tenants = self.tenants.lock().unwrap_or_else(PoisonError::into_inner);
+        tenants.get(tenant).map(|tenant| TenantStats {
+            quota: tenant.quota.load(Ordering::Relaxed),
+            in_use: tenant.in_use.load(Ordering::Relaxed),
+            checkouts: tenant.checkouts.load(Ordering::Relaxed),
+            rejections: tenant.rejections.load(Ordering::Relaxed),
+        })
+    }
+
+    /// Returns the pool shared by the tenants.
+    pub fn pool(&self) -> &Arc<Pool<P, T>> {
+        &self.pool
+    }
+
+    /// Returns the state of `tenant`, creating it with the default quota if
+    /// needed.
+    fn tenant(&self, tenant: &K) -> Arc<Tenant> {
+        let mut tenants = self.tenants.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(state) = tenants.get(tenant) {
+            return state.clone();
+        }
+        let state = Arc::new(Tenant {
+            quota: AtomicUsize::new(self.default_quota),
+            in_use: AtomicUsize::new(0),
+            checkouts: AtomicUsize::new(0),
+            rejections: AtomicUsize::new(0),
+        });
+        tenants.insert(tenant.clone(), state.clone());
+        state
+    }
+}
+
+impl<K, P: PoolAllocator<T> + fmt::Debug, T: fmt::Debug> fmt::Debug for QuotaPool<K, P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QuotaPool")
+            .field("pool", &self.pool)
+            .field("default_quota", &self.default_quota)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The usage of a tenant of a [`QuotaPool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TenantStats {
+    /// The number of objects the tenant can check out at once.
+    pub quota: usize,
+    /// The number of objects the tenant currently holds.
+    pub in_use: usize,
+    /// The number of successful checkouts by the tenant.
+    pub checkouts: usize,
+    /// The number of checkouts rejected because the tenant was at its quota.
+    pub rejections: usize,
+}
+
+/// A checkout counted against the quota of a tenant, freed when dropped.
+struct TenantSlot(Arc<Tenant>);
+
+impl Drop for TenantSlot {
+    fn drop(&mut self) {
+        self.0.in_use.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A guard over an object of a [`QuotaPool`].
+///
+/// Dropping the guard returns the object to the pool and frees its place in
+/// the quota of its tenant.
+pub struct QuotaGuard<P: PoolAllocator<T>, T> {
+    guard: RcGuard<P, T>,
+    _slot: TenantSlot,
+}
+
+impl<P: PoolAllocator<T>, T> QuotaGuard<P, T> {
+    /// Consumes the guard and returns the object, without returning it to the
+    /// pool. Its place in the quota is freed.
+    pub fn into_inner(self) -> T {
+        self.guard.into_inner()
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Deref for QuotaGuard<P, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<P: PoolAllocator<T>, T> DerefMut for QuotaGuard<P, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for QuotaGuard<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,53 @@
+//! An opt-in, process-wide registry of pools.
+//!
+//! Pools registered with [`register`] can be inspected together with
+//! [`snapshot_all`], for example from a health endpoint or a debug command,
+//! without passing references to every pool around. Give registered pools a
+//! name with [`Pool::with_name`] to tell their stats apart.
+//!
+//! The registry only holds weak references: a pool is unregistered
+//! automatically when it is dropped.
+
+use crate::{Pool, PoolAllocator, PoolStats};
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use std::sync::{Mutex, PoisonError};
+
+/// A pool whose stats can be read through the registry.
+trait StatsSource: Send + Sync {
+    fn stats(&self) -> PoolStats;
+}
+
+impl<P: PoolAllocator<T> + Send + Sync, T: Send> StatsSource for Pool<P, T> {
+    fn stats(&self) -> PoolStats {
+        Pool::stats(self)
+    }
+}
+
+static REGISTRY: Mutex<Vec<Weak<dyn StatsSource>>> = Mutex::new(Vec::new());
+
+/// Adds a pool to the registry.
+pub fn register<P, T>(pool: &Arc<Pool<P, T>>)
+where
+    P: PoolAllocator<T> + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    let pool: Arc<dyn StatsSource> = pool.clone();
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    registry.retain(|pool| pool.strong_count() > 0);
+    registry.push(Arc::downgrade(&pool));
+}
+
+/// Returns the stats of every registered pool that is still alive, in the
+/// order the pools were registered.
+pub fn snapshot_all() -> Vec<PoolStats> {
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    registry.retain(|pool| pool.strong_count() > 0);
+    registry
+        .iter()
+        .filter_map(Weak::upgrade)
+        .map(|pool| pool.stats())
+        .collect()
+}
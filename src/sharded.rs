@@ -0,0 +1,231 @@
+use crate::{ObjectPool, Pool, PoolAllocator, RefGuard};
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    cell::Cell,
+    fmt,
+    hash::BuildHasher,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{collections::hash_map::RandomState, thread};
+
+/// Chooses the shard of a [`ShardedPool`] that serves a get.
+///
+/// Selectors are called on every get, so they should be cheap. The strategies
+/// of this crate are [`ThreadHash`], [`RoundRobin`], [`RandomShard`] and
+/// [`Sticky`]; a custom selector can, for example, map threads pinned to CPUs
+/// to the shard of their CPU.
+pub trait ShardSelector {
+    /// Returns the index of the shard to use, below `shards`, which is never
+    /// zero.
+    fn select(&self, shards: usize) -> usize;
+}
+
+std::thread_local! {
+    static THREAD_HASH: u64 = RandomState::new().hash_one(thread::current().id());
+    static THREAD_INDEX: usize = {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    };
+    static RANDOM: Cell<u64> = Cell::new(THREAD_HASH.with(|hash| *hash) | 1);
+}
+
+/// Picks the shard from a hash of the current thread, so each thread keeps
+/// using the same shard. This is the default.
+///
+/// Threads spread over the shards at random, so a few busy threads can land
+/// on the same shard.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadHash;
+
+impl ShardSelector for ThreadHash {
+    #[inline]
+    fn select(&self, shards: usize) -> usize {
+        THREAD_HASH.with(|hash| (*hash % shards as u64) as usize)
+    }
+}
+
+/// Picks the shards in turn, spreading gets evenly regardless of which
+/// threads make them, at the cost of a shared counter.
+#[derive(Debug, Default)]
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl ShardSelector for RoundRobin {
+    #[inline]
+    fn select(&self, shards: usize) -> usize {
+        self.next.fetch_add(1, Ordering::Relaxed) % shards
+    }
+}
+
+/// Picks a random shard on every get, from a fast generator of the current
+/// thread.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomShard;
+
+impl ShardSelector for RandomShard {
+    #[inline]
+    fn select(&self, shards: usize) -> usize {
+        RANDOM.with(|state| {
+            // xorshift64
+            let mut x = state.get();
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            state.set(x);
+            (x % shards as u64) as usize
+        })
+    }
+}
+
+/// Gives each thread its own shard in the order threads first use a sticky
+/// pool, so `n` threads on `n` shards never share one.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sticky;
+
+impl ShardSelector for Sticky {
+    #[inline]
+    fn select(&self, shards: usize) -> usize {
+        THREAD_INDEX.with(|index| index % shards)
+    }
+}
+
+/// A pool split into several independent [`Pool`]s, to spread contention
+/// over many threads.
+///
+/// Each get is served by the shard chosen by the [`ShardSelector`] `S`, and
+/// the object is returned to the shard it came from. Shards do not steal
+/// from each other, so a get allocates when its shard is empty even if
+/// another shard has idle objects; picking a selector that matches how the
+/// threads of a workload are busy keeps that rare.
+///
+/// ```
+/// use opool::{BufferAllocator, RoundRobin, ShardedPool};
+///
+/// let pool = ShardedPool::new(4, 16, BufferAllocator::new(1024)).with_selector(RoundRobin::default());
+/// assert_eq!(pool.get().len(), 1024);
+/// assert_eq!(pool.cap(), 64);
+/// ```
+pub struct ShardedPool<P: PoolAllocator<T>, T, S = ThreadHash> {
+    shards: Box<[Pool<P, T>]>,
+    selector: S,
+}
+
+impl<P: PoolAllocator<T> + Clone, T> ShardedPool<P, T> {
+    /// Creates a new ShardedPool of `shards` shards, each holding up to
+    /// `shard_size` idle objects and using a clone of `allocator`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    pub fn new(shards: usize, shard_size: usize, allocator: P) -> Self {
+        assert!(shards != 0, "a sharded pool needs at least one shard");
+        ShardedPool {
+            shards: (0..shards)
+                .map(|_| Pool::new(shard_size, allocator.clone()))
+                .collect(),
+            selector: ThreadHash,
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T, S: ShardSelector> ShardedPool<P, T, S> {
+    /// Creates a new ShardedPool from the given shards and selector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is empty.
+    pub fn from_shards(shards: Vec<Pool<P, T>>, selector: S) -> Self {
+        assert!(
+            !shards.is_empty(),
+            "a sharded pool needs at least one shard"
+        );
+        ShardedPool {
+            shards: shards.into_boxed_slice(),
+            selector,
+        }
+    }
+
+    /// Replaces the shard selector of the pool.
+    pub fn with_selector<R: ShardSelector>(self, selector: R) -> ShardedPool<P, T, R> {
+        ShardedPool {
+            shards: self.shards,
+            selector,
+        }
+    }
+
+    /// Gets an object from the shard picked by the selector.
+    ///
+    /// If the shard is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get(&self) -> RefGuard<'_, P, T> {
+        self.shard().get()
+    }
+
+    /// Returns the shard the selector picks for the current get.
+    pub fn shard(&self) -> &Pool<P, T> {
+        let index = self.selector.select(self.shards.len());
+        &self.shards[index % self.shards.len()]
+    }
+
+    /// Returns the shards of the pool.
+    pub fn shards(&self) -> &[Pool<P, T>] {
+        &self.shards
+    }
+
+    /// Returns the shard selector of the pool.
+    pub fn selector(&self) -> &S {
+        &self.selector
+    }
+
+    /// Returns the number of idle objects in all shards.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(Pool::len).sum()
+    }
+
+    /// Returns `true` if no shard holds an idle object.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(Pool::is_empty)
+    }
+
+    /// Returns the maximum number of idle objects of all shards together.
+    pub fn cap(&self) -> usize {
+        self.shards
+            .iter()
+            .fold(0, |cap, shard| cap.saturating_add(shard.cap()))
+    }
+}
+
+impl<P: PoolAllocator<T>, T, S: ShardSelector> ObjectPool<T> for ShardedPool<P, T, S> {
+    type Guard<'a>
+        = RefGuard<'a, P, T>
+    where
+        Self: 'a;
+
+    #[inline(always)]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    fn get(&self) -> Self::Guard<'_> {
+        ShardedPool::get(self)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        ShardedPool::len(self)
+    }
+
+    #[inline(always)]
+    fn cap(&self) -> usize {
+        ShardedPool::cap(self)
+    }
+}
+
+impl<P: PoolAllocator<T> + fmt::Debug, T: fmt::Debug, S: fmt::Debug> fmt::Debug
+    for ShardedPool<P, T, S>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedPool")
+            .field("shards", &self.shards)
+            .field("selector", &self.selector)
+            .finish()
+    }
+}
@@ -0,0 +1,373 @@
+use crate::sync::{AtomicUsize, Ordering};
+use core::{cell::Cell, fmt};
+
+/// A snapshot of the state of a pool.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Name of the pool, if it was given one.
+    pub name: Option<&'static str>,
+    /// Number of objects the pool has created with its allocator.
+    pub allocated: usize,
+    /// Number of gets served with an idle object.
+    pub hits: usize,
+    /// Number of gets that found the pool empty and had to allocate.
+    pub misses: usize,
+    /// Number of objects handed back to the pool by guards.
+    pub returns: usize,
+    /// Number of returned objects that failed
+    /// [`PoolAllocator::is_valid`](crate::PoolAllocator::is_valid), whether
+    /// they were dropped or quarantined, including idle objects discarded by
+    /// `purge_invalid`.
+    pub dropped_invalid: usize,
+    /// Number of returned objects that were dropped because the pool was
+    /// full.
+    pub dropped_full: usize,
+    /// Number of objects belonging to the pool that still exist, whether they
+    /// are idle in the pool or checked out.
+    pub live: usize,
+    /// Number of idle objects in the pool.
+    pub idle: usize,
+    /// Maximum number of idle objects the pool can hold.
+    pub capacity: usize,
+}
+
+impl PoolStats {
+    /// Returns the number of objects currently checked out of the pool.
+    pub fn in_use(&self) -> usize {
+        self.live.saturating_sub(self.idle)
+    }
+
+    /// Returns the fraction of the live objects that are checked out, between
+    /// `0.0` and `1.0`.
+    ///
+    /// A utilization that stays close to `1.0` means the pool is
+    /// under-provisioned, one that stays close to `0.0` means most objects sit
+    /// idle.
+    pub fn utilization(&self) -> f64 {
+        ratio(self.in_use(), self.live)
+    }
+
+    /// Returns the fraction of the capacity that is filled with idle objects,
+    /// between `0.0` and `1.0`.
+    pub fn fill_ratio(&self) -> f64 {
+        ratio(self.idle, self.capacity)
+    }
+
+    /// Returns the fraction of gets that had to allocate, between `0.0` and
+    /// `1.0`.
+    pub fn miss_rate(&self) -> f64 {
+        ratio(self.misses, self.hits + self.misses)
+    }
+}
+
+impl PoolStats {
+    /// Returns the activity of the pool between an `earlier` snapshot and
+    /// this one.
+    ///
+    /// Dividing the delta by the time between the snapshots gives per-second
+    /// rates. If a counter went backwards because the stats were reset in
+    /// between, its delta counts from the reset.
+    pub fn diff(&self, earlier: &PoolStats) -> PoolStatsDelta {
+        let allocated = counter_delta(self.allocated, earlier.allocated);
+        PoolStatsDelta {
+            allocated,
+            hits: counter_delta(self.hits, earlier.hits),
+            misses: counter_delta(self.misses, earlier.misses),
+            returns: counter_delta(self.returns, earlier.returns),
+            dropped_invalid: counter_delta(self.dropped_invalid, earlier.dropped_invalid),
+            dropped_full: counter_delta(self.dropped_full, earlier.dropped_full),
+            released: (earlier.live + allocated).saturating_sub(self.live),
+            live: self.live as isize - earlier.live as isize,
+        }
+    }
+}
+
+fn counter_delta(later: usize, earlier: usize) -> usize {
+    later.checked_sub(earlier).unwrap_or(later)
+}
+
+/// The activity of a pool between two [`PoolStats`] snapshots, returned by
+/// [`PoolStats::diff`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStatsDelta {
+    /// Number of objects the pool created with its allocator.
+    pub allocated: usize,
+    /// Number of gets served with an idle object.
+    pub hits: usize,
+    /// Number of gets that found the pool empty and had to allocate.
+    pub misses: usize,
+    /// Number of objects handed back to the pool by guards.
+    pub returns: usize,
+    /// Number of returned or idle objects that failed validation.
+    pub dropped_invalid: usize,
+    /// Number of returned objects that were dropped because the pool was
+    /// full.
+    pub dropped_full: usize,
+    /// Number of objects that were dropped or left the pool for good.
+    pub released: usize,
+    /// Change in the number of live objects.
+    pub live: isize,
+}
+
+impl PoolStatsDelta {
+    /// Returns the number of gets.
+    pub fn gets(&self) -> usize {
+        self.hits + self.misses
+    }
+
+    /// Returns the fraction of gets that had to allocate, between `0.0` and
+    /// `1.0`.
+    pub fn miss_rate(&self) -> f64 {
+        ratio(self.misses, self.gets())
+    }
+}
+
+fn ratio(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+impl fmt::Display for PoolStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(name) = self.name {
+            write!(f, "{name}: ")?;
+        }
+        write!(
+            f,
+            "in use {}/{} ({:.1}%), idle {}/{} ({:.1}%), allocated {}",
+            self.in_use(),
+            self.live,
+            self.utilization() * 100.0,
+            self.idle,
+            self.capacity,
+            self.fill_ratio() * 100.0,
+            self.allocated,
+        )
+    }
+}
+
+/// Counters of a [`crate::Pool`].
+///
+/// Apart from `hits` and `returns`, they are only updated on the cold paths
+/// where an object is created or leaves the pool for good.
+#[derive(Debug, Default)]
+pub(crate) struct Stats {
+    allocated: AtomicUsize,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    returns: AtomicUsize,
+    dropped_invalid: AtomicUsize,
+    dropped_full: AtomicUsize,
+    live: AtomicUsize,
+}
+
+impl Stats {
+    /// Records a newly allocated object.
+    #[inline]
+    pub(crate) fn allocated(&self) {
+        self.allocated.fetch_add(1, Ordering::Relaxed);
+        self.live.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records gets served with idle objects.
+    #[inline]
+    pub(crate) fn hit(&self, n: usize) {
+        self.hits.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Records a get that had to allocate a new object.
+    #[inline]
+    pub(crate) fn missed(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        self.allocated();
+    }
+
+    /// Records a get that had to allocate a new object, unless the pool
+    /// already has `max` live objects, in which case nothing is recorded and
+    /// `false` is returned.
+    #[cfg(feature = "std")]
+    pub(crate) fn try_missed(&self, max: usize) -> bool {
+        let reserved = self
+            .live
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| {
+                (live < max).then_some(live + 1)
+            })
+            .is_ok();
+        if reserved {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            self.allocated.fetch_add(1, Ordering::Relaxed);
+        }
+        reserved
+    }
+
+    /// Resets the cumulative counters to zero.
+    pub(crate) fn reset(&self) {
+        self.allocated.store(0, Ordering::Relaxed);
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+        self.returns.store(0, Ordering::Relaxed);
+        self.dropped_invalid.store(0, Ordering::Relaxed);
+        self.dropped_full.store(0, Ordering::Relaxed);
+    }
+
+    /// Records objects handed back to the pool.
+    #[inline]
+    pub(crate) fn returned(&self, n: usize) {
+        self.returns.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Records a returned object that failed validation.
+    #[inline]
+    pub(crate) fn invalid(&self) {
+        self.dropped_invalid.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records returned objects that did not fit in the pool.
+    #[inline]
+    pub(crate) fn overflowed(&self, n: usize) {
+        self.dropped_full.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Records objects that joined the pool without being allocated by it.
+    #[inline]
+    pub(crate) fn adopted(&self, n: usize) {
+        self.live.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Records objects that were dropped or taken out of the pool for good.
+    #[inline]
+    pub(crate) fn released(&self, n: usize) {
+        self.live.fetch_sub(n, Ordering::Relaxed);
+    }
+
+    /// Returns the number of objects checked out, given the number of idle
+    /// objects.
+    #[inline]
+    pub(crate) fn in_use(&self, idle: usize) -> usize {
+        self.live.load(Ordering::Relaxed).saturating_sub(idle)
+    }
+
+    pub(crate) fn snapshot(
+        &self,
+        name: Option<&'static str>,
+        idle: usize,
+        capacity: usize,
+    ) -> PoolStats {
+        PoolStats {
+            name,
+            allocated: self.allocated.load(Ordering::Relaxed),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            returns: self.returns.load(Ordering::Relaxed),
+            dropped_invalid: self.dropped_invalid.load(Ordering::Relaxed),
+            dropped_full: self.dropped_full.load(Ordering::Relaxed),
+            live: self.live.load(Ordering::Relaxed),
+            idle,
+            capacity,
+        }
+    }
+}
+
+/// Counters of a [`crate::LocalPool`], see [`Stats`].
+#[derive(Debug, Default)]
+pub(crate) struct LocalStats {
+    allocated: Cell<usize>,
+    hits: Cell<usize>,
+    misses: Cell<usize>,
+    returns: Cell<usize>,
+    dropped_invalid: Cell<usize>,
+    dropped_full: Cell<usize>,
+    live: Cell<usize>,
+}
+
+impl LocalStats {
+    /// Records a newly allocated object.
+    #[inline]
+    pub(crate) fn allocated(&self) {
+        self.allocated.set(self.allocated.get() + 1);
+        self.live.set(self.live.get() + 1);
+    }
+
+    /// Records gets served with idle objects.
+    #[inline]
+    pub(crate) fn hit(&self, n: usize) {
+        self.hits.set(self.hits.get() + n);
+    }
+
+    /// Records a get that had to allocate a new object.
+    #[inline]
+    pub(crate) fn missed(&self) {
+        self.misses.set(self.misses.get() + 1);
+        self.allocated();
+    }
+
+    /// Resets the cumulative counters to zero.
+    pub(crate) fn reset(&self) {
+        self.allocated.set(0);
+        self.hits.set(0);
+        self.misses.set(0);
+        self.returns.set(0);
+        self.dropped_invalid.set(0);
+        self.dropped_full.set(0);
+    }
+
+    /// Records objects handed back to the pool.
+    #[inline]
+    pub(crate) fn returned(&self, n: usize) {
+        self.returns.set(self.returns.get() + n);
+    }
+
+    /// Records a returned object that failed validation.
+    #[inline]
+    pub(crate) fn invalid(&self) {
+        self.dropped_invalid.set(self.dropped_invalid.get() + 1);
+    }
+
+    /// Records returned objects that did not fit in the pool.
+    #[inline]
+    pub(crate) fn overflowed(&self, n: usize) {
+        self.dropped_full.set(self.dropped_full.get() + n);
+    }
+
+    /// Records objects that joined the pool without being allocated by it.
+    #[inline]
+    pub(crate) fn adopted(&self, n: usize) {
+        self.live.set(self.live.get() + n);
+    }
+
+    /// Records objects that were dropped or taken out of the pool for good.
+    #[inline]
+    pub(crate) fn released(&self, n: usize) {
+        self.live.set(self.live.get() - n);
+    }
+
+    /// Returns the number of objects checked out, given the number of idle
+    /// objects.
+    #[inline]
+    pub(crate) fn in_use(&self, idle: usize) -> usize {
+        self.live.get().saturating_sub(idle)
+    }
+
+    pub(crate) fn snapshot(
+        &self,
+        name: Option<&'static str>,
+        idle: usize,
+        capacity: usize,
+    ) -> PoolStats {
+        PoolStats {
+            name,
+            allocated: self.allocated.get(),
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            returns: self.returns.get(),
+            dropped_invalid: self.dropped_invalid.get(),
+            dropped_full: self.dropped_full.get(),
+            live: self.live.get(),
+            idle,
+            capacity,
+        }
+    }
+}
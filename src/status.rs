@@ -0,0 +1,17 @@
+/// What happened to an object handed back to its pool.
+///
+/// Returned by the `return_strict` method of the guards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReturnStatus {
+    /// The object was stored in the pool for reuse.
+    Returned,
+    /// The object was dropped because the pool was full.
+    DroppedFull,
+    /// The object was dropped because it failed
+    /// [`PoolAllocator::is_valid`](crate::PoolAllocator::is_valid).
+    DroppedInvalid,
+    /// The object failed validation and was kept in the quarantine of the
+    /// pool.
+    Quarantined,
+}
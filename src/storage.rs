@@ -0,0 +1,333 @@
+use crate::{
+    queue::{ArrayQueue, Lock},
+    sync::{spin_loop, AtomicPtr, AtomicUsize, Ordering},
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::{marker::PhantomData, mem, ptr};
+
+/// Capacity of the first segment of a storage.
+const FIRST_SEGMENT: usize = 32;
+
+/// A fixed-size block of a [`Storage`], linked to the next block.
+struct Segment<T> {
+    queue: ArrayQueue<T>,
+    next: AtomicPtr<Segment<T>>,
+}
+
+/// Storage for the idle objects of a [`crate::Pool`], lock-free unless the
+/// `crossbeam` feature is disabled.
+///
+/// Objects are kept in a linked list of fixed-size segments. Segments are
+/// only allocated when every existing segment is full, each one as large as
+/// all previous ones together, until the segments add up to the capacity of
+/// the storage. Storage that never holds an object never allocates, and a
+/// storage with a capacity of zero rejects every push. Segments are freed when
+/// the storage is dropped.
+///
+/// Zero-sized objects carry no data, so they bypass the segments entirely and
+/// the storage only counts them.
+///
+/// A LIFO storage keeps its objects in a stack guarded by a lock instead of
+/// the segments, so the most recently pushed object is popped first.
+///
+/// Objects are reserved before they are popped: `avail` counts the objects in
+/// the segments that are not reserved yet, which lets several objects be
+/// claimed in one atomic step.
+pub(crate) struct Storage<T> {
+    head: AtomicPtr<Segment<T>>,
+    capacity: AtomicUsize,
+    /// Total capacity of the segments allocated or being allocated.
+    allocated: AtomicUsize,
+    avail: AtomicUsize,
+    stack: Option<Lock<Vec<T>>>,
+    _marker: PhantomData<ArrayQueue<T>>,
+}
+
+impl<T> Storage<T> {
+    const IS_ZST: bool = mem::size_of::<T>() == 0;
+
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self::with_stack(capacity, None)
+    }
+
+    /// Creates a LIFO storage.
+    pub(crate) fn new_lifo(capacity: usize) -> Self {
+        Self::with_stack(capacity, Some(Lock::new(Vec::new())))
+    }
+
+    fn with_stack(capacity: usize, stack: Option<Lock<Vec<T>>>) -> Self {
+        Storage {
+            head: AtomicPtr::new(ptr::null_mut()),
+            capacity: AtomicUsize::new(capacity),
+            allocated: AtomicUsize::new(0),
+            avail: AtomicUsize::new(0),
+            stack,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn is_lifo(&self) -> bool {
+        self.stack.is_some()
+    }
+
+    /// Returns an iterator over the allocated segments, oldest first.
+    #[inline(always)]
+    fn segments(&self) -> impl Iterator<Item = &Segment<T>> {
+        // Safety: segments are only freed when the storage is dropped.
+        let head = unsafe { self.head.load(Ordering::Acquire).as_ref() };
+        core::iter::successors(head, |segment| unsafe {
+            segment.next.load(Ordering::Acquire).as_ref()
+        })
+    }
+
+    /// Pushes an object into the first segment with room, growing the storage
+    /// if every segment is full, without making the object available.
+    #[inline(always)]
+    fn push_raw(&self, mut obj: T) -> Result<(), T> {
+        if let Some(stack) = &self.stack {
+            return stack.with(|stack| {
+                if stack.len() >= self.capacity() {
+                    return Err(obj);
+                }
+                stack.push(obj);
+                Ok(())
+            });
+        }
+        for segment in self.segments() {
+            match segment.queue.push(obj) {
+                Ok(()) => return Ok(()),
+                Err(rejected) => obj = rejected,
+            }
+        }
+        self.grow(obj)
+    }
+
+    /// Allocates a new segment holding `obj` and appends it to the storage,
+    /// or hands `obj` back if the storage is at capacity.
+    #[cold]
+    fn grow(&self, obj: T) -> Result<(), T> {
+        let capacity = self.capacity();
+        let mut size = 0;
+        let reserved =
+            self.allocated
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |allocated| {
+                    size = allocated
+                        .max(FIRST_SEGMENT)
+                        .min(capacity.saturating_sub(allocated));
+                    (size > 0).then_some(allocated + size)
+                });
+        if reserved.is_err() {
+            return Err(obj);
+        }
+        let queue = ArrayQueue::new(size);
+        let _ = queue.push(obj);
+        let segment = Box::into_raw(Box::new(Segment {
+            queue,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }));
+        let mut link = &self.head;
+        loop {
+            match link.compare_exchange(
+                ptr::null_mut(),
+                segment,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return Ok(()),
+                // Safety: segments are only freed when the storage is dropped.
+                Err(current) => link = unsafe { &(*current).next },
+            }
+        }
+    }
+
+    /// Pushes an object into the storage, handing it back if the storage is
+    /// full.
+    #[inline(always)]
+    pub(crate) fn push(&self, obj: T) -> Result<(), T> {
+        if Self::IS_ZST {
+            return self.push_zst(obj);
+        }
+        if self.len() >= self.capacity() {
+            return Err(obj);
+        }
+        self.push_raw(obj)?;
+        self.avail.fetch_add(1, Ordering::Release);
+        Ok(())
+    }
+
+    /// Pushes objects into the storage until it is full and makes them
+    /// available in a single step.
+    ///
+    /// Returns the number of objects pushed and the first object that did not
+    /// fit, if any. The objects after it are left in `objs`.
+    pub(crate) fn push_many(&self, objs: impl Iterator<Item = T>) -> (usize, Option<T>) {
+        let mut pushed = 0;
+        if Self::IS_ZST {
+            for obj in objs {
+                if let Err(obj) = self.push_zst(obj) {
+                    return (pushed, Some(obj));
+                }
+                pushed += 1;
+            }
+            return (pushed, None);
+        }
+        let mut rejected = None;
+        let room = self.capacity().saturating_sub(self.len());
+        for obj in objs {
+            if pushed == room {
+                rejected = Some(obj);
+                break;
+            }
+            if let Err(obj) = self.push_raw(obj) {
+                rejected = Some(obj);
+                break;
+            }
+            pushed += 1;
+        }
+        self.avail.fetch_add(pushed, Ordering::Release);
+        (pushed, rejected)
+    }
+
+    /// Counts a zero-sized object as available, handing it back if the
+    /// storage is full.
+    #[inline(always)]
+    fn push_zst(&self, obj: T) -> Result<(), T> {
+        let counted = self
+            .avail
+            .fetch_update(Ordering::Release, Ordering::Relaxed, |avail| {
+                (avail < self.capacity()).then_some(avail + 1)
+            });
+        match counted {
+            Ok(_) => {
+                mem::forget(obj);
+                Ok(())
+            }
+            Err(_) => Err(obj),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn pop(&self) -> Option<T> {
+        if self.reserve(1) {
+            Some(self.pop_reserved())
+        } else {
+            None
+        }
+    }
+
+    /// Pops the object [`Self::pop`] hands out first, or in a LIFO storage the
+    /// object it hands out last, which was pushed the longest ago.
+    pub(crate) fn pop_cold(&self) -> Option<T> {
+        if !self.reserve(1) {
+            return None;
+        }
+        match &self.stack {
+            // The reserved object is in the stack, see `pop_reserved`.
+            Some(stack) if !Self::IS_ZST => Some(stack.with(|stack| stack.remove(0))),
+            _ => Some(self.pop_reserved()),
+        }
+    }
+
+    /// Reserves `n` objects for [`Self::pop_reserved`], or nothing if fewer
+    /// than `n` objects are available.
+    #[inline(always)]
+    pub(crate) fn reserve(&self, n: usize) -> bool {
+        self.avail
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |avail| {
+                avail.checked_sub(n)
+            })
+            .is_ok()
+    }
+
+    /// Reserves up to `n` objects for [`Self::pop_reserved`] in one step,
+    /// returning how many were reserved.
+    #[inline(always)]
+    pub(crate) fn reserve_up_to(&self, n: usize) -> usize {
+        let mut reserved = 0;
+        let _ = self
+            .avail
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |avail| {
+                reserved = avail.min(n);
+                (reserved != 0).then(|| avail - reserved)
+            });
+        reserved
+    }
+
+    /// Pops an object that was reserved with [`Self::reserve`].
+    #[inline(always)]
+    pub(crate) fn pop_reserved(&self) -> T {
+        if Self::IS_ZST {
+            // Safety: a zero-sized object has no bytes to read, and this one
+            // stands in for an object that was forgotten by `push_zst`.
+            return unsafe { ptr::NonNull::dangling().read() };
+        }
+        if let Some(stack) = &self.stack {
+            // The reserved object was pushed before it became available, and
+            // every other pop took its own reservation.
+            return stack
+                .with(Vec::pop)
+                .expect("reserved object is in the stack");
+        }
+        loop {
+            // The reserved object was pushed before it became available, so
+            // a segment holds it, but it may move past a racing pop.
+            for segment in self.segments() {
+                if let Some(obj) = segment.queue.pop() {
+                    return obj;
+                }
+            }
+            spin_loop();
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.avail.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity.load(Ordering::Relaxed)
+    }
+
+    /// Changes the capacity of the storage.
+    ///
+    /// Segments that are already allocated are kept, so after shrinking they
+    /// can hold more objects than the capacity, and pushes check the capacity
+    /// instead. A storage only allocates new segments while they add up to
+    /// less than its capacity.
+    pub(crate) fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::Relaxed);
+    }
+}
+
+impl<T> Drop for Storage<T> {
+    fn drop(&mut self) {
+        if Self::IS_ZST {
+            for _ in 0..self.avail.load(Ordering::Relaxed) {
+                drop(self.pop_reserved());
+            }
+            return;
+        }
+        let mut segment = self.head.load(Ordering::Acquire);
+        while !segment.is_null() {
+            // Safety: segments were created by `Box::into_raw` and are dropped
+            // only once, here.
+            let boxed = unsafe { Box::from_raw(segment) };
+            segment = boxed.next.load(Ordering::Acquire);
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for Storage<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Storage")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .field("segments", &self.segments().count())
+            .field("lifo", &self.is_lifo())
+            .finish()
+    }
+}
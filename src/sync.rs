@@ -0,0 +1,18 @@
+//! Synchronization primitives of the concurrent pool.
+//!
+//! Compiling with `RUSTFLAGS="--cfg opool_loom"` swaps them for the
+//! model-checked versions of [loom](https://docs.rs/loom), so the checkout and
+//! return logic can be tested under every thread interleaving. The cfg is
+//! specific to this crate so that dependencies with their own loom support,
+//! such as tokio, are not switched over as well.
+
+#[cfg(not(opool_loom))]
+pub(crate) use core::{
+    hint::spin_loop,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+#[cfg(opool_loom)]
+pub(crate) use loom::{
+    hint::spin_loop,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
@@ -0,0 +1,48 @@
+use crate::PoolAllocator;
+
+/// An allocator that creates objects by cloning a prototype.
+///
+/// This removes the need for a custom allocator when pooling preconfigured
+/// objects. By default checked out objects are not reset; use
+/// [`Self::with_reset`] to restore every recycled object to the prototype with
+/// [`Clone::clone_from`], which can reuse the object's existing allocations.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateAllocator<T> {
+    prototype: T,
+    reset: bool,
+}
+
+impl<T: Clone> TemplateAllocator<T> {
+    /// Creates a new TemplateAllocator that clones `prototype`.
+    pub const fn new(prototype: T) -> Self {
+        TemplateAllocator {
+            prototype,
+            reset: false,
+        }
+    }
+
+    /// Makes the allocator reset recycled objects to the prototype.
+    pub fn with_reset(mut self) -> Self {
+        self.reset = true;
+        self
+    }
+
+    /// Returns the prototype that new objects are cloned from.
+    pub fn prototype(&self) -> &T {
+        &self.prototype
+    }
+}
+
+impl<T: Clone> PoolAllocator<T> for TemplateAllocator<T> {
+    #[inline]
+    fn reset(&self, obj: &mut T) {
+        if self.reset {
+            obj.clone_from(&self.prototype);
+        }
+    }
+
+    #[inline]
+    fn allocate(&self) -> T {
+        self.prototype.clone()
+    }
+}
@@ -0,0 +1,331 @@
+use crate::{ObjectPool, PoolAllocator};
+use alloc::{collections::VecDeque, sync::Arc, vec::Vec};
+use core::{
+    fmt,
+    mem::ManuallyDrop,
+    ops::{Deref, DerefMut},
+};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// Locks a mutex, ignoring poisoning caused by panics in the code under test.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A call made to a [`ScriptedAllocator`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocatorCall {
+    /// A call to [`PoolAllocator::allocate`].
+    Allocate,
+    /// A call to [`PoolAllocator::reset`].
+    Reset,
+    /// A call to [`PoolAllocator::is_valid`], with the result it returned.
+    Validate {
+        /// Whether the object was reported as valid.
+        valid: bool,
+    },
+}
+
+struct Script<T> {
+    allocations: VecDeque<Option<T>>,
+    validations: VecDeque<bool>,
+    calls: Vec<AllocatorCall>,
+}
+
+/// An allocator whose results are scripted by the test using it.
+///
+/// Allocations return the values queued with [`Self::push_allocation`] in
+/// order, falling back to the factory once the queue is empty, and validations
+/// return the results queued with [`Self::push_validation`], falling back to
+/// `true`. Every call is recorded and can be inspected with [`Self::calls`].
+///
+/// Clones share the same script, so a clone can be kept to drive and inspect
+/// the allocator after it has been moved into a pool.
+pub struct ScriptedAllocator<T> {
+    factory: Arc<dyn Fn() -> T + Send + Sync>,
+    script: Arc<Mutex<Script<T>>>,
+}
+
+impl<T> ScriptedAllocator<T> {
+    /// Creates a new ScriptedAllocator that allocates with `factory` when no
+    /// allocation is scripted.
+    pub fn new(factory: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        ScriptedAllocator {
+            factory: Arc::new(factory),
+            script: Arc::new(Mutex::new(Script {
+                allocations: VecDeque::new(),
+                validations: VecDeque::new(),
+                calls: Vec::new(),
+            })),
+        }
+    }
+
+    /// Queues a value to be returned by the next unscripted allocation.
+    pub fn push_allocation(&self, value: T) {
+        lock(&self.script).allocations.push_back(Some(value));
+    }
+
+    /// Queues an allocation that fails by panicking, to exercise the unwind
+    /// paths of the code under test.
+    pub fn push_allocation_failure(&self) {
+        lock(&self.script).allocations.push_back(None);
+    }
+
+    /// Queues the result of the next unscripted validation.
+    pub fn push_validation(&self, valid: bool) {
+        lock(&self.script).validations.push_back(valid);
+    }
+
+    /// Returns the calls made to the allocator so far, in order.
+    pub fn calls(&self) -> Vec<AllocatorCall> {
+        lock(&self.script).calls.clone()
+    }
+
+    /// Forgets the calls recorded so far.
+    pub fn clear_calls(&self) {
+        lock(&self.script).calls.clear();
+    }
+}
+
+impl<T> Clone for ScriptedAllocator<T> {
+    fn clone(&self) -> Self {
+        ScriptedAllocator {
+            factory: self.factory.clone(),
+            script: self.script.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for ScriptedAllocator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let script = lock(&self.script);
+        f.debug_struct("ScriptedAllocator")
+            .field("scripted_allocations", &script.allocations.len())
+            .field("scripted_validations", &script.validations.len())
+            .field("calls", &script.calls)
+            .finish()
+    }
+}
+
+impl<T> PoolAllocator<T> for ScriptedAllocator<T> {
+    fn reset(&self, _obj: &mut T) {
+        lock(&self.script).calls.push(AllocatorCall::Reset);
+    }
+
+    fn allocate(&self) -> T {
+        let mut script = lock(&self.script);
+        script.calls.push(AllocatorCall::Allocate);
+        match script.allocations.pop_front() {
+            Some(Some(value)) => value,
+            Some(None) => {
+                drop(script);
+                panic!("ScriptedAllocator: scripted allocation failure");
+            }
+            None => {
+                drop(script);
+                (self.factory)()
+            }
+        }
+    }
+
+    fn is_valid(&self, _obj: &T) -> bool {
+        let mut script = lock(&self.script);
+        let valid = script.validations.pop_front().unwrap_or(true);
+        script.calls.push(AllocatorCall::Validate { valid });
+        valid
+    }
+}
+
+/// A snapshot of the activity recorded by a [`MockPool`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MockPoolRecord {
+    /// Number of objects handed out.
+    pub gets: usize,
+    /// Number of gets that had to allocate a new object.
+    pub allocations: usize,
+    /// Number of objects returned to the pool.
+    pub returns: usize,
+    /// Number of returned objects that failed validation and were dropped.
+    pub discards: usize,
+    /// Number of idle objects that failed to reset when they were handed out
+    /// again, and were dropped and replaced with a new object.
+    pub reset_failures: usize,
+}
+
+impl MockPoolRecord {
+    /// Returns the number of objects handed out and not yet returned.
+    pub fn outstanding(&self) -> usize {
+        self.gets - self.returns - self.discards
+    }
+}
+
+struct MockState<T> {
+    idle: VecDeque<T>,
+    record: MockPoolRecord,
+}
+
+/// An unbounded, deterministic pool that records how it is used.
+///
+/// Idle objects are handed out in the order they were returned or pushed with
+/// [`Self::push_idle`], and the pool never drops a valid object. Use it through
+/// [`ObjectPool`] in place of a real pool to unit-test code that handles
+/// pooled objects.
+pub struct MockPool<P: PoolAllocator<T>, T> {
+    allocator: P,
+    state: Mutex<MockState<T>>,
+}
+
+impl<P: PoolAllocator<T>, T> MockPool<P, T> {
+    /// Creates a new, empty MockPool with the given allocator.
+    pub fn new(allocator: P) -> Self {
+        MockPool {
+            allocator,
+            state: Mutex::new(MockState {
+                idle: VecDeque::new(),
+                record: MockPoolRecord::default(),
+            }),
+        }
+    }
+
+    /// Gets an object from the pool.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    pub fn get(&self) -> MockGuard<'_, P, T> {
+        let idle = {
+            let mut state = lock(&self.state);
+            state.record.gets += 1;
+            let idle = state.idle.pop_front();
+            if idle.is_none() {
+                state.record.allocations += 1;
+            }
+            idle
+        };
+        let mut obj = match idle {
+            Some(mut obj) => {
+                if self.allocator.try_reset(&mut obj) {
+                    obj
+                } else {
+                    self.allocator.on_destroy(obj);
+                    let mut state = lock(&self.state);
+                    state.record.reset_failures += 1;
+                    state.record.allocations += 1;
+                    drop(state);
+                    self.allocator.allocate()
+                }
+            }
+            None => self.allocator.allocate(),
+        };
+        if !self.allocator.is_warm(&obj) {
+            self.allocator.warm(&mut obj);
+        }
+        MockGuard {
+            obj: ManuallyDrop::new(obj),
+            pool: self,
+        }
+    }
+
+    /// Adds an idle object to the back of the pool.
+    pub fn push_idle(&self, obj: T) {
+        lock(&self.state).idle.push_back(obj);
+    }
+
+    /// Removes and returns all idle objects, in the order they would be
+    /// handed out.
+    pub fn drain_idle(&self) -> Vec<T> {
+        lock(&self.state).idle.drain(..).collect()
+    }
+
+    /// Returns the number of idle objects in the pool.
+    pub fn len(&self) -> usize {
+        lock(&self.state).idle.len()
+    }
+
+    /// Returns `true` if the pool has no idle objects.
+    pub fn is_empty(&self) -> bool {
+        lock(&self.state).idle.is_empty()
+    }
+
+    /// Returns a snapshot of the activity recorded so far.
+    pub fn record(&self) -> MockPoolRecord {
+        lock(&self.state).record
+    }
+
+    /// Returns a reference to the allocator of this pool.
+    pub fn allocator(&self) -> &P {
+        &self.allocator
+    }
+}
+
+impl<P: PoolAllocator<T>, T> fmt::Debug for MockPool<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = lock(&self.state);
+        f.debug_struct("MockPool")
+            .field("idle", &state.idle.len())
+            .field("record", &state.record)
+            .finish()
+    }
+}
+
+impl<P: PoolAllocator<T>, T> ObjectPool<T> for MockPool<P, T> {
+    type Guard<'a>
+        = MockGuard<'a, P, T>
+    where
+        Self: 'a;
+
+    fn get(&self) -> Self::Guard<'_> {
+        MockPool::get(self)
+    }
+
+    fn len(&self) -> usize {
+        MockPool::len(self)
+    }
+
+    /// A MockPool is unbounded, so its capacity is `usize::MAX`.
+    fn cap(&self) -> usize {
+        usize::MAX
+    }
+}
+
+/// The guard returned by [`MockPool`], which returns its object to the pool
+/// when dropped.
+pub struct MockGuard<'a, P: PoolAllocator<T>, T> {
+    obj: ManuallyDrop<T>,
+    pool: &'a MockPool<P, T>,
+}
+
+impl<P: PoolAllocator<T>, T> Deref for MockGuard<'_, P, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.obj
+    }
+}
+
+impl<P: PoolAllocator<T>, T> DerefMut for MockGuard<'_, P, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.obj
+    }
+}
+
+impl<P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for MockGuard<'_, P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("MockGuard").field(&*self.obj).finish()
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Drop for MockGuard<'_, P, T> {
+    fn drop(&mut self) {
+        // Safety: the object is taken exactly once, here.
+        let obj = unsafe { ManuallyDrop::take(&mut self.obj) };
+        let valid = self.pool.allocator.is_valid(&obj);
+        let mut state = lock(&self.pool.state);
+        if valid {
+            state.record.returns += 1;
+            state.idle.push_back(obj);
+        } else {
+            state.record.discards += 1;
+            drop(state);
+            self.pool.allocator.on_destroy(obj);
+        }
+    }
+}
@@ -1,13 +1,41 @@
-use crate::PoolAllocator;
-use alloc::{collections::VecDeque, fmt, rc::Rc};
+#[cfg(feature = "log")]
+use crate::logging::{log_event, LogEvent};
+#[cfg(feature = "tracing")]
+use crate::trace::{trace_event, TraceEvent};
+use crate::{
+    concurrent::{outstanding_on_drop, AbortOnUnwind},
+    stats::LocalStats,
+    ManualGuard, Pool, PoolAllocator, PoolConfig, PoolError, PoolObserver, PoolStats, ResetPolicy,
+    ReturnStatus,
+};
+#[cfg(feature = "diagnostics")]
+use crate::{
+    DenyAlloc, DenyAllocGuard, EventLog, MissSite, MissSites, OwnerTags, PoolEvent, PoolEventKind,
+    TagUsage, TaggedGuard,
+};
+#[cfg(feature = "std")]
+use crate::{HoldWatchdog, LongHeld, MissAlert, WatchedGuard};
+use alloc::{
+    boxed::Box,
+    collections::{vec_deque, VecDeque},
+    fmt,
+    rc::Rc,
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+#[cfg(any(feature = "diagnostics", feature = "std"))]
+use core::panic::Location;
 use core::{
-    cell::UnsafeCell,
+    cell::{Cell, UnsafeCell},
     hash::{Hash, Hasher},
     marker::PhantomData,
-    mem::{forget, MaybeUninit},
+    mem::{self, forget, ManuallyDrop, MaybeUninit},
     ops::{Deref, DerefMut},
     ptr,
 };
+#[cfg(feature = "log")]
+use log::LevelFilter;
 
 /// A struct representing an object pool for local thread, it cannot be moved
 /// between threads.
@@ -18,6 +46,31 @@ use core::{
 pub struct LocalPool<P: PoolAllocator<T>, T> {
     allocator: P,
     storage: UnsafeCell<VecDeque<T>>,
+    capacity: Cell<usize>,
+    stats: LocalStats,
+    shed_threshold: Cell<usize>,
+    name: Option<&'static str>,
+    lazy_prefill: Cell<usize>,
+    prefill_batch: usize,
+    quarantine: UnsafeCell<Vec<T>>,
+    quarantine_limit: usize,
+    drop_check: bool,
+    reset_policy: Cell<ResetPolicy>,
+    observer: Option<Box<dyn PoolObserver<T>>>,
+    #[cfg(feature = "diagnostics")]
+    deny_alloc: DenyAlloc,
+    #[cfg(feature = "diagnostics")]
+    miss_sites: MissSites,
+    #[cfg(feature = "diagnostics")]
+    owner_tags: OwnerTags,
+    #[cfg(feature = "diagnostics")]
+    events: EventLog,
+    #[cfg(feature = "std")]
+    miss_alert: Option<Box<MissAlert>>,
+    #[cfg(feature = "std")]
+    hold_watchdog: Option<Box<HoldWatchdog>>,
+    #[cfg(feature = "log")]
+    log_level: LevelFilter,
     // force the struct to be !Send
     _phantom: PhantomData<*mut usize>,
 }
@@ -28,29 +81,103 @@ impl<P: PoolAllocator<T>, T> LocalPool<P, T> {
     /// This method immediately fills the pool with new objects created by the
     /// allocator.
     pub fn new_prefilled(pool_size: usize, allocator: P) -> Self {
-        let mut storage = VecDeque::with_capacity(pool_size);
-        for _ in 0..pool_size {
-            storage.push_back(allocator.allocate());
-        }
-        LocalPool {
-            allocator,
-            storage: UnsafeCell::new(storage),
-            _phantom: PhantomData,
+        Self::new(pool_size, allocator).prefilled(pool_size)
+    }
+
+    /// Creates a new LocalPool tuned by `config`, with the given allocator.
+    pub fn from_config(config: &PoolConfig, allocator: P) -> Self {
+        let pool = Self::new(config.capacity, allocator)
+            .with_quarantine(config.quarantine)
+            .with_reset_policy(config.reset);
+        pool.update_config(config);
+        match config.prefill_batch {
+            Some(batch) => pool.with_lazy_prefill(config.prefill, batch),
+            None => pool.prefilled(config.prefill),
         }
     }
 
+    fn prefilled(self, count: usize) -> Self {
+        self.prefill(count);
+        self
+    }
+
+    /// Creates a new pool without a capacity limit, so returned objects are
+    /// never dropped because the pool is full.
+    ///
+    /// This suits objects that are cheap to hold but expensive to create: the
+    /// pool keeps every object it ever handed out. Its storage grows as
+    /// objects are returned and is only freed when the pool is dropped. Its
+    /// capacity is reported as `usize::MAX`.
+    pub fn new_unbounded(allocator: P) -> Self {
+        Self::new(usize::MAX, allocator)
+    }
+
     /// Creates a new Object Pool with a given size and allocator.
     ///
     /// Unlike [`Self::new_prefilled`], this method does not immediately fill
     /// the pool with objects.
+    ///
+    /// A `pool_size` of zero creates a pass-through pool: every get allocates
+    /// a new object and every returned object is dropped.
+    ///
+    /// Storage for idle objects is only allocated once the first object is
+    /// returned, so a pool that never sees traffic costs no more than its own
+    /// struct.
     pub fn new(pool_size: usize, allocator: P) -> Self {
         LocalPool {
             allocator,
-            storage: UnsafeCell::new(VecDeque::with_capacity(pool_size)),
+            storage: UnsafeCell::new(VecDeque::new()),
+            capacity: Cell::new(pool_size),
+            stats: LocalStats::default(),
+            shed_threshold: Cell::new(usize::MAX),
+            name: None,
+            lazy_prefill: Cell::new(0),
+            prefill_batch: 0,
+            quarantine: UnsafeCell::new(Vec::new()),
+            quarantine_limit: 0,
+            drop_check: false,
+            reset_policy: Cell::new(ResetPolicy::OnGet),
+            observer: None,
+            #[cfg(feature = "diagnostics")]
+            deny_alloc: DenyAlloc::default(),
+            #[cfg(feature = "diagnostics")]
+            miss_sites: MissSites::default(),
+            #[cfg(feature = "diagnostics")]
+            owner_tags: OwnerTags::default(),
+            #[cfg(feature = "diagnostics")]
+            events: EventLog::default(),
+            #[cfg(feature = "std")]
+            miss_alert: None,
+            #[cfg(feature = "std")]
+            hold_watchdog: None,
+            #[cfg(feature = "log")]
+            log_level: LevelFilter::Warn,
             _phantom: PhantomData,
         }
     }
 
+    /// Creates a new pool with a given size and allocator, holding the given
+    /// objects as its idle objects.
+    ///
+    /// Unlike [`Self::new_prefilled`], the objects are built by the caller,
+    /// for example with data loaded from disk. They are added like with
+    /// [`Self::put`], so objects that fail [`PoolAllocator::is_valid`] or do
+    /// not fit are destroyed instead.
+    pub fn with_objects(pool_size: usize, allocator: P, objs: Vec<T>) -> Self {
+        let mut pool = Self::new(pool_size, allocator);
+        pool.extend(objs);
+        pool
+    }
+
+    /// Creates a new pool holding the given objects, with room for exactly
+    /// as many objects, for example to restore objects from a snapshot.
+    ///
+    /// See [`Self::with_objects`].
+    pub fn from_iter_with(allocator: P, objs: impl IntoIterator<Item = T>) -> Self {
+        let objs: Vec<T> = objs.into_iter().collect();
+        Self::with_objects(objs.len(), allocator, objs)
+    }
+
     /// Get storage as mutable reference
     /// Safety: it's safe to call only if the pool is used by a single threaded.
     #[allow(clippy::mut_from_ref)]
@@ -72,35 +199,888 @@ impl<P: PoolAllocator<T>, T> LocalPool<P, T> {
         Rc::new(self)
     }
 
+    /// Moves the pool to the heap and leaks it, like [`Box::leak`], returning
+    /// a `'static` reference to it.
+    ///
+    /// Guards from [`Self::get`] on the leaked pool are `'static`, so they
+    /// can be stored anywhere without the reference counting of
+    /// [`Self::get_rc`]. The pool is never dropped, so its idle objects are
+    /// never handed to [`PoolAllocator::on_destroy`].
+    pub fn leak(self) -> &'static Self {
+        Box::leak(Box::new(self))
+    }
+
+    /// Converts the pool into a concurrent [`Pool`] that can be shared
+    /// between threads, moving the allocator, the idle objects and the
+    /// settings of the pool over.
+    ///
+    /// This lets a pool be warmed up cheaply during single-threaded setup
+    /// before it is shared. The name, capacity, load shedding threshold,
+    /// reset policy, quarantine limit, drop check, remaining lazy prefill,
+    /// miss alert, hold watchdog and log level all carry over. The observer
+    /// does not, since the observer of a LocalPool does not have to be
+    /// `Send` and `Sync`. Quarantined objects are destroyed, and the stats
+    /// and diagnostics of the new pool start from zero.
+    pub fn into_shared(self) -> Arc<Pool<P, T>> {
+        for obj in self.take_quarantined() {
+            self.dispose(obj);
+        }
+        let mut pool = ManuallyDrop::new(self);
+        // Every field is named, so a new field cannot be forgotten here.
+        let LocalPool {
+            allocator,
+            storage,
+            capacity,
+            stats: _,
+            shed_threshold,
+            name,
+            lazy_prefill,
+            prefill_batch,
+            quarantine,
+            quarantine_limit,
+            drop_check,
+            reset_policy,
+            observer,
+            #[cfg(feature = "diagnostics")]
+                deny_alloc: _,
+            #[cfg(feature = "diagnostics")]
+                miss_sites: _,
+            #[cfg(feature = "diagnostics")]
+                owner_tags: _,
+            #[cfg(feature = "diagnostics")]
+            events,
+            #[cfg(feature = "std")]
+            miss_alert,
+            #[cfg(feature = "std")]
+            hold_watchdog,
+            #[cfg(feature = "log")]
+            log_level,
+            _phantom: _,
+        } = &mut *pool;
+        // Safety: the pool is never dropped as a whole, and each of its
+        // fields that owns memory is moved out exactly once here, so it is
+        // dropped or handed to the new pool exactly once. The fields left
+        // out above own no memory.
+        let (allocator, storage) = unsafe { (ptr::read(allocator), ptr::read(storage)) };
+        // Safety: as above.
+        unsafe {
+            drop(ptr::read(quarantine));
+            drop(ptr::read(observer));
+            #[cfg(feature = "diagnostics")]
+            drop(ptr::read(events));
+        }
+        let mut shared =
+            Pool::from_parts(capacity.get(), allocator, storage.into_inner().into_iter())
+                .with_shed_threshold(shed_threshold.get())
+                .with_reset_policy(reset_policy.get())
+                .with_quarantine(*quarantine_limit);
+        if let Some(name) = *name {
+            shared = shared.with_name(name);
+        }
+        if *drop_check {
+            shared = shared.with_drop_check();
+        }
+        if lazy_prefill.get() != 0 {
+            shared = shared.with_lazy_prefill(lazy_prefill.get(), *prefill_batch);
+        }
+        #[cfg(feature = "std")]
+        {
+            // Safety: as above.
+            let (miss_alert, hold_watchdog) =
+                unsafe { (ptr::read(miss_alert), ptr::read(hold_watchdog)) };
+            if let Some(alert) = miss_alert {
+                shared = shared.with_miss_alert(*alert);
+            }
+            if let Some(watchdog) = hold_watchdog {
+                shared = shared.with_hold_watchdog(*watchdog);
+            }
+        }
+        #[cfg(feature = "log")]
+        {
+            shared = shared.with_log_level(*log_level);
+        }
+        shared.to_rc()
+    }
+
+    /// Sets the name of the pool, which is shown in its `Debug` output and
+    /// its [`PoolStats`].
+    ///
+    /// Names tell apart the metrics of pools of the same type. A name built
+    /// at runtime can be turned into a `&'static str` with `String::leak`.
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Returns the name of the pool, if it was given one.
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// Returns a reference to the allocator of the pool, for example to read
+    /// configuration or counters it carries.
+    #[inline]
+    pub fn allocator(&self) -> &P {
+        &self.allocator
+    }
+
+    /// Returns a mutable reference to the allocator of the pool.
+    ///
+    /// Objects that are already in the pool or checked out are not affected
+    /// by changes to the allocator until they are reset or validated again.
+    #[inline]
+    pub fn allocator_mut(&mut self) -> &mut P {
+        &mut self.allocator
+    }
+
+    /// Replaces the allocator of the pool, and returns the old allocator.
+    ///
+    /// This lets a configuration reload, such as a new buffer size, take
+    /// effect without rebuilding the pool. The idle objects built by the old
+    /// allocator are destroyed with its [`PoolAllocator::on_destroy`], so no
+    /// get hands them out again. Objects that are checked out keep working,
+    /// and are validated and reset by the new allocator when they are
+    /// returned, so an allocator whose [`PoolAllocator::is_valid`] rejects
+    /// the old objects makes sure none of them is reused.
+    ///
+    /// The old allocator is handed back, so the pool must be borrowed
+    /// exclusively; a pool shared through an [`Rc`] can use [`Rc::get_mut`].
+    pub fn replace_allocator(&mut self, allocator: P) -> P {
+        self.clear();
+        mem::replace(&mut self.allocator, allocator)
+    }
+
+    /// Sets the level at which the pool logs notable events, such as dropping
+    /// objects that fail validation or do not fit, through the `log` crate.
+    ///
+    /// The default is [`LevelFilter::Warn`], and [`LevelFilter::Off`]
+    /// silences the pool.
+    #[cfg(feature = "log")]
+    pub fn with_log_level(mut self, level: LevelFilter) -> Self {
+        self.log_level = level;
+        self
+    }
+
+    /// Sets the load shedding threshold of the pool.
+    ///
+    /// Once `threshold` objects are checked out, [`Self::try_get`] and
+    /// [`Self::try_get_rc`] fail fast with [`PoolError::Overloaded`] instead of
+    /// handing out more objects. [`Self::get`] ignores the threshold.
+    pub fn with_shed_threshold(self, threshold: usize) -> Self {
+        self.shed_threshold.set(threshold);
+        self
+    }
+
+    /// Installs an alert whose callback fires when the pool misses faster
+    /// than a given rate for a sustained time.
+    ///
+    /// See [`MissAlert`] for how the rate is measured.
+    #[cfg(feature = "std")]
+    pub fn with_miss_alert(mut self, alert: MissAlert) -> Self {
+        self.miss_alert = Some(Box::new(alert));
+        self
+    }
+
+    /// Installs a watchdog that flags guards returned by
+    /// [`Self::get_watched`] that are held for longer than its threshold.
+    ///
+    /// See [`HoldWatchdog`] for when held guards are checked.
+    #[cfg(feature = "std")]
+    pub fn with_hold_watchdog(mut self, watchdog: HoldWatchdog) -> Self {
+        self.hold_watchdog = Some(Box::new(watchdog));
+        self
+    }
+
+    /// Gets an object from the pool and watches how long it is held with the
+    /// watchdog installed by [`Self::with_hold_watchdog`].
+    ///
+    /// The call site of the get is recorded, so a guard that is held for too
+    /// long can be traced back to it. Without a watchdog, this behaves like
+    /// [`Self::get`].
+    #[cfg(feature = "std")]
+    #[track_caller]
+    pub fn get_watched(&self) -> WatchedGuard<'_, RefLocalGuard<'_, P, T>> {
+        let location = Location::caller();
+        let guard = RefLocalGuard::new(self.checkout(), self);
+        WatchedGuard::new(guard, self.hold_watchdog.as_deref(), location)
+    }
+
+    /// Returns the guards from [`Self::get_watched`] that have been held for
+    /// longer than the threshold of the watchdog, firing its callback for
+    /// those not reported yet.
+    #[cfg(feature = "std")]
+    pub fn long_held(&self) -> Vec<LongHeld> {
+        match &self.hold_watchdog {
+            Some(watchdog) => watchdog.scan(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Applies the settings of `config` that can change on a live pool.
+    ///
+    /// The capacity is changed like [`Self::resize`], and the load shedding
+    /// threshold and reset policy apply to the next get or return. When the
+    /// pool stops resetting objects on get, its idle objects are reset right
+    /// away with [`Self::reset_idle`] so none of them is handed out without
+    /// a reset.
+    ///
+    /// The prefill, prefill batch and quarantine only take effect when a
+    /// pool is built with [`Self::from_config`], so they are ignored here.
+    pub fn update_config(&self, config: &PoolConfig) {
+        if config.capacity != self.cap() {
+            self.resize(config.capacity);
+        }
+        self.shed_threshold
+            .set(config.shed_threshold.unwrap_or(usize::MAX));
+        let old = self.reset_policy.replace(config.reset);
+        if old == ResetPolicy::OnGet && config.reset != ResetPolicy::OnGet {
+            self.reset_idle();
+        }
+    }
+
+    /// Defers prefilling the pool with `count` objects until a get first
+    /// finds the pool empty.
+    ///
+    /// Each get that finds the pool empty creates up to `batch` of the
+    /// remaining objects in addition to its own. Passing `count` as `batch`
+    /// prefills the pool on first use, while a smaller batch spreads the
+    /// warm-up cost over the first misses. A pool that is never used never
+    /// pays for it.
+    pub fn with_lazy_prefill(mut self, count: usize, batch: usize) -> Self {
+        self.lazy_prefill = Cell::new(count.min(self.cap()));
+        self.prefill_batch = batch.max(1);
+        self
+    }
+
+    /// Resets every idle object with [`PoolAllocator::try_reset`], returning
+    /// how many were reset.
+    ///
+    /// Objects that fail to reset are destroyed. Running this at a convenient
+    /// time, such as at startup or during quiet periods, pairs with
+    /// [`Self::with_deferred_reset`] to keep resets off both the get and the
+    /// return path.
+    pub fn reset_idle(&self) -> usize {
+        let mut reset = 0;
+        for _ in 0..self.len() {
+            let Some(mut obj) = self.storage_mut().pop_front() else {
+                break;
+            };
+            if !self.allocator.try_reset(&mut obj) {
+                #[cfg(feature = "log")]
+                self.log_event(LogEvent::ResetFailed);
+                self.destroy(obj);
+                continue;
+            }
+            reset += 1;
+            self.storage_mut().push_back(obj);
+        }
+        reset
+    }
+
+    /// Changes the capacity of the pool, destroying the idle objects that no
+    /// longer fit.
+    ///
+    /// Checked out objects stay valid and are returned to the resized pool,
+    /// so the recycling budget of a long-running pool can be tuned without
+    /// recreating it.
+    pub fn resize(&self, capacity: usize) {
+        self.capacity.set(capacity);
+        self.drop_idle(capacity);
+    }
+
+    /// Tops the pool up to `count` idle objects, or to its capacity if that
+    /// is lower, returning how many objects were created.
+    ///
+    /// Unlike [`Self::new_prefilled`], this can warm the pool up at any time,
+    /// such as once the expected load is known.
+    pub fn prefill(&self, count: usize) -> usize {
+        let missing = count.min(self.cap()).saturating_sub(self.len());
+        for _ in 0..missing {
+            self.stats.allocated();
+            self.storage_mut().push_back(self.allocate());
+        }
+        missing
+    }
+
+    /// Warms the idle objects that are not [`PoolAllocator::is_warm`] yet,
+    /// returning how many were warmed.
+    ///
+    /// This lets the objects of a prefill finish becoming ready off the
+    /// critical path, for example while the thread is otherwise idle.
+    pub fn warm_idle(&self) -> usize {
+        self.with_idle(|idle| {
+            idle.iter_mut()
+                .filter(|obj| !self.allocator.is_warm(obj))
+                .map(|obj| self.allocator.warm(obj))
+                .count()
+        })
+    }
+
+    /// Keeps up to `limit` objects that fail validation in a quarantine
+    /// instead of destroying them, so they can be inspected with
+    /// [`Self::take_quarantined`].
+    ///
+    /// Once the quarantine is full, further invalid objects are destroyed as
+    /// usual. Quarantined objects no longer count as live objects of the pool.
+    pub fn with_quarantine(mut self, limit: usize) -> Self {
+        self.quarantine_limit = limit;
+        self
+    }
+
+    /// Installs an observer whose hooks are called as objects are allocated,
+    /// recycled, discarded or fail validation.
+    ///
+    /// See [`PoolObserver`] for when each hook runs. The observer is not
+    /// carried over by [`Self::into_shared`], since it does not have to be
+    /// `Send` and `Sync`.
+    pub fn with_observer(mut self, observer: impl PoolObserver<T> + 'static) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    /// Sets when recycled objects are reset with
+    /// [`PoolAllocator::try_reset`], see [`ResetPolicy`].
+    pub fn with_reset_policy(mut self, policy: ResetPolicy) -> Self {
+        self.reset_policy = Cell::new(policy);
+        self
+    }
+
+    /// Defers resetting recycled objects to [`Self::reset_idle`], like
+    /// [`ResetPolicy::Deferred`].
+    ///
+    /// Gets no longer call [`PoolAllocator::try_reset`], so neither getting
+    /// nor returning an object pays for resetting it. Objects returned since
+    /// the last call to `reset_idle` are handed out as they were returned.
+    pub fn with_deferred_reset(self) -> Self {
+        self.with_reset_policy(ResetPolicy::Deferred)
+    }
+
+    /// Makes dropping the pool panic if objects it handed out were never
+    /// returned, for example because their guards were leaked with
+    /// `mem::forget`.
+    ///
+    /// The idle objects are still destroyed before the panic. With the `std`
+    /// feature, the check is skipped if the thread is already panicking.
+    pub fn with_drop_check(mut self) -> Self {
+        self.drop_check = true;
+        self
+    }
+
+    /// Takes the objects held in quarantine, oldest first.
+    pub fn take_quarantined(&self) -> Vec<T> {
+        // Safety: the pool is only used by a single thread.
+        core::mem::take(unsafe { &mut *self.quarantine.get() })
+    }
+
     /// Gets an object from the pool.
     ///
     /// If the pool is empty, a new object is created using the allocator.
-    pub fn get(&self) -> RefLocalGuard<P, T> {
-        match self.storage_mut().pop_front() {
-            Some(mut obj) => {
-                self.allocator.reset(&mut obj);
-                RefLocalGuard::new(obj, self)
-            }
-            None => RefLocalGuard::new(self.allocator.allocate(), self),
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get(&self) -> RefLocalGuard<'_, P, T> {
+        RefLocalGuard::new(self.checkout(), self)
+    }
+
+    /// Gets the most recently returned idle object from the pool.
+    ///
+    /// [`Self::get`] hands out the object that has been idle the longest,
+    /// which spreads use evenly over the objects. The most recently used
+    /// object is more likely to still be in the CPU caches, which suits
+    /// cache-sensitive work.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_hot(&self) -> RefLocalGuard<'_, P, T> {
+        let obj = match self.take_idle_with(VecDeque::pop_back) {
+            Some(obj) => obj,
+            None => self.allocate_fallback(),
+        };
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Checkout, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        RefLocalGuard::new(obj, self)
+    }
+
+    /// Gets the idle object that has been in the pool the longest, like
+    /// [`Self::get`].
+    ///
+    /// Taking the coldest object spreads use, such as writes to buffers,
+    /// evenly over all objects of the pool.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_cold(&self) -> RefLocalGuard<'_, P, T> {
+        self.get()
+    }
+
+    /// Gets an object from the pool as a [`ManualGuard`], which must be
+    /// returned with [`Self::put_back`] instead of being dropped.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    ///
+    /// # Dropping the guard
+    ///
+    /// The guard holds no reference to the pool, so dropping it instead of
+    /// putting it back cannot tell the pool. In release builds the object is
+    /// dropped without [`PoolAllocator::on_destroy`], and the pool still
+    /// counts it as live for good, so [`Self::stats`] reports it as checked
+    /// out forever. Debug builds panic when a guard is dropped to catch the
+    /// mistake early.
+    #[inline(always)]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_manual(&self) -> ManualGuard<T> {
+        ManualGuard::new(self.checkout(), self as *const Self as usize)
+    }
+
+    /// Returns an object checked out with [`Self::get_manual`] to the pool.
+    ///
+    /// Unlike dropping a guard, this does not call
+    /// [`PoolAllocator::is_valid`], so the caller must only put back valid
+    /// objects. An object that does not fit in the pool is destroyed.
+    #[inline(always)]
+    pub fn put_back(&self, guard: ManualGuard<T>) {
+        let mut obj = guard.into_obj(self as *const Self as usize);
+        self.stats.returned(1);
+        if !self.reset_returned(&mut obj) {
+            #[cfg(feature = "log")]
+            self.log_event(LogEvent::ResetFailed);
+            return self.destroy(obj);
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_recycle(&obj);
+        }
+        let storage = self.storage_mut();
+        if storage.len() < self.cap() {
+            storage.push_back(obj);
+            #[cfg(feature = "diagnostics")]
+            self.events.record(PoolEventKind::Return, None);
+            #[cfg(feature = "tracing")]
+            self.trace_event(TraceEvent::Return);
+        } else {
+            #[cfg(feature = "log")]
+            self.log_event(LogEvent::Overflow(1));
+            self.stats.overflowed(1);
+            self.destroy(obj);
         }
     }
 
+    /// Adds an object created outside the pool, such as one rebuilt from
+    /// network state, to the idle objects of the pool.
+    ///
+    /// The object is checked with [`PoolAllocator::is_valid`] first, and is
+    /// handed back if it is invalid or the pool is full. Under
+    /// [`ResetPolicy::OnReturn`], it is also reset and handed back if that
+    /// fails.
+    pub fn put(&self, mut obj: T) -> Result<(), T> {
+        if !self.allocator.is_valid(&obj) || !self.reset_returned(&mut obj) {
+            return Err(obj);
+        }
+        let storage = self.storage_mut();
+        if storage.len() >= self.cap() {
+            return Err(obj);
+        }
+        storage.push_back(obj);
+        self.stats.adopted(1);
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Return, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Return);
+        Ok(())
+    }
+
     /// Gets an object from the pool that holds an rc reference to the owning
     /// pool. Allocated objects are not as efficient as those allocated by
     /// [`Self::get`] method but they are easier to move as they are not limited
     /// by allocator lifetime directly.
     ///
     /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
     pub fn get_rc(self: Rc<Self>) -> RcLocalGuard<P, T> {
-        match self.storage_mut().pop_front() {
-            Some(mut obj) => {
-                self.allocator.reset(&mut obj);
-                RcLocalGuard::new(obj, &self)
+        RcLocalGuard::new(self.checkout(), &self)
+    }
+
+    /// Gets an object from the pool that holds an rc reference to the owning
+    /// pool, like [`Self::get_rc`] but cloning the reference internally, so
+    /// the caller keeps its own.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_owned(self: &Rc<Self>) -> RcLocalGuard<P, T> {
+        RcLocalGuard::new(self.checkout(), self)
+    }
+
+    /// Gets an object from the pool, failing with [`PoolError::Overloaded`]
+    /// if the load shedding threshold is reached.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_get(&self) -> Result<RefLocalGuard<'_, P, T>, PoolError> {
+        self.check_load()?;
+        Ok(RefLocalGuard::new(self.checkout(), self))
+    }
+
+    /// Gets an object from the pool that holds an rc reference to the owning
+    /// pool, failing with [`PoolError::Overloaded`] if the load shedding
+    /// threshold is reached.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_get_rc(self: Rc<Self>) -> Result<RcLocalGuard<P, T>, PoolError> {
+        self.check_load()?;
+        Ok(RcLocalGuard::new(self.checkout(), &self))
+    }
+
+    /// Runs `f` with an object from the pool and returns the object to the
+    /// pool afterwards.
+    ///
+    /// No guard is built, which makes this the leanest way to use an object
+    /// within a single scope. If `f` panics, the object is dropped instead of
+    /// being returned.
+    #[inline]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut obj = self.checkout();
+        let unwind = ReleaseOnUnwind(&self.stats);
+        let result = f(&mut obj);
+        forget(unwind);
+        self.recycle(obj);
+        result
+    }
+
+    /// Runs `f` with an object from the pool like [`Self::with`], failing
+    /// with [`PoolError::Overloaded`] if the load shedding threshold is
+    /// reached.
+    #[inline]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn try_with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R, PoolError> {
+        self.check_load()?;
+        Ok(self.with(f))
+    }
+
+    /// Runs the async closure `f` with an object from the pool and returns the
+    /// object to the pool once the returned future completes.
+    ///
+    /// The object is held by a guard inside the future, so it is also
+    /// returned if the future is dropped before it completes.
+    pub async fn with_async<R>(&self, f: impl AsyncFnOnce(&mut T) -> R) -> R {
+        let mut obj = self.get();
+        f(&mut obj).await
+    }
+
+    /// Fails if the number of checked out objects reached the load shedding
+    /// threshold.
+    #[inline(always)]
+    fn check_load(&self) -> Result<(), PoolError> {
+        if self.stats.in_use(self.len()) >= self.shed_threshold.get() {
+            return Err(PoolError::Overloaded);
+        }
+        Ok(())
+    }
+
+    /// Gets an idle object from the pool, failing with [`PoolError::Empty`]
+    /// instead of allocating if there is none.
+    ///
+    /// This never calls [`PoolAllocator::allocate`], so code on a real-time
+    /// path can rely on a warmed up pool not allocating behind its back.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_or_fail(&self) -> Result<RefLocalGuard<'_, P, T>, PoolError> {
+        let obj = self.take_idle().ok_or(PoolError::Empty)?;
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Checkout, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        Ok(RefLocalGuard::new(obj, self))
+    }
+
+    /// Gets `n` objects from the pool as a [`LocalGuardVec`].
+    ///
+    /// If the pool holds fewer than `n` idle objects, new objects are created
+    /// using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_many(&self, n: usize) -> LocalGuardVec<'_, P, T> {
+        let mut objs = Vec::with_capacity(n);
+        for _ in 0..n {
+            objs.push(self.checkout());
+        }
+        LocalGuardVec { objs, pool: self }
+    }
+
+    /// Gets `n` objects from the pool, or none at all.
+    ///
+    /// If fewer than `n` objects are idle, returns `None` without taking any.
+    /// It only allocates to replace objects that fail to reset.
+    pub fn try_get_n(&self, n: usize) -> Option<LocalGuardVec<'_, P, T>> {
+        if self.len() < n {
+            return None;
+        }
+        let objs = (0..n).map(|_| self.take_counted()).collect();
+        Some(LocalGuardVec { objs, pool: self })
+    }
+
+    /// Gets exactly `N` objects from the pool as an array of guards, or none
+    /// at all.
+    ///
+    /// Like [`Self::try_get_n`], this either takes `N` idle objects or returns
+    /// `None` without taking any, but the guards live on the stack instead of
+    /// in a `Vec`.
+    pub fn get_array<const N: usize>(&self) -> Option<[RefLocalGuard<'_, P, T>; N]> {
+        if self.len() < N {
+            return None;
+        }
+        Some(core::array::from_fn(|_| {
+            RefLocalGuard::new(self.take_counted(), self)
+        }))
+    }
+
+    /// Takes an idle object counted by `len` and resets it, replacing it with
+    /// a new object if every remaining idle object fails to reset.
+    fn take_counted(&self) -> T {
+        let obj = self.take_idle().unwrap_or_else(|| {
+            self.stats.allocated();
+            self.warmed(self.allocate())
+        });
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Checkout, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        obj
+    }
+
+    /// Takes an idle object out of the pool and resets it, or allocates a new
+    /// object if the pool is empty.
+    #[inline(always)]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub(crate) fn checkout(&self) -> T {
+        let obj = match self.take_idle() {
+            Some(obj) => obj,
+            None => self.allocate_fallback(),
+        };
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Checkout, None);
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        obj
+    }
+
+    /// Takes an idle object out of the pool and resets it, destroying the
+    /// idle objects that fail to reset.
+    #[inline(always)]
+    pub(crate) fn take_idle(&self) -> Option<T> {
+        self.take_idle_with(VecDeque::pop_front)
+    }
+
+    /// Takes an idle object out of the pool with `pop` and resets it,
+    /// destroying the idle objects that fail to reset.
+    #[inline(always)]
+    fn take_idle_with(&self, pop: fn(&mut VecDeque<T>) -> Option<T>) -> Option<T> {
+        loop {
+            let mut obj = pop(self.storage_mut())?;
+            if self.reset_policy.get() != ResetPolicy::OnGet || self.allocator.try_reset(&mut obj) {
+                self.stats.hit(1);
+                return Some(self.warmed(obj));
             }
-            None => RcLocalGuard::new(self.allocator.allocate(), &self),
+            #[cfg(feature = "log")]
+            self.log_event(LogEvent::ResetFailed);
+            self.destroy(obj);
         }
     }
 
+    /// Resets a returned object if the pool resets objects on return,
+    /// returning whether it is ready to be stored.
+    #[inline(always)]
+    fn reset_returned(&self, obj: &mut T) -> bool {
+        self.reset_policy.get() != ResetPolicy::OnReturn || self.allocator.try_reset(obj)
+    }
+
+    /// Warms `obj` if the allocator reports that it is not ready yet.
+    #[inline(always)]
+    fn warmed(&self, mut obj: T) -> T {
+        if !self.allocator.is_warm(&obj) {
+            self.allocator.warm(&mut obj);
+        }
+        obj
+    }
+
+    /// Creates a new object with the allocator, leaving the accounting to the
+    /// caller.
+    #[inline]
+    fn allocate(&self) -> T {
+        let obj = self.allocator.allocate();
+        if let Some(observer) = &self.observer {
+            observer.on_allocate(&obj);
+        }
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Allocate);
+        obj
+    }
+
+    /// Hands an object to [`PoolAllocator::on_destroy`], leaving the
+    /// accounting to the caller.
+    #[inline]
+    fn dispose(&self, obj: T) {
+        if let Some(observer) = &self.observer {
+            observer.on_discard(&obj);
+        }
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Discard);
+        self.allocator.on_destroy(obj);
+    }
+
+    /// Destroys the object in `slot` and replaces it with one checked out of
+    /// the pool, freeing its live slot before the replacement is waited for.
+    pub(crate) fn regenerate(&self, slot: &mut MaybeUninit<T>) {
+        // The slot is empty until the replacement is written.
+        let abort = AbortOnUnwind;
+        // Safety: the slot holds an object, and is refilled below.
+        self.destroy(unsafe { slot.assume_init_read() });
+        slot.write(self.checkout());
+        forget(abort);
+    }
+
+    /// Destroys an object that is leaving the pool for good.
+    #[cold]
+    fn destroy(&self, obj: T) {
+        self.dispose(obj);
+        self.stats.released(1);
+        #[cfg(feature = "diagnostics")]
+        self.events.record(PoolEventKind::Drop, None);
+    }
+
+    /// Quarantines an object that failed validation, or destroys it if the
+    /// quarantine is full or disabled.
+    #[cold]
+    fn discard_invalid(&self, obj: T) -> ReturnStatus {
+        self.stats.invalid();
+        if let Some(observer) = &self.observer {
+            observer.on_validation_failure(&obj);
+        }
+        // Safety: the pool is only used by a single thread.
+        let quarantine = unsafe { &mut *self.quarantine.get() };
+        if quarantine.len() < self.quarantine_limit {
+            quarantine.push(obj);
+            self.stats.released(1);
+            return ReturnStatus::Quarantined;
+        }
+        #[cfg(feature = "log")]
+        self.log_event(LogEvent::Invalid);
+        self.destroy(obj);
+        ReturnStatus::DroppedInvalid
+    }
+
+    /// Logs a notable event of the pool at its log level.
+    #[cfg(feature = "log")]
+    #[cold]
+    fn log_event(&self, event: LogEvent) {
+        log_event::<T>(self.log_level, self.name, event);
+    }
+
+    /// Emits a trace level event of the pool.
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub(crate) fn trace_event(&self, event: TraceEvent) {
+        trace_event::<T>(self.name, event);
+    }
+
+    /// Records an untagged event in the event log of the pool.
+    #[cfg(feature = "diagnostics")]
+    #[inline]
+    pub(crate) fn record_event(&self, kind: PoolEventKind) {
+        self.events.record(kind, None);
+    }
+
+    /// Allocates a new object because the pool had no idle object to recycle.
+    #[cold]
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub(crate) fn allocate_fallback(&self) -> T {
+        #[cfg(feature = "diagnostics")]
+        {
+            self.deny_alloc.check();
+            self.miss_sites.record(Location::caller());
+            self.events.record(PoolEventKind::Allocate, None);
+        }
+        #[cfg(feature = "std")]
+        if let Some(alert) = &self.miss_alert {
+            alert.record();
+        }
+        self.stats.missed();
+        if self.lazy_prefill.get() != 0 {
+            self.run_lazy_prefill();
+        }
+        self.warmed(self.allocate())
+    }
+
+    /// Creates the next batch of objects of a deferred prefill.
+    #[cold]
+    fn run_lazy_prefill(&self) {
+        let batch = self.lazy_prefill.get().min(self.prefill_batch);
+        self.lazy_prefill.set(self.lazy_prefill.get() - batch);
+        for _ in 0..batch {
+            if self.storage_mut().len() >= self.cap() {
+                break;
+            }
+            self.stats.allocated();
+            let obj = self.allocate();
+            self.storage_mut().push_back(obj);
+        }
+    }
+
+    /// Marks a region of code in which this pool must not allocate new
+    /// objects.
+    ///
+    /// Until the returned guard is dropped, a get that finds the pool empty
+    /// panics instead of falling back to [`PoolAllocator::allocate`].
+    #[cfg(feature = "diagnostics")]
+    pub fn deny_alloc(&self) -> DenyAllocGuard<'_> {
+        self.deny_alloc.enter()
+    }
+
+    /// Returns the call sites of gets that found the pool empty and had to
+    /// allocate a new object, ordered by the number of misses.
+    ///
+    /// Only the first few dozen distinct call sites are recorded.
+    #[cfg(feature = "diagnostics")]
+    pub fn miss_sites(&self) -> Vec<MissSite> {
+        self.miss_sites.snapshot()
+    }
+
+    /// Gets an object from the pool and attributes it to an owner `tag`,
+    /// such as the name of a subsystem.
+    ///
+    /// The tag is kept on the returned guard and counted in
+    /// [`Self::tag_usage`] until the guard is dropped.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg(feature = "diagnostics")]
+    #[track_caller]
+    pub fn get_tagged(&self, tag: &'static str) -> TaggedGuard<'_, RefLocalGuard<'_, P, T>> {
+        let obj = match self.take_idle() {
+            Some(obj) => obj,
+            None => self.allocate_fallback(),
+        };
+        self.events.record(PoolEventKind::Checkout, Some(tag));
+        #[cfg(feature = "tracing")]
+        self.trace_event(TraceEvent::Checkout);
+        TaggedGuard::new(RefLocalGuard::new(obj, self), tag, &self.owner_tags)
+    }
+
+    /// Returns how many objects are checked out under each owner tag given
+    /// to [`Self::get_tagged`], ordered by the number of objects in use.
+    ///
+    /// Only the first few dozen distinct tags are recorded.
+    #[cfg(feature = "diagnostics")]
+    pub fn tag_usage(&self) -> Vec<TagUsage> {
+        self.owner_tags.snapshot()
+    }
+
+    /// Returns the most recent events of the pool, such as checkouts and
+    /// returns, oldest first.
+    ///
+    /// The last few hundred events are kept, which helps with finding out
+    /// what led to a pool running dry. Events can also be dumped from a panic
+    /// hook, as the log is readable while the pool is in use.
+    #[cfg(feature = "diagnostics")]
+    pub fn recent_events(&self) -> Vec<PoolEvent> {
+        self.events.snapshot()
+    }
+
     /// Gets the number of objects currently in the pool.
     ///
     /// Returns the length of the internal storage, indicating the number of
@@ -109,13 +1089,334 @@ impl<P: PoolAllocator<T>, T> LocalPool<P, T> {
         self.storage_borrow().len()
     }
 
+    /// Returns `true` if the pool holds no idle objects.
+    pub fn is_empty(&self) -> bool {
+        self.storage_borrow().is_empty()
+    }
+
+    /// Returns `true` if the pool holds as many idle objects as its capacity,
+    /// so that returned objects would be dropped.
+    pub fn is_full(&self) -> bool {
+        self.spare_capacity() == 0
+    }
+
+    /// Returns how many more idle objects the pool can hold.
+    pub fn spare_capacity(&self) -> usize {
+        self.cap().saturating_sub(self.storage_borrow().len())
+    }
+
     /// Gets the capacity of the pool.
     ///
     /// Returns the maximum number of objects that the pool can hold. This does
     /// not indicate the maximum number of objects that can be allocated,
     /// but maximum objects that can be stored and recycled from the pool.
     pub fn cap(&self) -> usize {
-        self.storage_borrow().capacity()
+        self.capacity.get()
+    }
+
+    /// Returns a snapshot of the state of the pool.
+    pub fn stats(&self) -> PoolStats {
+        self.stats.snapshot(self.name, self.len(), self.cap())
+    }
+
+    /// Resets the cumulative counters of [`Self::stats`], `allocated`, `hits`,
+    /// `misses`, `returns`, `dropped_invalid` and `dropped_full`, to zero.
+    ///
+    /// The counters describing the current state of the pool, such as the
+    /// number of live and idle objects, are not affected.
+    pub fn reset_stats(&self) {
+        self.stats.reset();
+    }
+
+    /// Returns the number of objects that are checked out of the pool and
+    /// not returned yet.
+    ///
+    /// Unlike [`Self::len`], which counts idle objects, this counts the
+    /// objects held by guards, so a number that keeps growing points at
+    /// guards that are never dropped. Objects taken out for good with
+    /// `into_inner` or `leak` are not counted.
+    pub fn outstanding(&self) -> usize {
+        self.stats.in_use(self.storage_borrow().len())
+    }
+
+    /// Returns the fraction of the objects created by the pool that are
+    /// checked out, see [`PoolStats::utilization`].
+    pub fn utilization(&self) -> f64 {
+        self.stats().utilization()
+    }
+
+    /// Returns the fraction of the capacity that is filled with idle objects,
+    /// see [`PoolStats::fill_ratio`].
+    pub fn fill_ratio(&self) -> f64 {
+        self.stats().fill_ratio()
+    }
+
+    /// Returns an iterator over copies of the idle objects in the pool.
+    ///
+    /// This is a debugging aid to inspect what is sitting in the pool without
+    /// checking objects out.
+    pub fn iter_idle(&self) -> vec::IntoIter<T>
+    where
+        T: Clone,
+    {
+        self.peek_idle(usize::MAX).into_iter()
+    }
+
+    /// Creates a new pool with a clone of the allocator and clones of the
+    /// idle objects of this pool.
+    ///
+    /// This lets several pools, for example one per shard, be spawned from a
+    /// single warmed template pool instead of each warming up on its own.
+    pub fn duplicate(&self) -> Self
+    where
+        P: Clone,
+        T: Clone,
+    {
+        let pool = Self::new(self.cap(), self.allocator.clone());
+        pool.storage_mut().extend(self.iter_idle());
+        pool.stats.adopted(pool.len());
+        pool
+    }
+
+    /// Destroys the idle objects that have been idle the longest with
+    /// [`PoolAllocator::on_destroy`] until at most `count` are left,
+    /// returning how many were destroyed.
+    ///
+    /// After a burst of traffic, this releases the memory of the objects the
+    /// pool no longer needs, while keeping its capacity for the next burst.
+    /// The most recently returned objects, which are the most likely to still
+    /// be in the CPU caches, are kept.
+    ///
+    /// Objects that [`PoolAllocator::on_trim`] keeps stay in the pool after
+    /// releasing their memory, and are not counted.
+    pub fn shrink_to(&self, count: usize) -> usize {
+        let storage = self.storage_mut();
+        let excess = storage.len().saturating_sub(count);
+        let trimmed: Vec<T> = storage.drain(..excess).collect();
+        let mut dropped = 0;
+        for mut obj in trimmed {
+            if self.allocator.on_trim(&mut obj) && self.len() < self.cap() {
+                self.storage_mut().push_back(obj);
+                continue;
+            }
+            dropped += 1;
+            self.stats.released(1);
+            self.dispose(obj);
+        }
+        dropped
+    }
+
+    /// Destroys every idle object in the pool with
+    /// [`PoolAllocator::on_destroy`], releasing their memory.
+    pub fn clear(&self) {
+        self.drop_idle(0);
+    }
+
+    /// Destroys the idle objects that have been idle the longest until at
+    /// most `keep` are left, returning how many were destroyed.
+    fn drop_idle(&self, keep: usize) -> usize {
+        let storage = self.storage_mut();
+        let excess = storage.len().saturating_sub(keep);
+        let dropped: Vec<T> = storage.drain(..excess).collect();
+        self.stats.released(excess);
+        dropped.into_iter().for_each(|obj| self.dispose(obj));
+        excess
+    }
+
+    /// Keeps only the idle objects for which `f` returns `true`, destroying
+    /// the others, and returns how many were destroyed.
+    ///
+    /// This flushes idle objects that no longer match a configuration changed
+    /// at runtime. Checked out objects are not affected.
+    pub fn retain(&self, mut f: impl FnMut(&T) -> bool) -> usize {
+        let mut destroyed = 0;
+        for _ in 0..self.len() {
+            let Some(obj) = self.storage_mut().pop_front() else {
+                break;
+            };
+            if f(&obj) {
+                self.storage_mut().push_back(obj);
+            } else {
+                destroyed += 1;
+                self.destroy(obj);
+            }
+        }
+        destroyed
+    }
+
+    /// Checks every idle object with [`PoolAllocator::is_valid`] again and
+    /// discards the objects that fail, returning how many were discarded.
+    ///
+    /// Failing objects are handled like invalid returns, so they are
+    /// quarantined if the pool has room in its quarantine.
+    pub fn purge_invalid(&self) -> usize {
+        let mut purged = 0;
+        for _ in 0..self.len() {
+            let Some(obj) = self.storage_mut().pop_front() else {
+                break;
+            };
+            if self.allocator.is_valid(&obj) {
+                self.storage_mut().push_back(obj);
+            } else {
+                purged += 1;
+                self.discard_invalid(obj);
+            }
+        }
+        purged
+    }
+
+    /// Takes the idle objects out of the pool, in the order they would be
+    /// handed out, for example to hand them to another subsystem at shutdown.
+    ///
+    /// The objects are taken as the iterator advances, up to the number of
+    /// idle objects when this is called, and belong to the caller from then
+    /// on, so [`PoolAllocator::on_destroy`] is not called for them.
+    pub fn drain(&self) -> impl Iterator<Item = T> + '_ {
+        (0..self.len()).map_while(move |_| {
+            let obj = self.storage_mut().pop_front()?;
+            self.stats.released(1);
+            Some(obj)
+        })
+    }
+
+    /// Moves the idle objects of `other` into this pool and drops `other`,
+    /// returning how many objects were moved.
+    ///
+    /// Each object is added like with [`Self::put`]. Objects that are
+    /// invalid or do not fit are destroyed by the allocator of `other`, like
+    /// the rest of `other` when it is dropped. This folds temporary pools,
+    /// such as per-core shards, back into a long-lived pool.
+    pub fn absorb<Q: PoolAllocator<T>>(&self, other: LocalPool<Q, T>) -> usize {
+        let mut absorbed = 0;
+        for obj in other.drain() {
+            match self.put(obj) {
+                Ok(()) => absorbed += 1,
+                Err(obj) => other.dispose(obj),
+            }
+        }
+        absorbed
+    }
+
+    /// Returns copies of the next `n` idle objects in the pool, in the order
+    /// they would be handed out.
+    pub fn peek_idle(&self, n: usize) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.with_idle(|idle| idle.iter().take(n).cloned().collect())
+    }
+
+    /// Returns an object to the pool, destroying it if it is invalid or does
+    /// not fit.
+    #[inline]
+    pub(crate) fn recycle(&self, mut obj: T) -> ReturnStatus {
+        self.stats.returned(1);
+        if !self.allocator.is_valid(&obj) {
+            return self.discard_invalid(obj);
+        }
+        if !self.reset_returned(&mut obj) {
+            #[cfg(feature = "log")]
+            self.log_event(LogEvent::ResetFailed);
+            self.destroy(obj);
+            return ReturnStatus::DroppedInvalid;
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_recycle(&obj);
+        }
+        let storage = self.storage_mut();
+        if storage.len() < self.cap() {
+            storage.push_back(obj);
+            #[cfg(feature = "diagnostics")]
+            self.events.record(PoolEventKind::Return, None);
+            #[cfg(feature = "tracing")]
+            self.trace_event(TraceEvent::Return);
+            return ReturnStatus::Returned;
+        }
+        #[cfg(feature = "log")]
+        self.log_event(LogEvent::Overflow(1));
+        self.stats.overflowed(1);
+        self.destroy(obj);
+        ReturnStatus::DroppedFull
+    }
+
+    /// Returns valid objects to the pool, destroying the invalid ones and
+    /// those that do not fit.
+    fn recycle_many(&self, objs: impl Iterator<Item = T>) {
+        for obj in objs {
+            self.recycle(obj);
+        }
+    }
+
+    /// Passes all idle objects to `f`, in the order they would be handed out.
+    pub(crate) fn with_idle<R>(&self, f: impl FnOnce(&mut [T]) -> R) -> R {
+        f(self.storage_mut().make_contiguous())
+    }
+}
+
+/// Counts the object of a [`LocalPool::with`] call as released if its closure
+/// panics and the object is dropped during unwinding.
+struct ReleaseOnUnwind<'a>(&'a LocalStats);
+
+/// Destroys the idle objects in the order they would have been handed out,
+/// followed by the quarantined objects, oldest first.
+impl<P: PoolAllocator<T>, T> Drop for LocalPool<P, T> {
+    fn drop(&mut self) {
+        let outstanding = self.outstanding();
+        #[cfg(feature = "log")]
+        if outstanding != 0 {
+            self.log_event(LogEvent::Leaked(outstanding));
+        }
+        let idle = self.storage.get_mut().drain(..);
+        let quarantined = self.quarantine.get_mut().drain(..);
+        idle.chain(quarantined).for_each(|obj| {
+            if let Some(observer) = &self.observer {
+                observer.on_discard(&obj);
+            }
+            #[cfg(feature = "tracing")]
+            trace_event::<T>(self.name, TraceEvent::Discard);
+            self.allocator.on_destroy(obj);
+        });
+        if self.drop_check && outstanding != 0 {
+            outstanding_on_drop(outstanding);
+        }
+    }
+}
+
+/// Consumes the pool and iterates over its idle objects in the order they
+/// would have been handed out, for example to move the warm objects into
+/// another pool at shutdown.
+///
+/// The objects belong to the caller, so [`PoolAllocator::on_destroy`] is not
+/// called for them. Quarantined objects are destroyed with the pool.
+impl<P: PoolAllocator<T>, T> IntoIterator for LocalPool<P, T> {
+    type Item = T;
+    type IntoIter = vec_deque::IntoIter<T>;
+
+    fn into_iter(mut self) -> Self::IntoIter {
+        let idle = mem::take(self.storage.get_mut());
+        self.stats.released(idle.len());
+        idle.into_iter()
+    }
+}
+
+/// Adds already constructed objects to the idle objects of the pool, such as
+/// objects migrated from another pool.
+///
+/// Each object is added like with [`LocalPool::put`], and the objects it hands
+/// back, because they are invalid or the pool is full, are destroyed.
+impl<P: PoolAllocator<T>, T> Extend<T> for LocalPool<P, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, objs: I) {
+        for obj in objs {
+            if let Err(obj) = self.put(obj) {
+                self.dispose(obj);
+            }
+        }
+    }
+}
+
+impl Drop for ReleaseOnUnwind<'_> {
+    fn drop(&mut self) {
+        self.0.released(1);
     }
 }
 
@@ -131,19 +1432,49 @@ pub struct RefLocalGuard<'a, P: PoolAllocator<T>, T> {
 impl<'a, P: PoolAllocator<T>, T> RefLocalGuard<'a, P, T> {
     /// Creates a new Guard for an object and a reference to the pool it
     /// belongs to.
-    fn new(obj: T, pool: &'a LocalPool<P, T>) -> Self {
+    pub(crate) fn new(obj: T, pool: &'a LocalPool<P, T>) -> Self {
         RefLocalGuard {
             obj: MaybeUninit::new(obj),
             pool,
         }
     }
 
+    /// Destroys the held object and replaces it with another object from the
+    /// pool, for error paths that leave the object in an unknown state.
+    ///
+    /// The held object is destroyed before the replacement is checked out, so
+    /// it doesn't count against a live object limit while the replacement is
+    /// waited for. The replacement is recycled from the idle objects of the
+    /// pool, or newly allocated if there are none.
+    ///
+    /// # Panics
+    ///
+    /// The process is aborted if the allocator panics while the object is
+    /// replaced, because the guard would be left without an object.
+    pub fn regenerate(&mut self) {
+        self.pool.regenerate(&mut self.obj);
+    }
+
     /// Consumes the guard and returns the object, without returning it to the
     /// pool.
     ///
     /// This method should be used with caution, as it leads to objects not
     /// being returned to the pool.
     pub fn into_inner(self) -> T {
+        self.pool.stats.released(1);
+        self.take()
+    }
+
+    /// Consumes the guard and returns the object to the pool, reporting
+    /// whether it was stored for reuse or dropped.
+    pub fn return_strict(self) -> ReturnStatus {
+        let pool = self.pool;
+        pool.recycle(self.take())
+    }
+
+    /// Consumes the guard and returns the object, leaving the accounting of
+    /// the pool to the caller.
+    fn take(self) -> T {
         let obj = unsafe { self.obj.as_ptr().read() };
         forget(self);
         obj
@@ -170,16 +1501,9 @@ impl<'a, P: PoolAllocator<T>, T> DerefMut for RefLocalGuard<'a, P, T> {
 /// dropped, unless the object fails validation.
 impl<'a, P: PoolAllocator<T>, T> Drop for RefLocalGuard<'a, P, T> {
     fn drop(&mut self) {
-        let storage = self.pool.storage_mut();
-        if self.pool.allocator.is_valid(self.deref()) && storage.len() < storage.capacity() {
-            // Safety: object is not moved and valid for this single move to the pool.
-            storage.push_back(unsafe { ptr::read(self.obj.as_mut_ptr()) });
-        } else {
-            // Safety: object is not moved back to the pool it is safe to drop it in place.
-            unsafe {
-                ptr::drop_in_place(self.obj.as_mut_ptr());
-            }
-        }
+        // Safety: the object is moved out of the guard only once, here.
+        self.pool
+            .recycle(unsafe { ptr::read(self.obj.as_mut_ptr()) });
     }
 }
 
@@ -252,6 +1576,78 @@ impl<'a, P: PoolAllocator<T>, T> AsRef<T> for RefLocalGuard<'a, P, T> {
     }
 }
 
+/// A container of objects checked out from the same local pool.
+///
+/// The objects are accessed as a slice and are returned to the pool together
+/// when the container is dropped.
+pub struct LocalGuardVec<'a, P: PoolAllocator<T>, T> {
+    objs: Vec<T>,
+    pool: &'a LocalPool<P, T>,
+}
+
+impl<'a, P: PoolAllocator<T>, T> LocalGuardVec<'a, P, T> {
+    /// Creates an empty container for objects of the given pool.
+    pub fn new(pool: &'a LocalPool<P, T>) -> Self {
+        LocalGuardVec {
+            objs: Vec::new(),
+            pool,
+        }
+    }
+
+    /// Moves the object of a guard into the container.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the guard belongs to another pool.
+    pub fn push(&mut self, guard: RefLocalGuard<'a, P, T>) {
+        assert!(
+            ptr::eq(guard.pool, self.pool),
+            "guard belongs to another pool"
+        );
+        self.objs.push(guard.take());
+    }
+
+    /// Removes the last object from the container and returns it as a guard.
+    pub fn pop(&mut self) -> Option<RefLocalGuard<'a, P, T>> {
+        let obj = self.objs.pop()?;
+        Some(RefLocalGuard::new(obj, self.pool))
+    }
+
+    /// Consumes the container and returns the objects, without returning
+    /// them to the pool.
+    pub fn into_inner(mut self) -> Vec<T> {
+        self.pool.stats.released(self.objs.len());
+        core::mem::take(&mut self.objs)
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Deref for LocalGuardVec<'_, P, T> {
+    type Target = [T];
+    #[inline(always)]
+    fn deref(&self) -> &[T] {
+        &self.objs
+    }
+}
+
+impl<P: PoolAllocator<T>, T> DerefMut for LocalGuardVec<'_, P, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.objs
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Drop for LocalGuardVec<'_, P, T> {
+    fn drop(&mut self) {
+        self.pool.recycle_many(self.objs.drain(..));
+    }
+}
+
+impl<P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for LocalGuardVec<'_, P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
 /// A struct representing a guard over an object in the pool.
 ///
 /// This struct ensures that the object is returned to the pool when it is
@@ -271,18 +1667,57 @@ impl<P: PoolAllocator<T>, T> RcLocalGuard<P, T> {
         }
     }
 
+    /// Destroys the held object and replaces it with another object from the
+    /// pool, for error paths that leave the object in an unknown state.
+    ///
+    /// The held object is destroyed before the replacement is checked out, so
+    /// it doesn't count against a live object limit while the replacement is
+    /// waited for. The replacement is recycled from the idle objects of the
+    /// pool, or newly allocated if there are none.
+    ///
+    /// # Panics
+    ///
+    /// The process is aborted if the allocator panics while the object is
+    /// replaced, because the guard would be left without an object.
+    pub fn regenerate(&mut self) {
+        self.pool.regenerate(&mut self.obj);
+    }
+
     /// Consumes the guard and returns the object, without returning it to the
     /// pool.
     ///
     /// This method should be used with caution, as it leads to objects not
     /// being returned to the pool.
     pub fn into_inner(mut self) -> T {
+        self.pool.stats.released(1);
         let obj = unsafe { self.obj.as_ptr().read() };
         // Drop the arc reference
         unsafe { ptr::drop_in_place(&mut self.pool) }
         forget(self);
         obj
     }
+
+    /// Consumes the guard and returns the object to the pool, reporting
+    /// whether it was stored for reuse or dropped.
+    pub fn return_strict(mut self) -> ReturnStatus {
+        let obj = unsafe { self.obj.as_ptr().read() };
+        let status = self.pool.recycle(obj);
+        // Drop the rc reference
+        unsafe { ptr::drop_in_place(&mut self.pool) }
+        forget(self);
+        status
+    }
+
+    /// Consumes the guard and leaks the object, returning a mutable reference
+    /// to it, like [`Box::leak`].
+    ///
+    /// The object is detached from the pool first, like with
+    /// [`Self::into_inner`], so the pool can still be dropped once its other
+    /// references are gone. This is intended for one-time setup objects that
+    /// live for the rest of the program.
+    pub fn leak<'a>(self) -> &'a mut T {
+        Box::leak(Box::new(self.into_inner()))
+    }
 }
 
 impl<P: PoolAllocator<T>, T> Deref for RcLocalGuard<P, T> {
@@ -306,16 +1741,9 @@ impl<P: PoolAllocator<T>, T> DerefMut for RcLocalGuard<P, T> {
 /// dropped, unless the object fails validation.
 impl<P: PoolAllocator<T>, T> Drop for RcLocalGuard<P, T> {
     fn drop(&mut self) {
-        let storage = self.pool.storage_mut();
-        if self.pool.allocator.is_valid(self.deref()) && storage.len() < storage.capacity() {
-            // Safety: object is not moved and valid for this single move to the pool.
-            storage.push_back(unsafe { ptr::read(self.obj.as_mut_ptr()) });
-        } else {
-            // Safety: object is not moved back to the pool it is safe to drop it in place.
-            unsafe {
-                ptr::drop_in_place(self.obj.as_mut_ptr());
-            }
-        }
+        // Safety: the object is moved out of the guard only once, here.
+        self.pool
+            .recycle(unsafe { ptr::read(self.obj.as_mut_ptr()) });
     }
 }
 
@@ -0,0 +1,38 @@
+use core::any::type_name;
+
+/// A step in the life of a pooled object reported through the `tracing`
+/// crate.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum TraceEvent {
+    /// The allocator created a new object.
+    Allocate,
+    /// An object was checked out of the pool.
+    Checkout,
+    /// An object was stored in the pool for reuse.
+    Return,
+    /// The pool handed an object to
+    /// [`PoolAllocator::on_destroy`](crate::PoolAllocator::on_destroy).
+    Discard,
+}
+
+impl TraceEvent {
+    fn as_str(self) -> &'static str {
+        match self {
+            TraceEvent::Allocate => "allocate",
+            TraceEvent::Checkout => "checkout",
+            TraceEvent::Return => "return",
+            TraceEvent::Discard => "discard",
+        }
+    }
+}
+
+/// Emits `event` of a pool of `T` as a trace level event, naming the pool by
+/// its name or, for unnamed pools, by the type of its objects.
+#[inline]
+pub(crate) fn trace_event<T>(name: Option<&'static str>, event: TraceEvent) {
+    tracing::trace!(
+        target: "opool",
+        pool = name.unwrap_or_else(type_name::<T>),
+        event = event.as_str(),
+    );
+}
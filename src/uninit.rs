@@ -0,0 +1,199 @@
+use crate::{LocalPool, Pool, PoolAllocator, RcGuard, RcLocalGuard, RefGuard, RefLocalGuard};
+use alloc::boxed::Box;
+use core::{
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+};
+
+/// An allocator providing raw, uninitialized storage for values of type `T`.
+///
+/// This is useful for pools of large objects, where initializing them in
+/// [`PoolAllocator::allocate`] would only be overwritten by the caller anyway.
+/// Use `get_uninit` on [`Pool`] or [`LocalPool`] to check out storage, and
+/// `write` or `assume_init` on the guard to turn it into an [`InitGuard`].
+pub struct UninitAllocator<T> {
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> UninitAllocator<T> {
+    /// Creates a new UninitAllocator.
+    pub const fn new() -> Self {
+        UninitAllocator {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for UninitAllocator<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> fmt::Debug for UninitAllocator<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UninitAllocator").finish()
+    }
+}
+
+impl<T> PoolAllocator<Box<MaybeUninit<T>>> for UninitAllocator<T> {
+    #[inline]
+    fn allocate(&self) -> Box<MaybeUninit<T>> {
+        Box::new_uninit()
+    }
+}
+
+/// A guard over pooled storage that holds an initialized value.
+///
+/// This struct dereferences to the value and drops it in place when it is
+/// dropped, before the wrapped guard returns the storage to the pool.
+pub struct InitGuard<G: DerefMut<Target = Box<MaybeUninit<T>>>, T> {
+    guard: G,
+}
+
+impl<G: DerefMut<Target = Box<MaybeUninit<T>>>, T> InitGuard<G, T> {
+    /// Wraps a guard whose storage holds an initialized value.
+    ///
+    /// # Safety
+    ///
+    /// The storage behind `guard` must be initialized.
+    unsafe fn new(guard: G) -> Self {
+        InitGuard { guard }
+    }
+
+    /// Drops the value and returns the guard over the now uninitialized
+    /// storage.
+    pub fn uninit(self) -> G {
+        let mut this = MaybeUninit::new(self);
+        // Safety: `this` is never used or dropped after the guard is moved out.
+        unsafe {
+            let this = this.assume_init_mut();
+            this.guard.assume_init_drop();
+            core::ptr::read(&this.guard)
+        }
+    }
+}
+
+impl<G: DerefMut<Target = Box<MaybeUninit<T>>>, T> Deref for InitGuard<G, T> {
+    type Target = T;
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        // Safety: the storage is initialized for the lifetime of the guard.
+        unsafe { self.guard.assume_init_ref() }
+    }
+}
+
+impl<G: DerefMut<Target = Box<MaybeUninit<T>>>, T> DerefMut for InitGuard<G, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Safety: the storage is initialized for the lifetime of the guard.
+        unsafe { self.guard.assume_init_mut() }
+    }
+}
+
+impl<G: DerefMut<Target = Box<MaybeUninit<T>>>, T> Drop for InitGuard<G, T> {
+    fn drop(&mut self) {
+        // Safety: the storage is initialized and dropped only once here.
+        unsafe { self.guard.assume_init_drop() }
+    }
+}
+
+impl<G: DerefMut<Target = Box<MaybeUninit<T>>>, T: fmt::Debug> fmt::Debug for InitGuard<G, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<P: PoolAllocator<Box<MaybeUninit<T>>>, T> Pool<P, Box<MaybeUninit<T>>> {
+    /// Gets uninitialized storage from the pool.
+    ///
+    /// Initialize it with [`RefGuard::write`], or write to it in place and
+    /// call [`RefGuard::assume_init`].
+    pub fn get_uninit(&self) -> RefGuard<'_, P, Box<MaybeUninit<T>>> {
+        self.get()
+    }
+}
+
+impl<P: PoolAllocator<Box<MaybeUninit<T>>>, T> LocalPool<P, Box<MaybeUninit<T>>> {
+    /// Gets uninitialized storage from the pool.
+    ///
+    /// Initialize it with [`RefLocalGuard::write`], or write to it in place
+    /// and call [`RefLocalGuard::assume_init`].
+    pub fn get_uninit(&self) -> RefLocalGuard<'_, P, Box<MaybeUninit<T>>> {
+        self.get()
+    }
+}
+
+impl<'a, P: PoolAllocator<Box<MaybeUninit<T>>>, T> RefGuard<'a, P, Box<MaybeUninit<T>>> {
+    /// Initializes the storage with `value`.
+    pub fn write(mut self, value: T) -> InitGuard<Self, T> {
+        self.as_mut().write(value);
+        // Safety: the storage was just initialized.
+        unsafe { InitGuard::new(self) }
+    }
+
+    /// Converts the guard into a guard over the initialized value.
+    ///
+    /// # Safety
+    ///
+    /// The storage must have been fully initialized by the caller.
+    pub unsafe fn assume_init(self) -> InitGuard<Self, T> {
+        InitGuard::new(self)
+    }
+}
+
+impl<P: PoolAllocator<Box<MaybeUninit<T>>>, T> RcGuard<P, Box<MaybeUninit<T>>> {
+    /// Initializes the storage with `value`.
+    pub fn write(mut self, value: T) -> InitGuard<Self, T> {
+        self.as_mut().write(value);
+        // Safety: the storage was just initialized.
+        unsafe { InitGuard::new(self) }
+    }
+
+    /// Converts the guard into a guard over the initialized value.
+    ///
+    /// # Safety
+    ///
+    /// The storage must have been fully initialized by the caller.
+    pub unsafe fn assume_init(self) -> InitGuard<Self, T> {
+        InitGuard::new(self)
+    }
+}
+
+impl<'a, P: PoolAllocator<Box<MaybeUninit<T>>>, T> RefLocalGuard<'a, P, Box<MaybeUninit<T>>> {
+    /// Initializes the storage with `value`.
+    pub fn write(mut self, value: T) -> InitGuard<Self, T> {
+        self.as_mut().write(value);
+        // Safety: the storage was just initialized.
+        unsafe { InitGuard::new(self) }
+    }
+
+    /// Converts the guard into a guard over the initialized value.
+    ///
+    /// # Safety
+    ///
+    /// The storage must have been fully initialized by the caller.
+    pub unsafe fn assume_init(self) -> InitGuard<Self, T> {
+        InitGuard::new(self)
+    }
+}
+
+impl<P: PoolAllocator<Box<MaybeUninit<T>>>, T> RcLocalGuard<P, Box<MaybeUninit<T>>> {
+    /// Initializes the storage with `value`.
+    pub fn write(mut self, value: T) -> InitGuard<Self, T> {
+        self.as_mut().write(value);
+        // Safety: the storage was just initialized.
+        unsafe { InitGuard::new(self) }
+    }
+
+    /// Converts the guard into a guard over the initialized value.
+    ///
+    /// # Safety
+    ///
+    /// The storage must have been fully initialized by the caller.
+    pub unsafe fn assume_init(self) -> InitGuard<Self, T> {
+        InitGuard::new(self)
+    }
+}
@@ -0,0 +1,203 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+    panic::Location,
+    time::Duration,
+};
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
+
+/// A guard held for longer than the threshold of a [`HoldWatchdog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LongHeld {
+    /// How long the guard has been held so far.
+    pub held: Duration,
+    /// The location of the get that checked the object out.
+    pub location: &'static Location<'static>,
+}
+
+struct Hold {
+    since: Instant,
+    location: &'static Location<'static>,
+    flagged: bool,
+}
+
+struct WatchState {
+    holds: Vec<Option<Hold>>,
+    free: Vec<usize>,
+    last_scan: Instant,
+}
+
+/// A watchdog that flags guards held for longer than a threshold.
+///
+/// Installed with `with_hold_watchdog` on [`crate::Pool`] and
+/// [`crate::LocalPool`], it watches the guards returned by their
+/// `get_watched`. Held guards are checked on watched gets, at most once per
+/// threshold, and whenever `long_held` is called. The callback fires once per
+/// guard, with the call site of the get, and can forward the report to a log
+/// or a tracing event.
+///
+/// The callback runs on the thread whose get or check found the guard. It may
+/// use the pool.
+pub struct HoldWatchdog {
+    threshold: Duration,
+    callback: Box<dyn Fn(&LongHeld) + Send + Sync>,
+    state: Mutex<WatchState>,
+}
+
+impl HoldWatchdog {
+    /// Creates a watchdog calling `callback` for every guard that is held for
+    /// longer than `threshold`.
+    pub fn new(threshold: Duration, callback: impl Fn(&LongHeld) + Send + Sync + 'static) -> Self {
+        HoldWatchdog {
+            threshold,
+            callback: Box::new(callback),
+            state: Mutex::new(WatchState {
+                holds: Vec::new(),
+                free: Vec::new(),
+                last_scan: Instant::now(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, WatchState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Starts watching a guard checked out at `location`, returning its slot.
+    pub(crate) fn check_out(&self, location: &'static Location<'static>) -> usize {
+        let now = Instant::now();
+        let (slot, due) = {
+            let mut state = self.lock();
+            let hold = Some(Hold {
+                since: now,
+                location,
+                flagged: false,
+            });
+            let slot = match state.free.pop() {
+                Some(slot) => {
+                    state.holds[slot] = hold;
+                    slot
+                }
+                None => {
+                    state.holds.push(hold);
+                    state.holds.len() - 1
+                }
+            };
+            (slot, now.duration_since(state.last_scan) >= self.threshold)
+        };
+        if due {
+            self.scan();
+        }
+        slot
+    }
+
+    /// Stops watching the guard in `slot`.
+    pub(crate) fn check_in(&self, slot: usize) {
+        let mut state = self.lock();
+        state.holds[slot] = None;
+        state.free.push(slot);
+    }
+
+    /// Flags the guards that went over the threshold since the last scan,
+    /// firing the callback for each, and returns every guard held for too
+    /// long.
+    pub(crate) fn scan(&self) -> Vec<LongHeld> {
+        let now = Instant::now();
+        let mut fresh = Vec::new();
+        let long_held = {
+            let mut state = self.lock();
+            state.last_scan = now;
+            state
+                .holds
+                .iter_mut()
+                .flatten()
+                .filter_map(|hold| {
+                    let held = now.duration_since(hold.since);
+                    if held <= self.threshold {
+                        return None;
+                    }
+                    let report = LongHeld {
+                        held,
+                        location: hold.location,
+                    };
+                    if !hold.flagged {
+                        hold.flagged = true;
+                        fresh.push(report);
+                    }
+                    Some(report)
+                })
+                .collect()
+        };
+        for report in &fresh {
+            (self.callback)(report);
+        }
+        long_held
+    }
+}
+
+impl fmt::Debug for HoldWatchdog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HoldWatchdog")
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A guard over a pooled object that is watched by a [`HoldWatchdog`].
+///
+/// Created by `get_watched` on [`crate::Pool`] and [`crate::LocalPool`]. The
+/// object is returned to the pool when the guard is dropped, like the guard
+/// it wraps, and the watchdog stops watching it then.
+pub struct WatchedGuard<'a, G> {
+    guard: G,
+    watch: Option<(usize, &'a HoldWatchdog)>,
+}
+
+impl<'a, G> WatchedGuard<'a, G> {
+    pub(crate) fn new(
+        guard: G,
+        watchdog: Option<&'a HoldWatchdog>,
+        location: &'static Location<'static>,
+    ) -> Self {
+        WatchedGuard {
+            guard,
+            watch: watchdog.map(|watchdog| (watchdog.check_out(location), watchdog)),
+        }
+    }
+}
+
+impl<G: Deref> Deref for WatchedGuard<'_, G> {
+    type Target = G::Target;
+
+    #[inline(always)]
+    fn deref(&self) -> &G::Target {
+        &self.guard
+    }
+}
+
+impl<G: DerefMut> DerefMut for WatchedGuard<'_, G> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut G::Target {
+        &mut self.guard
+    }
+}
+
+impl<G> Drop for WatchedGuard<'_, G> {
+    fn drop(&mut self) {
+        if let Some((slot, watchdog)) = self.watch {
+            watchdog.check_in(slot);
+        }
+    }
+}
+
+impl<G: fmt::Debug> fmt::Debug for WatchedGuard<'_, G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchedGuard")
+            .field("object", &self.guard)
+            .finish()
+    }
+}
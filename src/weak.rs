@@ -0,0 +1,254 @@
+use crate::{LocalPool, Pool, PoolAllocator};
+use alloc::{rc, sync};
+use core::{
+    fmt,
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+/// A weak reference to a shared [`Pool`], created by [`Pool::downgrade`].
+///
+/// A weak pool does not keep the pool alive. Objects obtained through it are
+/// held by [`WeakGuard`]s, which return them to the pool if it still exists
+/// when they are dropped, and drop them otherwise. This suits caches that may
+/// outlive the pool and breaks reference cycles between a pool and the
+/// objects that use it.
+pub struct WeakPool<P: PoolAllocator<T>, T> {
+    pool: sync::Weak<Pool<P, T>>,
+}
+
+impl<P: PoolAllocator<T>, T> WeakPool<P, T> {
+    /// Creates a weak pool that never upgrades, like [`sync::Weak::new`].
+    pub const fn new() -> Self {
+        WeakPool {
+            pool: sync::Weak::new(),
+        }
+    }
+
+    /// Returns the pool if it is still alive.
+    pub fn upgrade(&self) -> Option<sync::Arc<Pool<P, T>>> {
+        self.pool.upgrade()
+    }
+
+    /// Gets an object from the pool if it is still alive.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get(&self) -> Option<WeakGuard<P, T>> {
+        Some(self.upgrade()?.get_weak())
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Pool<P, T> {
+    /// Creates a [`WeakPool`] referencing this pool, like
+    /// [`sync::Arc::downgrade`].
+    pub fn downgrade(self: &sync::Arc<Self>) -> WeakPool<P, T> {
+        WeakPool {
+            pool: sync::Arc::downgrade(self),
+        }
+    }
+
+    /// Gets an object from the pool held by a [`WeakGuard`], which does not
+    /// keep the pool alive.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_weak(self: &sync::Arc<Self>) -> WeakGuard<P, T> {
+        WeakGuard {
+            obj: MaybeUninit::new(self.checkout()),
+            pool: sync::Arc::downgrade(self),
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Clone for WeakPool<P, T> {
+    fn clone(&self) -> Self {
+        WeakPool {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Default for WeakPool<P, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: PoolAllocator<T>, T> fmt::Debug for WeakPool<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakPool")
+            .field("alive", &(self.pool.strong_count() > 0))
+            .finish()
+    }
+}
+
+/// A guard over an object from a [`Pool`] that holds a weak reference to
+/// the pool.
+///
+/// The object is returned to the pool when the guard is dropped, or simply
+/// dropped if the pool is gone by then. While the guard is held, the object
+/// counts as checked out, so a pool built with `with_drop_check` still
+/// panics if it is dropped first.
+pub struct WeakGuard<P: PoolAllocator<T>, T> {
+    obj: MaybeUninit<T>,
+    pool: sync::Weak<Pool<P, T>>,
+}
+
+impl<P: PoolAllocator<T>, T> WeakGuard<P, T> {
+    /// Returns the pool the object came from if it is still alive.
+    pub fn pool(&self) -> Option<sync::Arc<Pool<P, T>>> {
+        self.pool.upgrade()
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Deref for WeakGuard<P, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.obj.as_ptr() }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> DerefMut for WeakGuard<P, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.obj.as_mut_ptr() }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Drop for WeakGuard<P, T> {
+    fn drop(&mut self) {
+        // Safety: the object is moved out of the guard only once, here.
+        let obj = unsafe { ptr::read(self.obj.as_mut_ptr()) };
+        if let Some(pool) = self.pool.upgrade() {
+            pool.recycle(obj);
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for WeakGuard<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// A weak reference to a shared [`LocalPool`], created by
+/// [`LocalPool::downgrade`].
+///
+/// This is the single-threaded counterpart of [`WeakPool`].
+pub struct WeakLocalPool<P: PoolAllocator<T>, T> {
+    pool: rc::Weak<LocalPool<P, T>>,
+}
+
+impl<P: PoolAllocator<T>, T> WeakLocalPool<P, T> {
+    /// Creates a weak pool that never upgrades, like [`rc::Weak::new`].
+    pub const fn new() -> Self {
+        WeakLocalPool {
+            pool: rc::Weak::new(),
+        }
+    }
+
+    /// Returns the pool if it is still alive.
+    pub fn upgrade(&self) -> Option<rc::Rc<LocalPool<P, T>>> {
+        self.pool.upgrade()
+    }
+
+    /// Gets an object from the pool if it is still alive.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get(&self) -> Option<WeakLocalGuard<P, T>> {
+        Some(self.upgrade()?.get_weak())
+    }
+}
+
+impl<P: PoolAllocator<T>, T> LocalPool<P, T> {
+    /// Creates a [`WeakLocalPool`] referencing this pool, like
+    /// [`rc::Rc::downgrade`].
+    pub fn downgrade(self: &rc::Rc<Self>) -> WeakLocalPool<P, T> {
+        WeakLocalPool {
+            pool: rc::Rc::downgrade(self),
+        }
+    }
+
+    /// Gets an object from the pool held by a [`WeakLocalGuard`], which does
+    /// not keep the pool alive.
+    ///
+    /// If the pool is empty, a new object is created using the allocator.
+    #[cfg_attr(feature = "diagnostics", track_caller)]
+    pub fn get_weak(self: &rc::Rc<Self>) -> WeakLocalGuard<P, T> {
+        WeakLocalGuard {
+            obj: MaybeUninit::new(self.checkout()),
+            pool: rc::Rc::downgrade(self),
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Clone for WeakLocalPool<P, T> {
+    fn clone(&self) -> Self {
+        WeakLocalPool {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Default for WeakLocalPool<P, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: PoolAllocator<T>, T> fmt::Debug for WeakLocalPool<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeakLocalPool")
+            .field("alive", &(self.pool.strong_count() > 0))
+            .finish()
+    }
+}
+
+/// A guard over an object from a [`LocalPool`] that holds a weak reference
+/// to the pool.
+///
+/// This is the single-threaded counterpart of [`WeakGuard`].
+pub struct WeakLocalGuard<P: PoolAllocator<T>, T> {
+    obj: MaybeUninit<T>,
+    pool: rc::Weak<LocalPool<P, T>>,
+}
+
+impl<P: PoolAllocator<T>, T> WeakLocalGuard<P, T> {
+    /// Returns the pool the object came from if it is still alive.
+    pub fn pool(&self) -> Option<rc::Rc<LocalPool<P, T>>> {
+        self.pool.upgrade()
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Deref for WeakLocalGuard<P, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.obj.as_ptr() }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> DerefMut for WeakLocalGuard<P, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.obj.as_mut_ptr() }
+    }
+}
+
+impl<P: PoolAllocator<T>, T> Drop for WeakLocalGuard<P, T> {
+    fn drop(&mut self) {
+        // Safety: the object is moved out of the guard only once, here.
+        let obj = unsafe { ptr::read(self.obj.as_mut_ptr()) };
+        if let Some(pool) = self.pool.upgrade() {
+            pool.recycle(obj);
+        }
+    }
+}
+
+impl<P: PoolAllocator<T>, T: fmt::Debug> fmt::Debug for WeakLocalGuard<P, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::PoolStats;
+use alloc::collections::VecDeque;
+use core::time::Duration;
+use std::time::Instant;
+
+/// Aggregates pool statistics over a rolling time window.
+///
+/// The cumulative counters of [`PoolStats`] cannot tell what the miss rate is
+/// right now. Feed a window with periodic samples of a pool's
+/// `stats()`, and it reports the activity between its oldest sample inside
+/// the window and the newest one.
+///
+/// ```
+/// use opool::{Pool, PoolAllocator, StatsWindow};
+/// use std::time::Duration;
+///
+/// struct Allocator;
+/// impl PoolAllocator<Vec<u8>> for Allocator {
+///     fn allocate(&self) -> Vec<u8> {
+///         Vec::new()
+///     }
+/// }
+///
+/// let pool = Pool::new(8, Allocator);
+/// let mut window = StatsWindow::new(Duration::from_secs(60));
+/// window.record(pool.stats());
+/// drop(pool.get());
+/// drop(pool.get());
+/// window.record(pool.stats());
+/// assert_eq!((window.hits(), window.misses()), (1, 1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatsWindow {
+    span: Duration,
+    samples: VecDeque<(Instant, PoolStats)>,
+}
+
+impl StatsWindow {
+    /// Creates an empty window covering the last `span` of samples.
+    pub fn new(span: Duration) -> Self {
+        StatsWindow {
+            span,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Records a sample taken now.
+    pub fn record(&mut self, stats: PoolStats) {
+        self.record_at(Instant::now(), stats);
+    }
+
+    /// Records a sample taken at `at`, evicting the samples that fell out of
+    /// the window.
+    ///
+    /// Samples must be recorded in chronological order. If the counters went
+    /// backwards because the pool's stats were reset, the window starts over.
+    pub fn record_at(&mut self, at: Instant, stats: PoolStats) {
+        if let Some((_, last)) = self.samples.back() {
+            if stats.hits < last.hits || stats.misses < last.misses {
+                self.samples.clear();
+            }
+        }
+        self.samples.push_back((at, stats));
+        while let Some((oldest, _)) = self.samples.front() {
+            if at.duration_since(*oldest) <= self.span {
+                break;
+            }
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns the oldest and newest samples in the window.
+    fn bounds(&self) -> Option<(&PoolStats, &PoolStats)> {
+        let (_, first) = self.samples.front()?;
+        let (_, last) = self.samples.back()?;
+        Some((first, last))
+    }
+
+    /// Returns the time between the oldest and newest samples in the window.
+    pub fn elapsed(&self) -> Duration {
+        match (self.samples.front(), self.samples.back()) {
+            (Some((first, _)), Some((last, _))) => last.duration_since(*first),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Returns the number of gets served with an idle object within the
+    /// window.
+    pub fn hits(&self) -> usize {
+        self.bounds()
+            .map_or(0, |(first, last)| last.diff(first).hits)
+    }
+
+    /// Returns the number of gets that had to allocate within the window.
+    pub fn misses(&self) -> usize {
+        self.bounds()
+            .map_or(0, |(first, last)| last.diff(first).misses)
+    }
+
+    /// Returns the fraction of gets that had to allocate within the window,
+    /// between `0.0` and `1.0`.
+    pub fn miss_rate(&self) -> f64 {
+        let gets = self.hits() + self.misses();
+        if gets == 0 {
+            0.0
+        } else {
+            self.misses() as f64 / gets as f64
+        }
+    }
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "std")]
+use opool::*;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread::sleep,
+    time::Duration,
+};
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_miss_alert() {
+    let fired = Arc::new(AtomicUsize::new(0));
+    let alert = MissAlert::new(100, Duration::from_millis(20), {
+        let fired = fired.clone();
+        move |spike| {
+            assert!(spike.misses_per_sec > 100.0);
+            assert!(spike.sustained >= Duration::from_millis(20));
+            fired.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    let pool = Pool::new(0, SimpleAllocator).with_miss_alert(alert);
+    let burst = |misses| {
+        for _ in 0..misses {
+            drop(pool.get());
+        }
+        sleep(Duration::from_millis(25));
+    };
+    burst(1);
+    burst(1);
+    assert_eq!(fired.load(Ordering::Relaxed), 0);
+    burst(20);
+    burst(20);
+    assert_eq!(fired.load(Ordering::Relaxed), 1);
+    burst(20);
+    burst(1);
+    assert_eq!(fired.load(Ordering::Relaxed), 1);
+    burst(1);
+    burst(20);
+    burst(1);
+    assert_eq!(fired.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_local_miss_alert() {
+    let fired = Arc::new(AtomicUsize::new(0));
+    let alert = MissAlert::new(1000, Duration::from_millis(10), {
+        let fired = fired.clone();
+        move |_| {
+            fired.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    let pool = LocalPool::new(4, SimpleAllocator).with_miss_alert(alert);
+    for _ in 0..5 {
+        drop(pool.get());
+        sleep(Duration::from_millis(10));
+    }
+    assert_eq!(fired.load(Ordering::Relaxed), 0);
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "tokio")]
+use opool::*;
+use std::sync::{Arc, Mutex};
+
+struct EvenAllocator;
+
+impl PoolAllocator<usize> for EvenAllocator {
+    fn allocate(&self) -> usize {
+        0
+    }
+
+    fn is_valid(&self, obj: &usize) -> bool {
+        obj % 2 == 0
+    }
+}
+
+#[tokio::test]
+async fn test_async_observer() {
+    let handled = Arc::new(Mutex::new(Vec::new()));
+    let (observer, worker) = AsyncObserver::new(|hook, obj: &usize| Some((hook, *obj)), {
+        let handled = handled.clone();
+        move |event: ObserverEvent<(ObserverHook, usize)>| {
+            let handled = handled.clone();
+            async move {
+                tokio::task::yield_now().await;
+                handled.lock().unwrap().push(event.data);
+            }
+        }
+    });
+    let worker = tokio::spawn(worker.run());
+    let pool = Pool::new(1, EvenAllocator).with_observer(observer);
+    *pool.get() = 2;
+    *pool.get() = 3;
+    drop(pool);
+    worker.await.unwrap();
+    assert_eq!(
+        *handled.lock().unwrap(),
+        [
+            (ObserverHook::Allocate, 0),
+            (ObserverHook::Recycle, 2),
+            (ObserverHook::ValidationFailure, 3),
+            (ObserverHook::Discard, 3),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_async_observer_skips_events() {
+    let (observer, worker) = AsyncObserver::new(
+        |hook, obj: &usize| (hook == ObserverHook::Discard).then_some(*obj),
+        |event: ObserverEvent<usize>| async move { assert_eq!(event.data, 4) },
+    );
+    let pool = LocalPool::new(0, EvenAllocator).with_observer(observer);
+    *pool.get() = 4;
+    drop(pool);
+    // the pool is gone, so the worker stops once the queue is drained
+    worker.run().await;
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "tokio")]
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Vec<u8>> for SimpleAllocator {
+    fn allocate(&self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
+#[tokio::test]
+async fn test_async_return() {
+    let (pool, worker) =
+        AsyncReturnPool::new(Pool::new(4, SimpleAllocator), |buf: Vec<u8>| async {
+            tokio::task::yield_now().await;
+            (buf != [0]).then_some(buf)
+        });
+    let worker = tokio::spawn(worker.run());
+    pool.get().push(1);
+    pool.get().push(0);
+    let kept = pool.get().into_inner();
+    assert!(kept.is_empty());
+    assert_eq!(pool.pool().len(), 0);
+
+    let inner = pool.pool().clone();
+    drop(pool);
+    worker.await.unwrap();
+    assert_eq!(inner.len(), 1);
+    assert_eq!(*inner.get(), [1]);
+    assert_eq!(inner.stats().live, 1);
+}
+
+#[tokio::test]
+async fn test_worker_gone() {
+    let (pool, worker) =
+        AsyncReturnPool::new(Pool::new(4, SimpleAllocator), |buf| async { Some(buf) });
+    drop(worker);
+    pool.get().push(1);
+    assert_eq!(pool.pool().len(), 1);
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "bb8")]
+use opool::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Default)]
+struct SimpleAllocator {
+    allocated: AtomicUsize,
+}
+
+impl PoolAllocator<Vec<u8>> for SimpleAllocator {
+    fn allocate(&self) -> Vec<u8> {
+        self.allocated.fetch_add(1, Ordering::Relaxed);
+        Vec::with_capacity(16)
+    }
+
+    fn reset(&self, obj: &mut Vec<u8>) {
+        obj.clear();
+    }
+
+    fn is_valid(&self, obj: &Vec<u8>) -> bool {
+        obj.capacity() == 16
+    }
+}
+
+#[tokio::test]
+async fn test_reuse() {
+    let pool = bb8::Pool::builder()
+        .max_size(2)
+        .min_idle(0)
+        .build(Bb8Manager::new(SimpleAllocator::default()))
+        .await
+        .unwrap();
+    {
+        let mut obj = pool.get().await.unwrap();
+        obj.push(1);
+    }
+    let obj = pool.get().await.unwrap();
+    assert!(obj.is_empty());
+    assert_eq!(obj.capacity(), 16);
+}
+
+#[tokio::test]
+async fn test_invalid() {
+    let manager = Bb8Manager::new(SimpleAllocator::default());
+    let pool = bb8::Pool::builder()
+        .max_size(1)
+        .min_idle(0)
+        .build(manager)
+        .await
+        .unwrap();
+    {
+        let mut obj = pool.get().await.unwrap();
+        obj.shrink_to_fit();
+    }
+    assert_eq!(pool.state().idle_connections, 0);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(obj.capacity(), 16);
+}
+
+#[tokio::test]
+async fn test_queue_strategy() {
+    let pool = bb8::Pool::builder()
+        .max_size(2)
+        .min_idle(0)
+        .queue_strategy(bb8::QueueStrategy::Lifo)
+        .build(Bb8Manager::new(SimpleAllocator::default()))
+        .await
+        .unwrap();
+    let (a, b) = (pool.get().await.unwrap(), pool.get().await.unwrap());
+    let (first, last) = (a.as_ptr(), b.as_ptr());
+    drop(a);
+    drop(b);
+    assert_eq!(pool.get().await.unwrap().as_ptr(), last);
+    assert_ne!(first, last);
+}
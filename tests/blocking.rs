@@ -0,0 +1,103 @@
+#![cfg(feature = "std")]
+use opool::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Default)]
+struct CountingAlloc {
+    allocated: AtomicUsize,
+}
+
+impl PoolAllocator<Box<usize>> for CountingAlloc {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(self.allocated.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[test]
+fn test_acquire_blocks_at_limit() {
+    let pool = Pool::new(4, CountingAlloc::default()).with_max_live(2);
+    let a = pool.acquire();
+    let b = pool.acquire();
+    std::thread::scope(|s| {
+        let waiter = s.spawn(|| **pool.acquire());
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!waiter.is_finished());
+        drop(a);
+        assert_eq!(waiter.join().unwrap(), 0);
+    });
+    drop(b);
+    assert_eq!(pool.stats().allocated, 2);
+}
+
+#[test]
+fn test_acquire_into_inner_frees_place() {
+    let pool = Pool::new(4, CountingAlloc::default()).with_max_live(1);
+    let obj = pool.acquire().into_inner();
+    assert_eq!(*obj, 0);
+    assert_eq!(**pool.acquire(), 1);
+}
+
+#[test]
+fn test_acquire_without_limit() {
+    let pool = Pool::new(4, CountingAlloc::default());
+    let guards: Vec<_> = (0..8).map(|_| pool.acquire()).collect();
+    assert_eq!(guards.len(), 8);
+    drop(guards);
+    assert_eq!(pool.len(), 4);
+}
+
+#[test]
+fn test_acquire_timeout() {
+    let pool = Pool::new(4, CountingAlloc::default()).with_max_live(1);
+    let held = pool.acquire();
+    assert!(pool.acquire_timeout(Duration::from_millis(20)).is_none());
+    std::thread::scope(|s| {
+        let waiter = s.spawn(|| {
+            pool.acquire_timeout(Duration::from_secs(10))
+                .map(|obj| **obj)
+        });
+        std::thread::sleep(Duration::from_millis(20));
+        drop(held);
+        assert_eq!(waiter.join().unwrap(), Some(0));
+    });
+    assert_eq!(pool.stats().allocated, 1);
+}
+
+#[test]
+fn test_get_blocks_at_limit() {
+    let pool = Pool::new(4, CountingAlloc::default()).with_max_live(2);
+    let a = pool.get();
+    let b = pool.acquire();
+    std::thread::scope(|s| {
+        let waiter = s.spawn(|| pool.with(|obj| **obj));
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!waiter.is_finished());
+        drop(a);
+        assert_eq!(waiter.join().unwrap(), 0);
+    });
+    assert!(pool.acquire_timeout(Duration::from_millis(20)).is_some());
+    let a = pool.get();
+    assert!(pool.acquire_timeout(Duration::from_millis(20)).is_none());
+    drop(a.into_inner());
+    assert_eq!(**pool.get(), 2);
+    drop(b);
+    assert_eq!(pool.stats().allocated, 3);
+    assert_eq!(pool.stats().live, 2);
+}
+
+#[test]
+fn test_regenerate_at_limit() {
+    let pool = Arc::new(Pool::new(4, CountingAlloc::default()).with_max_live(1));
+    let mut obj = pool.get();
+    obj.regenerate();
+    assert_eq!(**obj, 1);
+    drop(obj);
+    let mut obj = pool.clone().get_rc();
+    obj.regenerate();
+    assert_eq!(**obj, 2);
+    drop(obj);
+    assert_eq!(pool.stats().allocated, 3);
+    assert_eq!(pool.stats().live, 1);
+}
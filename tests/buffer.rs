@@ -0,0 +1,140 @@
+use opool::*;
+
+#[test]
+fn test_allocate() {
+    let pool = Pool::new(2, BufferAllocator::new(64));
+    let buf = pool.get();
+    assert_eq!(buf.len(), 64);
+    assert!(buf.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn test_reset_restores_length() {
+    let pool = LocalPool::new(2, BufferAllocator::new(64));
+    let mut buf = pool.get();
+    buf.truncate(3);
+    drop(buf);
+    assert_eq!(pool.get().len(), 64);
+}
+
+#[test]
+fn test_get_zeroed() {
+    let pool = Pool::new(2, BufferAllocator::new(64));
+    let mut buf = pool.get();
+    buf.fill(0xff);
+    buf.truncate(10);
+    drop(buf);
+    let buf = pool.get_zeroed();
+    assert_eq!(buf.len(), 64);
+    assert!(buf.iter().all(|&b| b == 0));
+}
+
+#[test]
+fn test_local_get_zeroed() {
+    let pool = LocalPool::new(2, BufferAllocator::new(64));
+    pool.get().fill(0xff);
+    assert!(pool.get_zeroed().iter().all(|&b| b == 0));
+    assert!(pool.get_zeroed().iter().all(|&b| b == 0));
+}
+
+#[test]
+fn test_pretouched() {
+    let pool = Pool::new_prefilled(4, BufferAllocator::new(10_000)).pretouched();
+    assert_eq!(pool.len(), 4);
+    assert_eq!(pool.stats().allocated, 4);
+    assert!(pool.get().iter().all(|&b| b == 0));
+    let pool = LocalPool::new_prefilled(2, BufferAllocator::new(1 << 20)).pretouched();
+    assert_eq!(pool.len(), 2);
+    assert!(pool.get().iter().all(|&b| b == 0));
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+#[test]
+fn test_huge_pages() {
+    let pool = Pool::new(2, BufferAllocator::new(8 << 20).with_huge_pages());
+    let mut buf = pool.get();
+    buf.fill(1);
+    assert_eq!(buf.len(), 8 << 20);
+    assert!(buf.iter().all(|&b| b == 1));
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+#[test]
+fn test_decommit_idle() {
+    let pool = Pool::new(2, BufferAllocator::new(1 << 20));
+    let mut buf = pool.get();
+    buf.fill(1);
+    let ptr = buf.as_ptr();
+    drop(buf);
+    pool.decommit_idle();
+    assert_eq!(pool.len(), 1);
+    let buf = pool.get();
+    assert_eq!(buf.as_ptr(), ptr);
+    assert_eq!(buf.len(), 1 << 20);
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+#[test]
+fn test_decommit_on_trim() {
+    let allocator = BufferAllocator::new(1 << 20).with_decommit_on_trim();
+    let pool = Pool::new(2, allocator);
+    let mut buf = pool.get();
+    buf.fill(1);
+    let ptr = buf.as_ptr();
+    drop(buf);
+    assert_eq!(pool.shrink_to(0), 0);
+    assert_eq!(pool.len(), 1);
+    let buf = pool.get();
+    assert_eq!(buf.as_ptr(), ptr);
+    assert_eq!(buf[buf.len() / 2], 0);
+    drop(buf);
+    pool.clear();
+    assert!(pool.is_empty());
+
+    let pool = LocalPool::new_prefilled(2, allocator);
+    pool.get().fill(1);
+    assert_eq!(pool.shrink_to(1), 0);
+    assert_eq!(pool.len(), 2);
+    pool.resize(1);
+    assert_eq!(pool.len(), 1);
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+#[test]
+fn test_local_decommit_idle() {
+    let pool = LocalPool::new_prefilled(2, BufferAllocator::new(1 << 20));
+    pool.decommit_idle();
+    assert_eq!(pool.len(), 2);
+    assert!(pool.get_zeroed().iter().all(|&b| b == 0));
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+#[test]
+fn test_secure_buffer() {
+    let pool = Pool::new_secure(2, 32);
+    let mut key = pool.get();
+    assert_eq!(key.len(), 32);
+    key.copy_from_slice(&[7; 32]);
+    let ptr = key.as_ptr();
+    drop(key);
+    // the idle buffer is wiped on return, before it is handed out again
+    let idle = unsafe { std::slice::from_raw_parts(ptr, 32) };
+    assert!(idle.iter().all(|&b| b == 0));
+    let key = pool.get();
+    assert!(key.iter().all(|&b| b == 0));
+    assert!(!format!("{:?}", *key).contains('7'));
+}
+
+#[cfg(all(feature = "linux", target_os = "linux"))]
+#[test]
+fn test_secure_buffer_pages() {
+    let page = 4096;
+    let keys: Vec<_> = [1, 32, 5000, 0].map(SecureBuffer::new).into();
+    for key in &keys {
+        assert_eq!(key.as_ptr() as usize % page, 0);
+    }
+    assert_eq!(keys.iter().map(|key| key.len()).sum::<usize>(), 5033);
+    let pool = LocalPool::new_secure(2, 16);
+    pool.get().fill(9);
+    assert!(pool.get().iter().all(|&b| b == 0));
+}
@@ -1,5 +1,6 @@
 use opool::*;
 
+#[derive(Debug, Clone)]
 struct SimpleAllocator;
 
 impl PoolAllocator<Box<usize>> for SimpleAllocator {
@@ -41,9 +42,649 @@ fn test_get_rc() {
     assert_eq!(**guard, 10);
 }
 
+#[test]
+fn test_get_owned() {
+    let pool = Pool::new_prefilled(10, SimpleAllocator).to_rc();
+    let a = pool.get_owned();
+    let b = pool.get_owned();
+    assert_eq!(**a, 10);
+    assert_eq!(**b, 10);
+    assert_eq!(std::sync::Arc::strong_count(&pool), 3);
+    drop((a, b));
+    assert_eq!(std::sync::Arc::strong_count(&pool), 1);
+    assert_eq!(pool.len(), 10);
+}
+
+#[test]
+fn test_leak() {
+    struct Holder {
+        guards: Vec<RefGuard<'static, SimpleAllocator, Box<usize>>>,
+    }
+    let pool = Pool::new_prefilled(2, SimpleAllocator).leak();
+    let mut holder = Holder { guards: Vec::new() };
+    holder.guards.push(pool.get());
+    holder.guards.push(pool.get());
+    assert!(pool.is_empty());
+    drop(holder);
+    assert_eq!(pool.len(), 2);
+}
+
 #[test]
 fn test_get_rc_into_inner() {
     let pool = Pool::new_prefilled(10, SimpleAllocator).to_rc();
     let guard = pool.clone().get_rc().into_inner();
     assert_eq!(*guard, 10);
 }
+
+#[test]
+fn test_peek_idle() {
+    let pool = Pool::new(10, SimpleAllocator);
+    let (mut a, mut b) = (pool.get(), pool.get());
+    **a = 1;
+    **b = 2;
+    drop((a, b));
+    assert_eq!(pool.peek_idle(1), vec![Box::new(1)]);
+    let idle: Vec<_> = pool.iter_idle().map(|obj| *obj).collect();
+    assert_eq!(idle, vec![1, 2]);
+    assert_eq!(pool.len(), 2);
+    assert_eq!(**pool.get(), 1);
+}
+
+#[test]
+fn test_lifo() {
+    let pool = Pool::new(3, SimpleAllocator);
+    let (mut a, mut b) = (pool.get(), pool.get());
+    **a = 1;
+    **b = 2;
+    drop((a, b));
+    let pool = pool.with_lifo();
+    assert_eq!(pool.peek_idle(2), vec![Box::new(2), Box::new(1)]);
+    assert_eq!(pool.warm_idle(), 0);
+    assert_eq!(pool.reset_idle(), 2);
+    assert_eq!(pool.peek_idle(2), vec![Box::new(2), Box::new(1)]);
+    let mut c = pool.get();
+    assert_eq!(**c, 2);
+    **c = 3;
+    drop(c);
+    let idle: Vec<_> = pool.drain().map(|obj| *obj).collect();
+    assert_eq!(idle, vec![3, 1]);
+
+    pool.prefill(3);
+    let held = pool.get();
+    assert!(pool.put(Box::new(4)).is_ok());
+    assert!(pool.put(Box::new(5)).is_err());
+    assert_eq!(**pool.get(), 4);
+    drop(held);
+}
+
+#[test]
+fn test_get_rc_leak() {
+    let pool = Pool::new(10, SimpleAllocator).to_rc();
+    let obj: &'static mut Box<usize> = pool.clone().get_rc().leak();
+    **obj = 20;
+    assert_eq!(**obj, 20);
+    assert_eq!(pool.len(), 0);
+    assert_eq!(pool.stats().live, 0);
+    assert_eq!(std::sync::Arc::strong_count(&pool), 1);
+}
+
+#[test]
+fn test_get_rc_send() {
+    fn assert_send_static<T: Send + 'static>(_: &T) {}
+    let pool = Pool::new(10, SimpleAllocator).to_rc();
+    let mut guard = pool.clone().get_rc();
+    assert_send_static(&guard);
+    let handle = std::thread::spawn(move || {
+        **guard = 20;
+    });
+    handle.join().unwrap();
+    assert_eq!(pool.len(), 1);
+    assert_eq!(**pool.get(), 20);
+}
+
+#[test]
+fn test_zero_capacity() {
+    let pool = Pool::new_prefilled(0, SimpleAllocator);
+    assert_eq!(pool.cap(), 0);
+    let mut obj = pool.get();
+    **obj = 20;
+    drop(obj);
+    assert!(pool.is_empty());
+    assert_eq!(**pool.get(), 10);
+}
+
+#[test]
+fn test_try_get_n() {
+    let pool = Pool::new_prefilled(3, SimpleAllocator);
+    assert!(pool.try_get_n(4).is_none());
+    assert_eq!(pool.len(), 3);
+    let guards = pool.try_get_n(2).unwrap();
+    assert_eq!(guards.len(), 2);
+    assert_eq!(pool.len(), 1);
+    assert!(pool.try_get_n(2).is_none());
+    drop(guards);
+    assert_eq!(pool.len(), 3);
+}
+
+#[test]
+fn test_try_get_n_concurrent() {
+    let pool = Pool::new_prefilled(8, SimpleAllocator);
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    if let Some(guards) = pool.try_get_n(3) {
+                        assert_eq!(guards.len(), 3);
+                    }
+                    drop(pool.get());
+                }
+            });
+        }
+    });
+    assert_eq!(pool.len(), 8);
+}
+
+#[test]
+fn test_get_array() {
+    let pool = Pool::new_prefilled(3, SimpleAllocator);
+    assert!(pool.get_array::<4>().is_none());
+    assert_eq!(pool.len(), 3);
+    let [mut front, back] = pool.get_array().unwrap();
+    **front = 20;
+    assert_eq!((**front, **back), (20, 10));
+    assert_eq!(pool.len(), 1);
+    drop((front, back));
+    assert_eq!(pool.len(), 3);
+    assert_eq!(pool.stats().allocated, 3);
+}
+
+#[test]
+fn test_get_many() {
+    let pool = Pool::new(4, SimpleAllocator);
+    let mut objs = pool.get_many(6);
+    assert_eq!(objs.len(), 6);
+    for (i, obj) in objs.iter_mut().enumerate() {
+        **obj = i;
+    }
+    let last = objs.pop().unwrap();
+    assert_eq!(**last, 5);
+    objs.push(pool.get());
+    drop(objs);
+    assert_eq!(pool.len(), 4);
+    drop(last);
+    assert_eq!(pool.len(), 4);
+    assert_eq!(
+        pool.peek_idle(4),
+        vec![Box::new(0), Box::new(1), Box::new(2), Box::new(3)]
+    );
+}
+
+#[test]
+fn test_get_batch() {
+    let pool = Pool::new_prefilled(4, SimpleAllocator);
+    let mut guards = pool.get_batch(6);
+    assert_eq!(guards.len(), 6);
+    assert!(pool.is_empty());
+    let stats = pool.stats();
+    assert_eq!((stats.hits, stats.misses), (4, 2));
+    for (i, guard) in guards.iter_mut().enumerate() {
+        ***guard = i;
+    }
+    let last = guards.pop().unwrap();
+    pool.return_batch(guards);
+    assert_eq!(pool.len(), 4);
+    assert_eq!(pool.stats().returns, 5);
+    drop(last);
+    assert_eq!(
+        pool.peek_idle(4),
+        vec![Box::new(0), Box::new(1), Box::new(2), Box::new(3)]
+    );
+}
+
+#[test]
+#[should_panic(expected = "guard belongs to another pool")]
+fn test_return_batch_other_pool() {
+    let (a, b) = (Pool::new(4, SimpleAllocator), Pool::new(4, SimpleAllocator));
+    a.return_batch([a.get(), b.get()]);
+}
+
+#[test]
+#[should_panic(expected = "guard belongs to another pool")]
+fn test_guard_vec_other_pool() {
+    let (a, b) = (Pool::new(4, SimpleAllocator), Pool::new(4, SimpleAllocator));
+    let mut objs = GuardVec::new(&a);
+    objs.push(b.get());
+}
+
+#[test]
+fn test_unbounded() {
+    let pool = Pool::new_unbounded(SimpleAllocator);
+    assert_eq!(pool.cap(), usize::MAX);
+    drop(pool.get_many(1000));
+    assert_eq!(pool.len(), 1000);
+    let stats = pool.stats();
+    assert_eq!((stats.dropped_full, stats.live), (0, 1000));
+}
+
+#[test]
+fn test_lazy_storage() {
+    // a pool this large can only be created if its storage is not allocated
+    // upfront
+    let pool = Pool::new(1 << 40, SimpleAllocator);
+    assert_eq!(pool.cap(), 1 << 40);
+    assert_eq!(*pool.get().into_inner(), 10);
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_storage_growth() {
+    let pool = Pool::new(100, SimpleAllocator);
+    let objs = pool.get_many(150);
+    drop(objs);
+    assert_eq!(pool.len(), 100);
+    assert_eq!(pool.cap(), 100);
+    let objs = pool.get_many(100);
+    assert!(pool.is_empty());
+    drop(objs);
+    assert_eq!(pool.len(), 100);
+}
+
+#[test]
+fn test_storage_growth_concurrent() {
+    let pool = Pool::new(1000, SimpleAllocator);
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    let guards: Vec<_> = (0..200).map(|_| pool.get()).collect();
+                    drop(guards);
+                }
+            });
+        }
+    });
+    assert!(pool.len() <= 1000);
+    drop(pool.get_many(1000));
+    assert_eq!(pool.len(), 1000);
+}
+
+#[test]
+fn test_zero_sized() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+    struct Token;
+    impl Drop for Token {
+        fn drop(&mut self) {
+            LIVE.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+    struct TokenAllocator;
+    impl PoolAllocator<Token> for TokenAllocator {
+        fn allocate(&self) -> Token {
+            LIVE.fetch_add(1, Ordering::Relaxed);
+            Token
+        }
+    }
+
+    let pool = Pool::new(2, TokenAllocator);
+    drop(pool.get_many(3));
+    assert_eq!(pool.len(), 2);
+    assert_eq!(LIVE.load(Ordering::Relaxed), 2);
+    let tokens = pool.get_many(2);
+    assert!(pool.is_empty());
+    drop(tokens);
+    drop(pool);
+    assert_eq!(LIVE.load(Ordering::Relaxed), 0);
+}
+
+#[test]
+fn test_duplicate() {
+    let pool = Pool::new_prefilled(4, SimpleAllocator);
+    let mut obj = pool.get();
+    **obj = 20;
+    drop(obj);
+    let copy = pool.duplicate();
+    assert_eq!(copy.cap(), 4);
+    assert_eq!(
+        copy.iter_idle().collect::<Vec<_>>(),
+        pool.iter_idle().collect::<Vec<_>>()
+    );
+    **copy.get() = 30;
+    assert!(pool.iter_idle().all(|obj| *obj != 30));
+}
+
+#[test]
+fn test_stats() {
+    let pool = Pool::new(4, SimpleAllocator);
+    let objs = pool.get_many(5);
+    let kept = pool.get().into_inner();
+    let stats = pool.stats();
+    assert_eq!(
+        stats,
+        PoolStats {
+            name: None,
+            allocated: 6,
+            hits: 0,
+            misses: 6,
+            returns: 0,
+            dropped_invalid: 0,
+            dropped_full: 0,
+            live: 5,
+            idle: 0,
+            capacity: 4,
+        }
+    );
+    assert_eq!(stats.in_use(), 5);
+    assert_eq!(pool.utilization(), 1.0);
+    drop(objs);
+    assert_eq!(pool.stats().live, 4);
+    let obj = pool.get();
+    assert_eq!(pool.utilization(), 0.25);
+    assert_eq!(pool.fill_ratio(), 0.75);
+    assert_eq!(
+        pool.stats().to_string(),
+        "in use 1/4 (25.0%), idle 3/4 (75.0%), allocated 6"
+    );
+    drop((obj, kept));
+}
+
+#[test]
+fn test_shed_threshold() {
+    let pool = Pool::new(4, SimpleAllocator).with_shed_threshold(2);
+    let a = pool.try_get().unwrap();
+    let b = pool.try_get().unwrap();
+    assert_eq!(pool.try_get().unwrap_err(), PoolError::Overloaded);
+    let c = pool.get();
+    drop(a);
+    assert!(pool.try_get().is_err());
+    drop((b, c));
+    let pool = pool.to_rc();
+    let c = pool.clone().try_get_rc().unwrap();
+    let d = pool.clone().try_get_rc().unwrap();
+    assert!(pool.clone().try_get_rc().is_err());
+    drop((c, d));
+}
+
+#[test]
+fn test_reset_stats() {
+    let pool = Pool::new(4, SimpleAllocator);
+    drop(pool.get());
+    let obj = pool.get();
+    assert_eq!((pool.stats().hits, pool.stats().misses), (1, 1));
+    assert_eq!(pool.stats().miss_rate(), 0.5);
+    pool.reset_stats();
+    let stats = pool.stats();
+    assert_eq!((stats.allocated, stats.hits, stats.misses), (0, 0, 0));
+    assert_eq!((stats.live, stats.in_use()), (1, 1));
+    drop(obj);
+}
+
+#[test]
+fn test_stats_diff() {
+    let pool = Pool::new(2, SimpleAllocator);
+    let start = pool.stats();
+    let objs = pool.get_many(3);
+    drop(objs);
+    drop(pool.get());
+    let delta = pool.stats().diff(&start);
+    assert_eq!(
+        delta,
+        PoolStatsDelta {
+            allocated: 3,
+            hits: 1,
+            misses: 3,
+            returns: 4,
+            dropped_invalid: 0,
+            dropped_full: 1,
+            released: 1,
+            live: 2,
+        }
+    );
+    assert_eq!((delta.gets(), delta.miss_rate()), (4, 0.75));
+
+    drop(pool.get());
+    let before_reset = pool.stats();
+    pool.reset_stats();
+    drop(pool.get());
+    let delta = pool.stats().diff(&before_reset);
+    assert_eq!((delta.hits, delta.misses, delta.live), (1, 0, 0));
+}
+
+#[test]
+fn test_name() {
+    let pool = Pool::new(4, SimpleAllocator).with_name("buffers");
+    assert_eq!(pool.name(), Some("buffers"));
+    assert_eq!(pool.stats().name, Some("buffers"));
+    assert!(pool.stats().to_string().starts_with("buffers: in use 0/0"));
+    assert!(format!("{pool:?}").contains("\"buffers\""));
+}
+
+#[test]
+fn test_borrowed_objects() {
+    struct SliceAllocator<'a>(&'a [u8]);
+    impl<'a> PoolAllocator<Vec<&'a [u8]>> for SliceAllocator<'a> {
+        fn allocate(&self) -> Vec<&'a [u8]> {
+            vec![self.0]
+        }
+        fn reset(&self, obj: &mut Vec<&'a [u8]>) {
+            obj.truncate(1);
+        }
+    }
+
+    let arena = vec![1u8, 2, 3];
+    let pool = Pool::new(2, SliceAllocator(&arena));
+    let mut obj = pool.get();
+    obj.push(&arena[1..]);
+    drop(obj);
+    assert_eq!(*pool.get(), vec![&arena[..]]);
+}
+
+#[test]
+fn test_with() {
+    let pool = Pool::new(4, SimpleAllocator).with_shed_threshold(1);
+    assert_eq!(pool.with(|obj| **obj), 10);
+    assert_eq!(pool.len(), 1);
+    let held = pool.get();
+    assert_eq!(pool.try_with(|obj| **obj), Err(PoolError::Overloaded));
+    drop(held);
+    assert_eq!(pool.try_with(|obj| **obj + 1), Ok(11));
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        pool.with(|_| panic!("closure panicked"));
+    }));
+    assert!(panicked.is_err());
+    assert_eq!(pool.len(), 0);
+    assert_eq!(pool.stats().live, 0);
+}
+
+#[tokio::test]
+async fn test_with_async() {
+    let pool = Pool::new(4, SimpleAllocator);
+    let value = pool
+        .with_async(async |obj| {
+            tokio::task::yield_now().await;
+            **obj + 1
+        })
+        .await;
+    assert_eq!(value, 11);
+    assert_eq!(pool.len(), 1);
+    let pending = pool.with_async(async |_| std::future::pending::<()>().await);
+    let timed_out = tokio::time::timeout(std::time::Duration::from_millis(10), pending).await;
+    assert!(timed_out.is_err());
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.stats().in_use(), 0);
+}
+
+#[test]
+fn test_lazy_prefill() {
+    let pool = Pool::new(8, SimpleAllocator).with_lazy_prefill(6, 4);
+    assert_eq!(pool.len(), 0);
+    assert_eq!(pool.stats().allocated, 0);
+    let a = pool.get();
+    assert_eq!((pool.len(), pool.stats().allocated), (4, 5));
+    let objs: Vec<_> = (0..4).map(|_| pool.get()).collect();
+    assert_eq!(pool.stats().misses, 1);
+    let b = pool.get();
+    assert_eq!((pool.len(), pool.stats().allocated), (2, 8));
+    drop((a, b, objs));
+    drop(pool.get_many(4));
+    assert_eq!(pool.stats().allocated, 8);
+    assert_eq!(pool.len(), 8);
+}
+
+#[test]
+fn test_manual_guard() {
+    let pool = Pool::new(4, SimpleAllocator);
+    let mut obj = pool.get_manual();
+    **obj += 1;
+    pool.put_back(obj);
+    assert_eq!(pool.len(), 1);
+    let obj = pool.get_manual();
+    assert_eq!(**obj, 11);
+    pool.put_back(obj);
+    assert_eq!(pool.stats().in_use(), 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "without being put back")]
+fn test_manual_guard_forgotten() {
+    let pool = Pool::new(4, SimpleAllocator);
+    drop(pool.get_manual());
+}
+
+#[test]
+fn test_return_strict() {
+    struct OddAllocator;
+    impl PoolAllocator<usize> for OddAllocator {
+        fn allocate(&self) -> usize {
+            1
+        }
+
+        fn is_valid(&self, obj: &usize) -> bool {
+            obj % 2 == 1
+        }
+    }
+    let pool = Pool::new(1, OddAllocator);
+    let (a, mut b) = (pool.get(), pool.get());
+    *b += 1;
+    assert_eq!(b.return_strict(), ReturnStatus::DroppedInvalid);
+    assert_eq!(a.return_strict(), ReturnStatus::Returned);
+    let pool = pool.to_rc();
+    let held = pool.clone().get_rc();
+    assert_eq!(
+        pool.clone().get_rc().return_strict(),
+        ReturnStatus::Returned
+    );
+    assert_eq!(held.return_strict(), ReturnStatus::DroppedFull);
+    assert_eq!(pool.stats().live, 1);
+}
+
+#[test]
+fn test_quarantine() {
+    struct OddAllocator;
+    impl PoolAllocator<usize> for OddAllocator {
+        fn allocate(&self) -> usize {
+            1
+        }
+
+        fn is_valid(&self, obj: &usize) -> bool {
+            obj % 2 == 1
+        }
+    }
+    let pool = Pool::new(4, OddAllocator).with_quarantine(1);
+    let (mut a, mut b) = (pool.get(), pool.get());
+    *a = 2;
+    *b = 4;
+    assert_eq!(a.return_strict(), ReturnStatus::Quarantined);
+    assert_eq!(b.return_strict(), ReturnStatus::DroppedInvalid);
+    assert_eq!(pool.take_quarantined(), vec![2]);
+    assert!(pool.take_quarantined().is_empty());
+    assert_eq!(pool.stats().live, 0);
+}
+
+#[test]
+fn test_regenerate() {
+    let pool = Pool::new_prefilled(2, SimpleAllocator);
+    let mut guard = pool.get();
+    **guard = 1;
+    guard.regenerate();
+    assert_eq!(**guard, 10);
+    assert_eq!(pool.len(), 0);
+    assert_eq!(pool.stats().live, 1);
+    drop(guard);
+    let mut guard = pool.to_rc().get_rc();
+    **guard = 1;
+    guard.regenerate();
+    assert_eq!(**guard, 10);
+}
+
+#[test]
+fn test_put() {
+    let pool = Pool::new(1, SimpleAllocator);
+    assert_eq!(pool.put(Box::new(5)), Ok(()));
+    assert_eq!(pool.put(Box::new(6)), Err(Box::new(6)));
+    assert_eq!(pool.stats().live, 1);
+    assert_eq!(pool.stats().in_use(), 0);
+    assert_eq!(**pool.get(), 5);
+    assert_eq!(pool.stats().allocated, 0);
+}
+
+#[test]
+fn test_prefill() {
+    let pool = Pool::new(4, SimpleAllocator);
+    let guard = pool.get();
+    assert_eq!(pool.prefill(2), 2);
+    assert_eq!(pool.prefill(3), 1);
+    assert_eq!(pool.prefill(2), 0);
+    assert_eq!(pool.prefill(10), 1);
+    assert_eq!(pool.len(), 4);
+    assert_eq!(pool.stats().live, 5);
+    drop(guard);
+    assert_eq!(pool.len(), 4);
+}
+
+#[test]
+fn test_resize() {
+    let pool = Pool::new_prefilled(40, SimpleAllocator);
+    let guard = pool.get();
+    pool.resize(8);
+    assert_eq!((pool.len(), pool.cap()), (8, 8));
+    drop(guard);
+    assert_eq!(pool.len(), 8);
+    assert_eq!(pool.stats().live, 8);
+    pool.resize(100);
+    let guards: Vec<_> = (0..100).map(|_| pool.get()).collect();
+    drop(guards);
+    assert_eq!(pool.len(), 100);
+    pool.resize(0);
+    assert!(pool.is_empty());
+    drop(pool.get());
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_get_or_fail() {
+    let pool = Pool::new_prefilled(1, SimpleAllocator);
+    let obj = pool.get_or_fail().unwrap();
+    assert_eq!(**obj, 10);
+    assert_eq!(pool.get_or_fail().unwrap_err(), PoolError::Empty);
+    drop(obj);
+    assert!(pool.get_or_fail().is_ok());
+    assert_eq!(pool.stats().allocated, 1);
+}
+
+#[test]
+fn test_outstanding() {
+    let pool = Pool::new_prefilled(2, SimpleAllocator);
+    assert_eq!(pool.outstanding(), 0);
+    let a = pool.get();
+    let b = pool.get();
+    let c = pool.get();
+    assert_eq!(pool.outstanding(), 3);
+    drop(a);
+    let _ = c.into_inner();
+    assert_eq!(pool.outstanding(), 1);
+    drop(b);
+    assert_eq!(pool.outstanding(), 0);
+    assert_eq!(pool.len(), 2);
+}
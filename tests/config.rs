@@ -0,0 +1,107 @@
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_from_config() {
+    let mut config = PoolConfig::new(8);
+    config.prefill = 4;
+    let pool = Pool::from_config(&config, SimpleAllocator);
+    assert_eq!((pool.cap(), pool.len()), (8, 4));
+    let pool = LocalPool::from_config(&config, SimpleAllocator);
+    assert_eq!((pool.cap(), pool.len()), (8, 4));
+
+    config.prefill = 16;
+    config.prefill_batch = Some(16);
+    config.shed_threshold = Some(1);
+    let pool = Pool::from_config(&config, SimpleAllocator);
+    assert_eq!(pool.len(), 0);
+    let _a = pool.try_get().unwrap();
+    assert_eq!(pool.len(), 8);
+    assert_eq!(pool.try_get().unwrap_err(), PoolError::Overloaded);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_deserialize() {
+    let config: PoolConfig = serde_json::from_str(
+        r#"{ "capacity": 32, "prefill": 8, "quarantine": 2, "reset": "on_return" }"#,
+    )
+    .unwrap();
+    let mut expected = PoolConfig::new(32);
+    expected.prefill = 8;
+    expected.quarantine = 2;
+    expected.reset = ResetPolicy::OnReturn;
+    assert_eq!(config, expected);
+    assert!(serde_json::from_str::<PoolConfig>(r#"{ "prefill": 8 }"#).is_err());
+    assert!(serde_json::from_str::<PoolConfig>(r#"{ "capacity": 1, "ttl": 5 }"#).is_err());
+}
+
+#[test]
+fn test_update_config() {
+    let pool = Pool::new(4, SimpleAllocator);
+    let a = pool.try_get().unwrap();
+    let mut config = PoolConfig::new(4);
+    config.shed_threshold = Some(1);
+    pool.update_config(&config);
+    assert_eq!(pool.try_get().unwrap_err(), PoolError::Overloaded);
+    std::thread::scope(|scope| {
+        scope.spawn(|| assert!(pool.try_get().is_err()));
+    });
+    config.shed_threshold = None;
+    pool.update_config(&config);
+    assert!(pool.try_get().is_ok());
+    drop(a);
+
+    let pool = LocalPool::from_config(&config, SimpleAllocator);
+    let _a = pool.try_get().unwrap();
+    config.shed_threshold = Some(1);
+    pool.update_config(&config);
+    assert!(pool.try_get().is_err());
+}
+
+struct DirtyAllocator;
+
+impl PoolAllocator<Vec<u8>> for DirtyAllocator {
+    fn allocate(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn reset(&self, obj: &mut Vec<u8>) {
+        obj.clear();
+    }
+}
+
+#[test]
+fn test_update_config_capacity_and_reset() {
+    let pool = Pool::new_prefilled(4, DirtyAllocator);
+    pool.get().push(1);
+    let mut config = PoolConfig::new(2);
+    config.reset = ResetPolicy::OnReturn;
+    pool.update_config(&config);
+    assert_eq!(pool.cap(), 2);
+    assert_eq!(pool.len(), 2);
+    // the idle objects were reset when the pool stopped resetting on get
+    let guards = [pool.get(), pool.get()];
+    assert!(guards.iter().all(|obj| obj.is_empty()));
+    drop(guards);
+    pool.get().push(2);
+    assert!(pool.get().is_empty());
+
+    let pool = LocalPool::new_prefilled(2, DirtyAllocator);
+    pool.get().push(1);
+    config.capacity = 3;
+    pool.update_config(&config);
+    assert_eq!(pool.cap(), 3);
+    let guards = [pool.get(), pool.get()];
+    assert!(guards.iter().all(|obj| obj.is_empty()));
+    drop(guards);
+    pool.get().push(2);
+    assert!(pool.get().is_empty());
+}
@@ -0,0 +1,90 @@
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Vec<u8>> for SimpleAllocator {
+    fn allocate(&self) -> Vec<u8> {
+        Vec::with_capacity(16)
+    }
+
+    fn reset(&self, obj: &mut Vec<u8>) {
+        obj.clear();
+    }
+
+    fn is_valid(&self, obj: &Vec<u8>) -> bool {
+        obj.capacity() == 16
+    }
+}
+
+#[test]
+fn test_counts() {
+    let allocator = CountingAllocator::new(SimpleAllocator);
+    let counter = allocator.counter();
+    let pool = Pool::new(2, allocator);
+    drop(pool.get());
+    drop(pool.get());
+    let mut obj = pool.get();
+    obj.reserve(1024);
+    drop(obj);
+    // the only pooled object was discarded, so this allocates again
+    drop(pool.get());
+    assert_eq!(
+        counter.counts(),
+        AllocatorCounts {
+            allocations: 2,
+            resets: 2,
+            validations: 4,
+            validation_failures: 1,
+            reset_failures: 0,
+        }
+    );
+}
+
+#[test]
+fn test_local_counts() {
+    let allocator = CountingAllocator::new(SimpleAllocator);
+    let counter = allocator.counter();
+    let pool = LocalPool::new_prefilled(4, allocator);
+    let a = pool.get();
+    let b = pool.get();
+    drop((a, b));
+    let counts = counter.counts();
+    assert_eq!(counts.allocations, 4);
+    assert_eq!(counts.resets, 2);
+    assert_eq!(counts.validations, 2);
+    assert_eq!(counts.validation_failures, 0);
+}
+
+#[test]
+fn test_reset_counts() {
+    let allocator = CountingAllocator::new(SimpleAllocator);
+    drop(PoolAllocator::<Vec<u8>>::allocate(&allocator));
+    assert_eq!(allocator.counts().allocations, 1);
+    allocator.counter().reset();
+    assert_eq!(allocator.counts(), AllocatorCounts::default());
+}
+
+struct FlakyReset;
+
+impl PoolAllocator<usize> for FlakyReset {
+    fn allocate(&self) -> usize {
+        0
+    }
+
+    fn try_reset(&self, obj: &mut usize) -> bool {
+        *obj == 0
+    }
+}
+
+#[test]
+fn test_reset_failure_counts() {
+    let allocator = CountingAllocator::new(FlakyReset);
+    let counter = allocator.counter();
+    let pool = Pool::new(2, allocator);
+    *pool.get() = 1;
+    drop(pool.get());
+    let counts = counter.counts();
+    assert_eq!(counts.resets, 1);
+    assert_eq!(counts.reset_failures, 1);
+    assert_eq!(counts.allocations, 2);
+}
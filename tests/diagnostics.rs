@@ -0,0 +1,134 @@
+#![cfg(feature = "diagnostics")]
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_deny_alloc_allows_recycling() {
+    let pool = Pool::new_prefilled(2, SimpleAllocator);
+    let _region = pool.deny_alloc();
+    let a = pool.get();
+    let b = pool.get();
+    drop((a, b));
+    assert_eq!(**pool.get(), 10);
+}
+
+#[test]
+#[should_panic(expected = "deny_alloc")]
+fn test_deny_alloc_panics_on_miss() {
+    let pool = Pool::new(2, SimpleAllocator);
+    let _region = pool.deny_alloc();
+    let _obj = pool.get();
+}
+
+#[test]
+fn test_deny_alloc_region_ends() {
+    let pool = LocalPool::new(2, SimpleAllocator);
+    drop(pool.deny_alloc());
+    assert_eq!(**pool.get(), 10);
+}
+
+#[test]
+#[should_panic(expected = "deny_alloc")]
+fn test_local_deny_alloc_panics_on_miss() {
+    let pool = LocalPool::new(2, SimpleAllocator).to_rc();
+    let _region = pool.deny_alloc();
+    let _obj = pool.clone().get_rc();
+}
+
+#[test]
+fn test_miss_sites() {
+    let pool = Pool::new(4, SimpleAllocator);
+    let first = pool.get();
+    let line = line!() - 1;
+    for _ in 0..2 {
+        drop(pool.get());
+    }
+    drop(first);
+    drop(pool.get());
+    let sites = pool.miss_sites();
+    assert_eq!(sites.len(), 2);
+    assert_eq!(sites[0].misses, 1);
+    assert_eq!(sites[1].misses, 1);
+    assert!(sites.iter().all(|site| site.location.file() == file!()));
+    assert!(sites.iter().any(|site| site.location.line() == line));
+}
+
+#[test]
+fn test_local_miss_sites() {
+    let pool = LocalPool::new(4, SimpleAllocator).to_rc();
+    for _ in 0..3 {
+        let _a = pool.clone().get_rc();
+        let _b = pool.clone().get_rc();
+    }
+    let sites = pool.miss_sites();
+    assert_eq!(sites.len(), 2);
+    assert_eq!(sites.iter().map(|site| site.misses).sum::<usize>(), 2);
+}
+
+#[test]
+fn test_tag_usage() {
+    let pool = Pool::new(4, SimpleAllocator);
+    let a = pool.get_tagged("parser");
+    let b = pool.get_tagged("parser");
+    let c = pool.get_tagged(&String::from("writer").leak()[..]);
+    assert_eq!(a.tag(), "parser");
+    assert_eq!(**c, 10);
+    drop(b);
+    let usage = pool.tag_usage();
+    assert_eq!(usage.len(), 2);
+    assert_eq!(
+        (usage[0].tag, usage[0].in_use, usage[0].checkouts),
+        ("parser", 1, 2)
+    );
+    assert_eq!((usage[1].tag, usage[1].in_use), ("writer", 1));
+    drop((a, c));
+    assert!(pool.tag_usage().iter().all(|usage| usage.in_use == 0));
+
+    let pool = LocalPool::new(4, SimpleAllocator);
+    let _a = pool.get_tagged("parser");
+    assert_eq!(pool.tag_usage()[0].in_use, 1);
+}
+
+#[test]
+fn test_recent_events() {
+    use PoolEventKind::*;
+
+    let pool = Pool::new(1, SimpleAllocator);
+    let a = pool.get();
+    let b = pool.get_tagged("parser");
+    drop((a, b));
+    let kinds: Vec<_> = pool
+        .recent_events()
+        .iter()
+        .map(|e| (e.kind, e.tag))
+        .collect();
+    assert_eq!(
+        kinds,
+        [
+            (Allocate, None),
+            (Checkout, None),
+            (Allocate, None),
+            (Checkout, Some("parser")),
+            (Return, None),
+            (Drop, None),
+        ]
+    );
+
+    let pool = LocalPool::new(4, SimpleAllocator);
+    for _ in 0..1000 {
+        drop(pool.get());
+    }
+    let events = pool.recent_events();
+    assert_eq!(events.len(), 256);
+    assert_eq!(events.last().unwrap().seq, 2000);
+    assert!(events.windows(2).all(|w| w[0].seq + 1 == w[1].seq));
+    assert!(events[0].elapsed <= events[255].elapsed);
+    assert!(events[255].to_string().starts_with("#2000 Return"));
+}
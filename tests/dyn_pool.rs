@@ -0,0 +1,35 @@
+use opool::*;
+use std::collections::HashMap;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_downcast() {
+    let mut pools = HashMap::new();
+    pools.insert("numbers", DynPool::new(Pool::new(2, SimpleAllocator)));
+    let buffers = Pool::new(2, BufferAllocator::new(16)).to_handle();
+    pools.insert("buffers", buffers.clone().into());
+
+    let mut guard = pools["numbers"].get();
+    assert!(guard.is::<Box<usize>>());
+    assert!(guard.downcast_ref::<Vec<u8>>().is_none());
+    **guard.downcast_mut::<Box<usize>>().unwrap() = 20;
+    drop(guard);
+    assert_eq!(
+        **pools["numbers"].get().downcast_ref::<Box<usize>>().unwrap(),
+        20
+    );
+    assert_eq!(pools["numbers"].type_name(), "alloc::boxed::Box<usize>");
+
+    let guard = pools["buffers"].get();
+    assert_eq!(guard.downcast_ref::<Vec<u8>>().unwrap().len(), 16);
+    assert_eq!(buffers.pool().stats().in_use(), 1);
+    drop(guard);
+    assert_eq!(pools["buffers"].stats().idle, 1);
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "embassy")]
+use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+use opool::*;
+use std::time::Duration;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[tokio::test]
+async fn test_limit() {
+    let pool = EmbassyPool::<NoopRawMutex, _, _>::new(Pool::new(4, SimpleAllocator), 2);
+    let a = pool.get().await;
+    let _b = pool.get().await;
+    assert_eq!(pool.try_get().unwrap_err(), PoolError::Overloaded);
+    let timed_out = tokio::time::timeout(Duration::from_millis(10), pool.get()).await;
+    assert!(timed_out.is_err());
+    let (value, ()) = tokio::join!(async { **pool.get().await }, async { drop(a) });
+    assert_eq!(value, 10);
+    assert!(pool.try_get().is_ok());
+    assert_eq!(pool.pool().stats().allocated, 2);
+}
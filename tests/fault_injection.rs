@@ -0,0 +1,50 @@
+#![cfg(feature = "fault-injection")]
+use opool::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_fail_validate_every() {
+    let allocator = FaultInjectingAllocator::new(SimpleAllocator).fail_validate(Fault::Every(2));
+    let pool = Pool::new(4, allocator);
+    drop(pool.get());
+    assert_eq!(pool.len(), 1);
+    drop(pool.get());
+    assert_eq!(pool.len(), 0);
+    drop(pool.get());
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn test_fail_allocate_on_call() {
+    let allocator = FaultInjectingAllocator::new(SimpleAllocator).fail_allocate(Fault::OnCall(2));
+    let pool = Pool::new(4, allocator);
+    let first = pool.get();
+    assert!(catch_unwind(AssertUnwindSafe(|| drop(pool.get()))).is_err());
+    assert_eq!(**pool.get(), 10);
+    drop(first);
+}
+
+#[test]
+fn test_fail_rate_is_reproducible() {
+    let run = |seed| {
+        let allocator = FaultInjectingAllocator::new(SimpleAllocator)
+            .with_seed(seed)
+            .fail_validate(Fault::Rate(0.5));
+        (0..64)
+            .map(|_| allocator.is_valid(&Box::new(0)))
+            .collect::<Vec<_>>()
+    };
+    let results = run(7);
+    assert_eq!(results, run(7));
+    assert!(results.contains(&true) && results.contains(&false));
+    let allocator = FaultInjectingAllocator::new(SimpleAllocator).fail_validate(Fault::Rate(0.0));
+    assert!((0..64).all(|_| allocator.is_valid(&Box::new(0))));
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "defmt")]
+use defmt::Format;
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<u32> for SimpleAllocator {
+    fn allocate(&self) -> u32 {
+        0
+    }
+}
+
+fn assert_format<T: Format + ?Sized>() {}
+
+#[test]
+fn test_format_impls() {
+    assert_format::<PoolError>();
+    assert_format::<ReturnStatus>();
+    assert_format::<PoolStats>();
+    assert_format::<Pool<SimpleAllocator, u32>>();
+    assert_format::<LocalPool<SimpleAllocator, u32>>();
+    assert_format::<RefGuard<'static, SimpleAllocator, u32>>();
+    assert_format::<RcGuard<SimpleAllocator, u32>>();
+    assert_format::<GuardVec<'static, SimpleAllocator, u32>>();
+    assert_format::<RefLocalGuard<'static, SimpleAllocator, u32>>();
+    assert_format::<RcLocalGuard<SimpleAllocator, u32>>();
+    assert_format::<LocalGuardVec<'static, SimpleAllocator, u32>>();
+}
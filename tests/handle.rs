@@ -0,0 +1,51 @@
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_get() {
+    let handle = Pool::new(10, SimpleAllocator).to_handle();
+    assert_eq!(**handle.get(), 10);
+    assert_eq!(handle.pool().len(), 1);
+}
+
+#[test]
+fn test_clones_share_pool() {
+    fn assert_send_sync<T: Send + Sync + Clone + 'static>(_: &T) {}
+    let handle = PoolHandle::new(Pool::new(10, SimpleAllocator));
+    assert_send_sync(&handle);
+    let workers: Vec<_> = (0..4)
+        .map(|_| {
+            let handle = handle.clone();
+            std::thread::spawn(move || {
+                for _ in 0..100 {
+                    let _obj = handle.get();
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    assert!(!handle.pool().is_empty());
+}
+
+#[test]
+fn test_deref_to_pool() {
+    let handle = Pool::new(10, SimpleAllocator)
+        .with_shed_threshold(1)
+        .to_handle();
+    let obj = handle.try_get().unwrap();
+    assert!(matches!(handle.try_get(), Err(PoolError::Overloaded)));
+    assert_eq!(handle.stats().in_use(), 1);
+    drop(obj);
+    assert_eq!(handle.len(), 1);
+    handle.clear();
+    assert!(handle.is_empty());
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "std")]
+use opool::*;
+
+#[test]
+fn test_intern() {
+    let pool = InternPool::new();
+    let a = pool.get_or_intern(&String::from("hello"));
+    let b = pool.intern(String::from("hello"));
+    let c = pool.intern(String::from("world"));
+    assert!(a.ptr_eq(&b));
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_eq!(*c, "world");
+    assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn test_purge() {
+    let pool = InternPool::new();
+    let kept = pool.intern(1u32);
+    drop(pool.intern(2));
+    assert_eq!(pool.len(), 2);
+    assert_eq!(pool.purge(), 1);
+    assert_eq!(pool.len(), 1);
+    assert!(pool.intern(1).ptr_eq(&kept));
+    for i in 0..1000 {
+        drop(pool.intern(i + 10));
+    }
+    assert!(pool.len() <= 64);
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "tower")]
+use opool::*;
+use std::{
+    convert::Infallible,
+    future::poll_fn,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower_layer::Layer;
+use tower_service::Service;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Vec<u8>> for SimpleAllocator {
+    fn allocate(&self) -> Vec<u8> {
+        Vec::with_capacity(16)
+    }
+
+    fn reset(&self, obj: &mut Vec<u8>) {
+        obj.clear();
+    }
+}
+
+type Scratch = Pooled<SimpleAllocator, Vec<u8>>;
+
+struct Echo;
+
+impl Service<http::Request<&'static str>> for Echo {
+    type Response = (usize, Scratch);
+    type Error = Infallible;
+    type Future = std::future::Ready<Result<Self::Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<&'static str>) -> Self::Future {
+        let scratch = req.extensions().get::<Scratch>().unwrap().clone();
+        let len = scratch
+            .with(|buf| {
+                buf.extend_from_slice(req.body().as_bytes());
+                buf.len()
+            })
+            .unwrap();
+        std::future::ready(Ok((len, scratch)))
+    }
+}
+
+#[tokio::test]
+async fn test_layer() {
+    let pool = Arc::new(Pool::new(4, SimpleAllocator));
+    let mut service = PoolLayer::new(pool.clone()).layer(Echo);
+    poll_fn(|cx| service.poll_ready(cx)).await.unwrap();
+    let (len, scratch) = service.call(http::Request::new("hello")).await.unwrap();
+    assert_eq!(len, 5);
+    assert_eq!(scratch.with(|buf| buf.len()), None);
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.stats().in_use(), 0);
+}
+
+#[test]
+fn test_dropped_future() {
+    let pool = Arc::new(Pool::new(4, SimpleAllocator));
+    let mut service = PoolLayer::new(pool.clone()).layer(Echo);
+    let future = service.call(http::Request::new("hello"));
+    assert_eq!(pool.stats().in_use(), 1);
+    drop(future);
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.stats().in_use(), 0);
+}
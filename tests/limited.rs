@@ -0,0 +1,40 @@
+#![cfg(feature = "tokio")]
+use opool::*;
+use std::time::Duration;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[tokio::test]
+async fn test_limit() {
+    let pool = LimitedPool::new(Pool::new(4, SimpleAllocator), 2);
+    let a = pool.get().await;
+    let _b = pool.get().await;
+    assert_eq!(pool.available(), 0);
+    assert_eq!(pool.try_get().unwrap_err(), PoolError::Overloaded);
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { **pool.get().await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!waiter.is_finished());
+    drop(a);
+    assert_eq!(waiter.await.unwrap(), 10);
+    assert_eq!(pool.available(), 1);
+}
+
+#[tokio::test]
+async fn test_cancelled_waiter() {
+    let pool = LimitedPool::new(Pool::new(4, SimpleAllocator), 1);
+    let a = pool.get().await;
+    let timed_out = tokio::time::timeout(Duration::from_millis(10), pool.get()).await;
+    assert!(timed_out.is_err());
+    drop(a);
+    assert_eq!(pool.available(), 1);
+    assert!(pool.try_get().is_ok());
+}
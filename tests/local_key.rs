@@ -0,0 +1,24 @@
+#![cfg(feature = "std")]
+use opool::*;
+
+local_pool! {
+    static BUFS: Vec<u8> = BufferAllocator::new(4096); cap = 32
+}
+
+#[test]
+fn test_with_get() {
+    let len = BUFS.with_get(|buf| {
+        buf[0] = 1;
+        buf.len()
+    });
+    assert_eq!(len, 4096);
+    BUFS.with(|pool| assert_eq!(pool.len(), 1));
+}
+
+#[test]
+fn test_per_thread() {
+    BUFS.with_get(|_| ());
+    std::thread::spawn(|| BUFS.with(|pool| assert_eq!(pool.len(), 0)))
+        .join()
+        .unwrap();
+}
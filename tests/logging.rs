@@ -0,0 +1,72 @@
+#![cfg(feature = "log")]
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use opool::*;
+use std::sync::Mutex;
+
+static LINES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+struct Capture;
+
+impl Log for Capture {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let line = format!("{} {}: {}", record.level(), record.target(), record.args());
+        LINES.lock().unwrap().push(line);
+    }
+
+    fn flush(&self) {}
+}
+
+struct OddAllocator;
+
+impl PoolAllocator<usize> for OddAllocator {
+    fn allocate(&self) -> usize {
+        1
+    }
+
+    fn is_valid(&self, obj: &usize) -> bool {
+        obj % 2 == 1
+    }
+}
+
+fn take_lines() -> Vec<String> {
+    std::mem::take(&mut *LINES.lock().unwrap())
+}
+
+#[test]
+fn test_log_events() {
+    log::set_logger(&Capture).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    let pool = Pool::new(1, OddAllocator).with_name("odd");
+    *pool.get() = 2;
+    let (a, b) = (pool.get(), pool.get());
+    drop((a, b));
+    std::mem::forget(pool.get());
+    drop(pool);
+    assert_eq!(
+        take_lines(),
+        [
+            "WARN opool: pool `odd` dropped an object that failed validation",
+            "WARN opool: pool `odd` dropped 1 returned object(s) because it is full",
+            "WARN opool: pool `odd` was dropped with 1 object(s) never returned",
+        ]
+    );
+
+    let pool = LocalPool::new(0, OddAllocator).with_log_level(LevelFilter::Info);
+    drop(pool.get());
+    assert_eq!(
+        take_lines(),
+        [format!(
+            "{} opool: pool of `usize` dropped 1 returned object(s) because it is full",
+            Level::Info
+        )]
+    );
+
+    let pool = LocalPool::new(1, OddAllocator).with_log_level(LevelFilter::Off);
+    *pool.get() = 2;
+    assert!(take_lines().is_empty());
+}
@@ -0,0 +1,46 @@
+//! Model checks of the concurrent pool, run with:
+//!
+//! ```sh
+//! RUSTFLAGS="--cfg opool_loom" cargo test --release --test loom
+//! ```
+#![cfg(opool_loom)]
+use loom::{sync::Arc, thread};
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn concurrent_get_and_return() {
+    loom::model(|| {
+        let pool = Arc::new(Pool::new_prefilled(1, SimpleAllocator));
+        let other = {
+            let pool = pool.clone();
+            thread::spawn(move || drop(pool.get()))
+        };
+        drop(pool.get());
+        other.join().unwrap();
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool.stats().live, 1);
+    });
+}
+
+#[test]
+fn concurrent_try_get_n() {
+    loom::model(|| {
+        let pool = Arc::new(Pool::new_prefilled(2, SimpleAllocator));
+        let other = {
+            let pool = pool.clone();
+            thread::spawn(move || pool.try_get_n(2).map(|objs| objs.len()))
+        };
+        let taken = pool.try_get_n(2).map(|objs| objs.len());
+        let other = other.join().unwrap();
+        assert!(taken.is_some() || other.is_some());
+        assert_eq!(pool.len(), 2);
+    });
+}
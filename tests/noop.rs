@@ -0,0 +1,55 @@
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+fn use_pool<O: ObjectPool<Box<usize>>>(pool: &O) -> usize {
+    let mut obj = pool.get();
+    let value = **obj;
+    **obj = 20;
+    value
+}
+
+#[test]
+fn test_noop_pool() {
+    let pool = NoopPool::new(SimpleAllocator);
+    assert_eq!(use_pool(&pool), 10);
+    assert_eq!(use_pool(&pool), 10);
+    assert_eq!(*pool.get::<Box<usize>>().into_inner(), 10);
+}
+
+#[test]
+fn test_object_pool() {
+    let pool = Pool::new(1, SimpleAllocator);
+    assert_eq!(use_pool(&pool), 10);
+    assert_eq!(use_pool(&pool), 20);
+    let pool = LocalPool::new(1, SimpleAllocator);
+    assert_eq!(use_pool(&pool), 10);
+    assert_eq!(use_pool(&pool), 20);
+    let pool = Pool::new(1, SimpleAllocator).to_handle();
+    assert_eq!(use_pool(&pool), 10);
+    assert_eq!(use_pool(&pool), 20);
+}
+
+fn capacity<O: ObjectPool<Box<usize>>>(pool: &O) -> (bool, bool, usize) {
+    (pool.is_empty(), pool.is_full(), pool.spare_capacity())
+}
+
+#[test]
+fn test_object_pool_capacity() {
+    let pool = Pool::new_prefilled(2, SimpleAllocator);
+    assert_eq!(capacity(&pool), (false, true, 0));
+    let _a = pool.get();
+    assert_eq!(capacity(&pool), (false, false, 1));
+    let pool = LocalPool::new(2, SimpleAllocator);
+    assert_eq!(capacity(&pool), (true, false, 2));
+    assert_eq!((pool.is_full(), pool.spare_capacity()), (false, 2));
+    let pool = Pool::new(0, SimpleAllocator).to_handle();
+    assert_eq!(capacity(&pool), (true, true, 0));
+    assert_eq!(capacity(&NoopPool::new(SimpleAllocator)), (true, true, 0));
+}
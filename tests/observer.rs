@@ -0,0 +1,93 @@
+use opool::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+struct EvenAllocator;
+
+impl PoolAllocator<usize> for EvenAllocator {
+    fn allocate(&self) -> usize {
+        0
+    }
+
+    fn is_valid(&self, obj: &usize) -> bool {
+        obj % 2 == 0
+    }
+}
+
+#[derive(Default, Clone)]
+struct Recorder(Arc<Mutex<Vec<(&'static str, usize)>>>);
+
+impl Recorder {
+    fn take(&self) -> Vec<(&'static str, usize)> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+impl PoolObserver<usize> for Recorder {
+    fn on_allocate(&self, obj: &usize) {
+        self.0.lock().unwrap().push(("allocate", *obj));
+    }
+
+    fn on_recycle(&self, obj: &usize) {
+        self.0.lock().unwrap().push(("recycle", *obj));
+    }
+
+    fn on_discard(&self, obj: &usize) {
+        self.0.lock().unwrap().push(("discard", *obj));
+    }
+
+    fn on_validation_failure(&self, obj: &usize) {
+        self.0.lock().unwrap().push(("invalid", *obj));
+    }
+}
+
+#[test]
+fn test_observer() {
+    let recorder = Recorder::default();
+    let pool = Pool::new(1, EvenAllocator).with_observer(recorder.clone());
+    let (mut a, mut b, mut c) = (pool.get(), pool.get(), pool.get());
+    (*a, *b, *c) = (2, 3, 4);
+    drop((a, b, c));
+    assert_eq!(
+        recorder.take(),
+        [
+            ("allocate", 0),
+            ("allocate", 0),
+            ("allocate", 0),
+            ("recycle", 2),
+            ("invalid", 3),
+            ("discard", 3),
+            ("recycle", 4),
+            ("discard", 4),
+        ]
+    );
+    drop(pool.get_many(2));
+    drop(pool);
+    assert_eq!(
+        recorder.take(),
+        [
+            ("allocate", 0),
+            ("recycle", 2),
+            ("recycle", 0),
+            ("discard", 0),
+            ("discard", 2),
+        ]
+    );
+}
+
+#[test]
+fn test_local_observer() {
+    struct Allocations(Rc<RefCell<usize>>);
+    impl PoolObserver<usize> for Allocations {
+        fn on_allocate(&self, _obj: &usize) {
+            *self.0.borrow_mut() += 1;
+        }
+    }
+    let allocations = Rc::new(RefCell::new(0));
+    let pool = LocalPool::new(2, EvenAllocator).with_observer(Allocations(allocations.clone()));
+    drop(pool.get());
+    drop(pool.get());
+    assert_eq!(*allocations.borrow(), 1);
+    assert_eq!(pool.into_shared().len(), 1);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "rayon")]
+use opool::*;
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct VecAllocator;
+
+impl PoolAllocator<Vec<u8>> for VecAllocator {
+    fn allocate(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn reset(&self, obj: &mut Vec<u8>) {
+        obj.clear();
+    }
+}
+
+#[test]
+fn test_par_map_with() {
+    let pool = Pool::new(64, VecAllocator);
+    let items: Vec<usize> = (0..10_000).collect();
+    let sums: Vec<usize> = pool
+        .par_map_with(items.par_iter().with_min_len(1000), |buf, &item| {
+            buf.clear();
+            buf.extend_from_slice(&item.to_le_bytes());
+            buf.iter().map(|&b| b as usize).sum()
+        })
+        .collect();
+    assert_eq!(sums.len(), 10_000);
+    assert_eq!(sums[258], 3);
+    assert!(pool.stats().allocated <= 10);
+    assert_eq!(pool.stats().in_use(), 0);
+}
+
+#[test]
+fn test_par_for_each_with() {
+    let pool = Pool::new(64, VecAllocator);
+    let total = AtomicUsize::new(0);
+    pool.par_for_each_with(0..1000, |buf, item: usize| {
+        buf.push(1);
+        total.fetch_add(item, Ordering::Relaxed);
+    });
+    assert_eq!(total.into_inner(), 999 * 1000 / 2);
+    assert_eq!(pool.stats().in_use(), 0);
+}
@@ -0,0 +1,61 @@
+use opool::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+struct CheckedAllocator;
+
+impl PoolAllocator<Vec<u8>> for CheckedAllocator {
+    fn allocate(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn reset(&self, obj: &mut Vec<u8>) {
+        obj.clear();
+    }
+
+    fn is_valid(&self, obj: &Vec<u8>) -> bool {
+        obj.len() < 4
+    }
+}
+
+#[test]
+fn test_default_policy() {
+    let pool = Pool::new(4, PolicyAllocator::<_>::new(CheckedAllocator));
+    pool.get().extend([1, 2, 3, 4]);
+    assert_eq!(pool.len(), 0);
+    pool.get().push(1);
+    assert!(pool.get().is_empty());
+}
+
+#[test]
+fn test_unchecked_policy() {
+    let pool = Pool::new(
+        4,
+        PolicyAllocator::<_, UncheckedPolicy>::new(CheckedAllocator),
+    );
+    pool.get().extend([1, 2, 3, 4]);
+    assert_eq!(pool.len(), 1);
+    assert!(pool.get().is_empty());
+}
+
+#[test]
+fn test_no_reset_policy() {
+    struct KeepContents;
+
+    impl PoolPolicy for KeepContents {
+        const RESET: bool = false;
+    }
+
+    let pool = LocalPool::new(4, PolicyAllocator::<_, KeepContents>::new(CheckedAllocator));
+    pool.get().push(1);
+    assert_eq!(*pool.get(), [1]);
+}
+
+#[test]
+fn test_strict_policy() {
+    let pool = Pool::new(4, PolicyAllocator::<_, StrictPolicy>::new(CheckedAllocator));
+    pool.get().push(1);
+    let result = catch_unwind(AssertUnwindSafe(|| pool.get().extend([1, 2, 3, 4])));
+    assert!(result.is_err());
+    assert_eq!(pool.len(), 0);
+    assert!(pool.get().is_empty());
+}
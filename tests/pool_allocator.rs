@@ -0,0 +1,546 @@
+use opool::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_shared_allocator() {
+    let allocator = CountingAllocator::new(SimpleAllocator);
+    let a = Pool::new(2, &allocator);
+    let b = LocalPool::new(2, &allocator);
+    drop((a.get(), b.get()));
+    assert_eq!(allocator.counts().allocations, 2);
+
+    let allocator = Arc::new(CountingAllocator::new(SimpleAllocator));
+    let a = Pool::new(2, allocator.clone());
+    let b = Pool::new(2, allocator.clone());
+    drop((a.get(), b.get()));
+    assert_eq!(allocator.counts().allocations, 2);
+
+    let allocator = Rc::new(SimpleAllocator);
+    let pool = LocalPool::new(2, allocator.clone());
+    assert_eq!(**pool.get(), 10);
+
+    let allocator: Box<dyn PoolAllocator<Box<usize>>> = Box::new(SimpleAllocator);
+    let pool = Pool::new(2, allocator);
+    assert_eq!(**pool.get(), 10);
+}
+
+#[derive(Default)]
+struct DecoderAllocator {
+    destroyed: AtomicUsize,
+}
+
+impl PoolAllocator<Vec<u8>> for DecoderAllocator {
+    fn allocate(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
+    fn try_reset(&self, obj: &mut Vec<u8>) -> bool {
+        // A decoder left mid-frame cannot be reused.
+        let ok = obj.last() != Some(&0xff);
+        obj.clear();
+        ok
+    }
+
+    fn on_destroy(&self, _obj: Vec<u8>) {
+        self.destroyed.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn test_try_reset() {
+    let allocator = DecoderAllocator::default();
+    let destroyed = || allocator.destroyed.load(Ordering::Relaxed);
+    let pool = Pool::new(1, &allocator);
+    let (mut a, b) = (pool.get(), pool.get());
+    a.push(0xff);
+    drop(a);
+    drop(b);
+    assert_eq!(destroyed(), 1);
+    assert!(pool.get().is_empty());
+    assert_eq!(destroyed(), 2);
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.stats().live, 1);
+
+    let pool = LocalPool::new(1, &allocator);
+    pool.get().push(0xff);
+    assert!(pool.get().is_empty());
+    assert_eq!(destroyed(), 3);
+    assert_eq!(pool.stats().misses, 2);
+}
+
+#[derive(Default)]
+struct OrderAllocator {
+    next: Cell<usize>,
+    destroyed: RefCell<Vec<usize>>,
+}
+
+impl PoolAllocator<usize> for OrderAllocator {
+    fn allocate(&self) -> usize {
+        self.next.set(self.next.get() + 1);
+        self.next.get()
+    }
+
+    fn is_valid(&self, obj: &usize) -> bool {
+        *obj != 0
+    }
+
+    fn on_destroy(&self, obj: usize) {
+        self.destroyed.borrow_mut().push(obj);
+    }
+}
+
+#[test]
+fn test_drop_order() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new(4, &allocator).with_quarantine(2);
+    let objs: Vec<_> = (0..4).map(|_| pool.get()).collect();
+    drop(objs);
+    *pool.get() = 0;
+    drop(pool);
+    assert_eq!(allocator.destroyed.take(), [2, 3, 4, 0]);
+
+    let pool = LocalPool::new(4, &allocator).with_quarantine(2);
+    let objs: Vec<_> = (0..3).map(|_| pool.get()).collect();
+    drop(objs);
+    *pool.get() = 0;
+    drop(pool);
+    assert_eq!(allocator.destroyed.take(), [6, 7, 0]);
+}
+
+#[test]
+#[should_panic(expected = "1 of its objects were never returned")]
+fn test_drop_check() {
+    let pool = LocalPool::new(2, SimpleAllocator).with_drop_check();
+    drop(pool.get());
+    std::mem::forget(pool.get());
+}
+
+#[derive(Default)]
+struct HandshakeAllocator {
+    handshakes: AtomicUsize,
+}
+
+impl PoolAllocator<Option<u32>> for HandshakeAllocator {
+    fn allocate(&self) -> Option<u32> {
+        None
+    }
+
+    fn warm(&self, obj: &mut Option<u32>) {
+        self.handshakes.fetch_add(1, Ordering::Relaxed);
+        *obj = Some(7);
+    }
+
+    fn is_warm(&self, obj: &Option<u32>) -> bool {
+        obj.is_some()
+    }
+}
+
+#[test]
+fn test_warm() {
+    let allocator = HandshakeAllocator::default();
+    let handshakes = || allocator.handshakes.load(Ordering::Relaxed);
+    let pool = Pool::new_prefilled(4, &allocator);
+    assert_eq!(handshakes(), 0);
+    assert_eq!(*pool.get(), Some(7));
+    assert_eq!(handshakes(), 1);
+    assert_eq!(pool.warm_idle(), 3);
+    assert_eq!(pool.warm_idle(), 0);
+    let objs: Vec<_> = (0..5).map(|_| pool.get()).collect();
+    assert!(objs.iter().all(|obj| **obj == Some(7)));
+    assert_eq!(handshakes(), 5);
+
+    let pool = LocalPool::new_prefilled(2, &allocator);
+    assert_eq!(pool.warm_idle(), 2);
+    assert_eq!(pool.warm_idle(), 0);
+    assert_eq!(*pool.get(), Some(7));
+    assert_eq!(handshakes(), 7);
+}
+
+#[test]
+fn test_reset_idle() {
+    let allocator = DecoderAllocator::default();
+    let pool = Pool::new(4, &allocator).with_deferred_reset();
+    let (mut a, mut b, mut c) = (pool.get(), pool.get(), pool.get());
+    a.push(1);
+    b.push(0xff);
+    c.push(2);
+    drop((a, b, c));
+    assert_eq!(*pool.get(), [1]);
+    assert_eq!(pool.reset_idle(), 2);
+    assert_eq!(allocator.destroyed.load(Ordering::Relaxed), 1);
+    assert_eq!(pool.len(), 2);
+    assert!(pool.get().is_empty());
+    assert_eq!(pool.stats().live, 2);
+
+    let pool = LocalPool::new(4, &allocator).with_deferred_reset();
+    pool.get().push(0xff);
+    assert_eq!(*pool.get(), [0xff]);
+    assert_eq!(pool.reset_idle(), 0);
+    assert_eq!(pool.len(), 0);
+    assert_eq!(allocator.destroyed.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn test_reset_on_return() {
+    let allocator = DecoderAllocator::default();
+    let pool = Pool::new(4, &allocator).with_reset_policy(ResetPolicy::OnReturn);
+    let (mut a, mut b) = (pool.get(), pool.get());
+    a.push(1);
+    b.push(0xff);
+    assert_eq!(a.return_strict(), ReturnStatus::Returned);
+    assert_eq!(b.return_strict(), ReturnStatus::DroppedInvalid);
+    assert_eq!(allocator.destroyed.load(Ordering::Relaxed), 1);
+    assert_eq!(pool.peek_idle(1), [Vec::<u8>::new()]);
+    assert_eq!(pool.stats().live, 1);
+
+    let pool = LocalPool::new(4, &allocator).with_reset_policy(ResetPolicy::OnReturn);
+    pool.get().push(1);
+    assert_eq!(pool.peek_idle(1), [Vec::<u8>::new()]);
+    assert_eq!(pool.put(vec![0xff]), Err(vec![]));
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn test_put_invalid() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new(2, &allocator);
+    assert_eq!(pool.put(0), Err(0));
+    assert_eq!(pool.put(9), Ok(()));
+    let pool = LocalPool::new(2, &allocator);
+    assert_eq!(pool.put(0), Err(0));
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_clear_and_drain() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new_prefilled(4, &allocator);
+    assert_eq!(pool.drain().take(2).collect::<Vec<_>>(), [1, 2]);
+    assert_eq!(pool.len(), 2);
+    pool.clear();
+    assert!(pool.is_empty());
+    assert_eq!(pool.stats().live, 0);
+    assert_eq!(allocator.destroyed.take(), [3, 4]);
+
+    let pool = LocalPool::new_prefilled(3, &allocator);
+    pool.clear();
+    assert_eq!(allocator.destroyed.take(), [5, 6, 7]);
+    let objs: Vec<_> = (0..2).map(|_| pool.get()).collect();
+    drop(objs);
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [8, 9]);
+    assert_eq!(pool.stats().live, 0);
+    drop(pool);
+    assert!(allocator.destroyed.take().is_empty());
+}
+
+#[test]
+fn test_into_iter() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new_prefilled(3, &allocator);
+    let local = LocalPool::new(3, &allocator);
+    for obj in pool {
+        assert_eq!(local.put(obj), Ok(()));
+    }
+    assert!(allocator.destroyed.take().is_empty());
+    assert_eq!(*local.get(), 1);
+    assert_eq!(local.into_iter().collect::<Vec<_>>(), [2, 3, 1]);
+    assert!(allocator.destroyed.take().is_empty());
+}
+
+#[test]
+fn test_with_objects() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::with_objects(2, &allocator, vec![7, 0, 8, 9]);
+    assert_eq!((pool.len(), pool.cap()), (2, 2));
+    assert_eq!(allocator.destroyed.take(), [0, 9]);
+    assert_eq!(*pool.get(), 7);
+    assert_eq!(pool.stats().allocated, 0);
+
+    let pool = LocalPool::with_objects(4, &allocator, vec![7, 8]);
+    assert_eq!((pool.len(), pool.cap()), (2, 4));
+    assert_eq!(pool.stats().live, 2);
+}
+
+#[test]
+fn test_extend() {
+    let allocator = OrderAllocator::default();
+    let mut pool = Pool::from_iter_with(&allocator, [7, 0, 8]);
+    assert_eq!((pool.len(), pool.cap()), (2, 3));
+    assert_eq!(allocator.destroyed.take(), [0]);
+    pool.extend([9, 10]);
+    assert_eq!(allocator.destroyed.take(), [10]);
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [7, 8, 9]);
+
+    let mut pool = LocalPool::from_iter_with(&allocator, [7, 8]);
+    pool.extend([0, 9]);
+    assert_eq!(allocator.destroyed.take(), [0, 9]);
+    assert_eq!(pool.stats().live, 2);
+    assert_eq!(pool.into_iter().collect::<Vec<_>>(), [7, 8]);
+}
+
+#[test]
+fn test_retain() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new_prefilled(4, &allocator);
+    assert_eq!(pool.retain(|obj| obj % 2 == 0), 2);
+    assert_eq!(allocator.destroyed.take(), [1, 3]);
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [2, 4]);
+
+    let pool = LocalPool::new_prefilled(4, &allocator).with_quarantine(1);
+    assert_eq!(pool.retain(|obj| *obj != 6), 1);
+    assert_eq!(allocator.destroyed.take(), [6]);
+    assert_eq!(pool.stats().live, 3);
+}
+
+/// Allocates objects tagged with the current version of its configuration,
+/// which are only valid while that version is current.
+struct VersionAllocator {
+    version: Cell<usize>,
+}
+
+impl PoolAllocator<usize> for VersionAllocator {
+    fn allocate(&self) -> usize {
+        self.version.get()
+    }
+
+    fn is_valid(&self, obj: &usize) -> bool {
+        *obj == self.version.get()
+    }
+}
+
+#[test]
+fn test_purge_invalid() {
+    let allocator = VersionAllocator {
+        version: Cell::new(1),
+    };
+    let pool = Pool::new_prefilled(3, &allocator);
+    assert_eq!(pool.purge_invalid(), 0);
+    allocator.version.set(2);
+    assert_eq!(pool.purge_invalid(), 3);
+    assert!(pool.is_empty());
+    let stats = pool.stats();
+    assert_eq!((stats.live, stats.dropped_invalid), (0, 3));
+    assert_eq!(*pool.get(), 2);
+
+    let pool = LocalPool::new_prefilled(3, &allocator).with_quarantine(1);
+    allocator.version.set(3);
+    assert_eq!(pool.purge_invalid(), 3);
+    assert_eq!(pool.take_quarantined(), [2]);
+    assert_eq!(pool.stats().live, 0);
+}
+
+#[test]
+fn test_allocator_access() {
+    let version = |version| VersionAllocator {
+        version: Cell::new(version),
+    };
+    let mut pool = Pool::new_prefilled(2, version(1));
+    assert_eq!(pool.allocator().version.get(), 1);
+    *pool.allocator_mut() = version(2);
+    assert_eq!(pool.purge_invalid(), 2);
+    assert_eq!(*pool.get(), 2);
+
+    let mut pool = LocalPool::new_prefilled(2, version(1));
+    *pool.allocator_mut().version.get_mut() = 2;
+    assert_eq!(pool.allocator().version.get(), 2);
+    assert_eq!(pool.purge_invalid(), 2);
+}
+
+#[test]
+fn test_replace_allocator() {
+    let (old, new) = (OrderAllocator::default(), OrderAllocator::default());
+    new.next.set(10);
+    let pool = Pool::new_prefilled(2, &old);
+    let held = pool.get().into_inner();
+    assert!(std::ptr::eq(*pool.replace_allocator(&new), &old));
+    assert_eq!(old.destroyed.take(), [2]);
+    assert_eq!(pool.put(held), Ok(()));
+    assert_eq!(pool.put(0), Err(0));
+    let (a, b) = (pool.get(), pool.get());
+    assert_eq!((*a, *b), (1, 11));
+
+    let mut pool = LocalPool::new_prefilled(2, &old);
+    assert!(std::ptr::eq(pool.replace_allocator(&new), &old));
+    assert_eq!(old.destroyed.take(), [3, 4]);
+    assert!(pool.is_empty());
+    drop(pool);
+    assert!(new.destroyed.take().is_empty());
+}
+
+#[test]
+fn test_absorb() {
+    let (global, shard) = (OrderAllocator::default(), OrderAllocator::default());
+    let pool = Pool::new(2, &global);
+    drop(pool.get());
+    let other = Pool::new_prefilled(3, &shard);
+    *other.get() = 0;
+    assert_eq!(pool.absorb(other), 1);
+    assert!(global.destroyed.take().is_empty());
+    assert_eq!(shard.destroyed.take(), [0, 3]);
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [1, 2]);
+
+    let pool = LocalPool::new(4, &global);
+    assert_eq!(pool.absorb(LocalPool::new_prefilled(2, &shard)), 2);
+    assert_eq!(pool.stats().live, 2);
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [4, 5]);
+}
+
+#[test]
+fn test_split_off() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new_prefilled(3, &allocator);
+    let local = pool.split_off(2);
+    assert_eq!((local.len(), local.cap()), (2, 2));
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.stats().live, 1);
+    let objs: Vec<_> = (0..3).map(|_| local.get()).collect();
+    assert_eq!(objs.iter().map(|obj| **obj).collect::<Vec<_>>(), [1, 2, 4]);
+    drop(objs);
+    assert_eq!(allocator.destroyed.take(), [4]);
+    for obj in local {
+        assert_eq!(pool.put(obj), Ok(()));
+    }
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [3, 1, 2]);
+    assert!(pool.split_off(2).is_empty());
+}
+
+#[test]
+fn test_shrink_to() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new_prefilled(4, &allocator);
+    assert_eq!(pool.shrink_to(1), 3);
+    assert_eq!(pool.shrink_to(1), 0);
+    assert_eq!((pool.len(), pool.cap()), (1, 4));
+    assert_eq!(allocator.destroyed.take(), [1, 2, 3]);
+
+    let pool = LocalPool::new_prefilled(3, &allocator);
+    assert_eq!(pool.shrink_to(2), 1);
+    assert_eq!(pool.shrink_to(5), 0);
+    assert_eq!(pool.stats().live, 2);
+    assert_eq!(allocator.destroyed.take(), [5]);
+}
+
+#[test]
+fn test_shrink_to_keeps_recent() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new_prefilled(4, &allocator);
+    let objs: Vec<_> = (0..2).map(|_| pool.get()).collect();
+    drop(objs);
+    assert_eq!(pool.shrink_to(2), 2);
+    assert_eq!(allocator.destroyed.take(), [3, 4]);
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [1, 2]);
+
+    let pool = Pool::new_prefilled(4, &allocator).with_lifo();
+    drop(pool.get());
+    assert_eq!(pool.shrink_to(2), 2);
+    assert_eq!(allocator.destroyed.take(), [5, 6]);
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [8, 7]);
+
+    let pool = LocalPool::new_prefilled(4, &allocator);
+    drop(pool.get_hot());
+    drop(pool.get());
+    assert_eq!(pool.shrink_to(2), 2);
+    assert_eq!(allocator.destroyed.take(), [10, 11]);
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [12, 9]);
+}
+
+#[test]
+fn test_shrink_to_segments() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new(64, &allocator);
+    let objs: Vec<_> = (0..40).map(|_| pool.get()).collect();
+    drop(objs);
+    let objs: Vec<_> = (0..10).map(|_| pool.get()).collect();
+    drop(objs);
+    // The returns refill the first segment, ahead of the objects in the
+    // second one.
+    assert_eq!(pool.shrink_to(5), 35);
+    let destroyed: Vec<_> = (11..=32).chain(1..=10).chain(33..=35).collect();
+    assert_eq!(allocator.destroyed.take(), destroyed);
+    assert_eq!(pool.drain().collect::<Vec<_>>(), [36, 37, 38, 39, 40]);
+}
+
+#[test]
+fn test_return_stats() {
+    let allocator = OrderAllocator::default();
+    let pool = Pool::new(1, &allocator);
+    let (a, b, mut c) = (pool.get(), pool.get(), pool.get());
+    *c = 0;
+    drop((a, b, c));
+    let stats = pool.stats();
+    assert_eq!(stats.returns, 3);
+    assert_eq!((stats.dropped_invalid, stats.dropped_full), (1, 1));
+    pool.reset_stats();
+    assert_eq!(pool.stats().returns, 0);
+
+    let pool = LocalPool::new(1, &allocator);
+    let (a, mut b) = (pool.get(), pool.get());
+    *b = 0;
+    drop((a, b));
+    drop(pool.get_many(2));
+    let stats = pool.stats();
+    assert_eq!(stats.returns, 4);
+    assert_eq!((stats.dropped_invalid, stats.dropped_full), (1, 1));
+}
+
+#[test]
+fn test_replace_allocator_shared() {
+    let pool = std::sync::Arc::new(Pool::new(4, BufferAllocator::new(16)));
+    let held = pool.clone().get_rc();
+    drop(pool.get());
+    std::thread::spawn({
+        let pool = pool.clone();
+        move || pool.replace_allocator(BufferAllocator::new(64)).len()
+    })
+    .join()
+    .map(|old| assert_eq!(old, 16))
+    .unwrap();
+    assert!(pool.is_empty());
+    assert_eq!(pool.allocator().len(), 64);
+    assert_eq!(pool.get().len(), 64);
+    // the buffer of the old size fails validation when it is returned
+    drop(held);
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.get().len(), 64);
+}
+
+#[test]
+fn test_replace_allocator_reclaim() {
+    let drops = std::rc::Rc::new(Cell::new(0));
+    struct Alloc(std::rc::Rc<Cell<usize>>);
+    impl PoolAllocator<usize> for Alloc {
+        fn allocate(&self) -> usize {
+            0
+        }
+    }
+    impl Drop for Alloc {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+    let mut pool = Pool::new(2, Alloc(drops.clone()));
+    for _ in 0..3 {
+        pool.replace_allocator(Alloc(drops.clone()));
+    }
+    assert_eq!(drops.get(), 0);
+    pool.allocator_mut();
+    assert_eq!(drops.get(), 3);
+    pool.replace_allocator(Alloc(drops.clone()));
+    drop(pool);
+    assert_eq!(drops.get(), 5);
+}
@@ -0,0 +1,56 @@
+use opool::*;
+use std::sync::Mutex;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[derive(Default)]
+struct ManualPressure {
+    callbacks: Mutex<Vec<Box<dyn Fn() + Send + Sync>>>,
+}
+
+impl ManualPressure {
+    fn report(&self) {
+        for callback in self.callbacks.lock().unwrap().iter() {
+            callback();
+        }
+    }
+}
+
+impl MemoryPressure for ManualPressure {
+    fn on_pressure(&self, callback: Box<dyn Fn() + Send + Sync>) {
+        self.callbacks.lock().unwrap().push(callback);
+    }
+}
+
+#[test]
+fn test_trim_on_pressure() {
+    let pressure = ManualPressure::default();
+    let pool = Pool::new_prefilled(4, SimpleAllocator).to_rc();
+    pool.trim_on_pressure(&pressure);
+    let obj = pool.get();
+    pressure.report();
+    assert!(pool.is_empty());
+    assert_eq!(pool.stats().live, 1);
+    drop(obj);
+    assert_eq!(pool.len(), 1);
+    drop(pool);
+    pressure.report();
+}
+
+#[cfg(all(feature = "pressure", target_os = "linux"))]
+#[test]
+fn test_psi_watcher() {
+    // triggers may be unavailable or need privileges in the test environment
+    if let Ok(watcher) = PsiWatcher::new() {
+        let pool = Pool::new_prefilled(4, SimpleAllocator).to_rc();
+        pool.trim_on_pressure(&watcher);
+        drop(watcher);
+        assert_eq!(pool.len(), 4);
+    }
+}
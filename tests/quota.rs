@@ -0,0 +1,30 @@
+#![cfg(feature = "std")]
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_quota() {
+    let pool = QuotaPool::new(Pool::new(8, SimpleAllocator), 2);
+    pool.set_quota("batch", 1);
+    let a = pool.try_get(&"web").unwrap();
+    let _b = pool.try_get(&"web").unwrap();
+    assert_eq!(pool.try_get(&"web").unwrap_err(), PoolError::QuotaExceeded);
+    let c = pool.try_get(&"batch").unwrap();
+    assert!(pool.try_get(&"batch").is_err());
+    drop(a);
+    assert_eq!(**pool.try_get(&"web").unwrap(), 10);
+    drop(c);
+    let stats = pool.tenant_stats(&"web").unwrap();
+    assert_eq!((stats.quota, stats.in_use), (2, 1));
+    assert_eq!((stats.checkouts, stats.rejections), (3, 1));
+    assert_eq!(pool.tenant_stats(&"batch").unwrap().in_use, 0);
+    assert!(pool.tenant_stats(&"other").is_none());
+    assert_eq!(pool.pool().stats().in_use(), 1);
+}
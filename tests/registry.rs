@@ -0,0 +1,27 @@
+#![cfg(feature = "std")]
+use opool::*;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_snapshot_all() {
+    let requests = Pool::new(4, SimpleAllocator).with_name("requests").to_rc();
+    let responses = Pool::new(8, SimpleAllocator).with_name("responses").to_rc();
+    registry::register(&requests);
+    registry::register(&responses);
+    let _obj = requests.get();
+
+    let names = |stats: &[PoolStats]| stats.iter().map(|s| s.name).collect::<Vec<_>>();
+    let stats = registry::snapshot_all();
+    assert_eq!(names(&stats), vec![Some("requests"), Some("responses")]);
+    assert_eq!(stats[0].in_use(), 1);
+
+    drop(responses);
+    assert_eq!(names(&registry::snapshot_all()), vec![Some("requests")]);
+}
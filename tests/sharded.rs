@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+use opool::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Clone)]
+struct SimpleAllocator;
+
+impl PoolAllocator<usize> for SimpleAllocator {
+    fn allocate(&self) -> usize {
+        0
+    }
+}
+
+#[test]
+fn test_round_robin() {
+    let pool = ShardedPool::new(3, 2, SimpleAllocator).with_selector(RoundRobin::default());
+    assert_eq!(pool.cap(), 6);
+    let guards: Vec<_> = (0..3).map(|_| pool.get()).collect();
+    drop(guards);
+    assert!(pool.shards().iter().all(|shard| shard.len() == 1));
+    assert_eq!(pool.len(), 3);
+}
+
+#[test]
+fn test_thread_selectors_stay_on_one_shard() {
+    let pool = ShardedPool::new(4, 4, SimpleAllocator);
+    let shard = pool.shard() as *const _;
+    assert!((0..8).all(|_| std::ptr::eq(pool.shard(), shard)));
+    let pool = pool.with_selector(Sticky);
+    let shard = pool.shard() as *const _;
+    assert!((0..8).all(|_| std::ptr::eq(pool.shard(), shard)));
+    drop(pool.get());
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn test_sticky_spreads_threads() {
+    let pool = ShardedPool::new(64, 1, SimpleAllocator).with_selector(Sticky);
+    std::thread::scope(|s| {
+        for _ in 0..2 {
+            s.spawn(|| drop(pool.get()));
+        }
+    });
+    // each new thread took the next shard, so two shards hold an object
+    assert_eq!(
+        pool.shards()
+            .iter()
+            .filter(|shard| shard.len() == 1)
+            .count(),
+        2
+    );
+}
+
+#[test]
+fn test_random_and_custom_selectors() {
+    let pool = ShardedPool::new(3, 8, SimpleAllocator).with_selector(RandomShard);
+    for _ in 0..32 {
+        drop(pool.get());
+    }
+    assert!(pool.len() <= 3);
+
+    struct Last(AtomicUsize);
+    impl ShardSelector for Last {
+        fn select(&self, shards: usize) -> usize {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            shards - 1
+        }
+    }
+    let shards = (0..2).map(|_| Pool::new(1, SimpleAllocator)).collect();
+    let pool = ShardedPool::from_shards(shards, Last(AtomicUsize::new(0)));
+    drop(pool.get());
+    assert_eq!(pool.shards()[1].len(), 1);
+    assert_eq!(pool.selector().0.load(Ordering::Relaxed), 1);
+    assert_eq!(ObjectPool::len(&pool), 1);
+}
@@ -0,0 +1,22 @@
+use opool::*;
+
+#[test]
+fn test_clone_prototype() {
+    let pool = Pool::new(4, TemplateAllocator::new(vec![1u8, 2, 3]));
+    let mut obj = pool.get();
+    assert_eq!(*obj, [1, 2, 3]);
+    obj.push(4);
+    drop(obj);
+    assert_eq!(*pool.get(), [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_reset_to_prototype() {
+    let allocator = TemplateAllocator::new(String::from("preset")).with_reset();
+    assert_eq!(allocator.prototype(), "preset");
+    let pool = LocalPool::new(4, allocator);
+    let mut obj = pool.get();
+    obj.push_str(" changed");
+    drop(obj);
+    assert_eq!(*pool.get(), "preset");
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "test-util")]
+use opool::*;
+
+#[test]
+fn test_scripted_allocator() {
+    let allocator = ScriptedAllocator::new(|| 0usize);
+    let script = allocator.clone();
+    allocator.push_allocation(7);
+    allocator.push_validation(false);
+    let pool = Pool::new(4, allocator);
+    let obj = pool.get();
+    assert_eq!(*obj, 7);
+    drop(obj);
+    assert!(pool.is_empty());
+    drop(pool.get());
+    drop(pool.get());
+    assert_eq!(
+        script.calls(),
+        vec![
+            AllocatorCall::Allocate,
+            AllocatorCall::Validate { valid: false },
+            AllocatorCall::Allocate,
+            AllocatorCall::Validate { valid: true },
+            AllocatorCall::Reset,
+            AllocatorCall::Validate { valid: true },
+        ]
+    );
+}
+
+#[test]
+fn test_scripted_allocation_failure() {
+    let allocator = ScriptedAllocator::new(|| 0usize);
+    allocator.push_allocation_failure();
+    let pool = Pool::new(4, allocator);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        drop(pool.get());
+    }));
+    assert!(result.is_err());
+    assert_eq!(*pool.get(), 0);
+}
+
+#[test]
+fn test_mock_pool() {
+    let allocator = ScriptedAllocator::new(|| 0usize);
+    allocator.push_validation(true);
+    allocator.push_validation(false);
+    let pool = MockPool::new(allocator);
+    pool.push_idle(5);
+    let (mut a, b, c) = (pool.get(), pool.get(), pool.get());
+    assert_eq!((*a, *b, *c), (5, 0, 0));
+    *a = 1;
+    assert_eq!(pool.record().outstanding(), 3);
+    drop((a, b));
+    assert_eq!(
+        pool.record(),
+        MockPoolRecord {
+            gets: 3,
+            allocations: 2,
+            returns: 1,
+            discards: 1,
+            reset_failures: 0,
+        }
+    );
+    assert_eq!(pool.drain_idle(), vec![1]);
+    drop(c);
+    assert_eq!(pool.len(), 1);
+}
+
+struct FlakyReset;
+
+impl PoolAllocator<usize> for FlakyReset {
+    fn allocate(&self) -> usize {
+        0
+    }
+
+    fn try_reset(&self, obj: &mut usize) -> bool {
+        *obj != 1
+    }
+}
+
+#[test]
+fn test_mock_pool_reset_failure() {
+    let pool = MockPool::new(FlakyReset);
+    pool.push_idle(1);
+    let obj = pool.get();
+    assert_eq!(*obj, 0);
+    let record = pool.record();
+    assert_eq!(record.reset_failures, 1);
+    assert_eq!(record.discards, 0);
+    assert_eq!(record.outstanding(), 1);
+    drop(obj);
+    assert_eq!(pool.record().outstanding(), 0);
+}
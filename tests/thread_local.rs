@@ -1,5 +1,6 @@
 use opool::*;
 
+#[derive(Debug, Clone)]
 struct SimpleAllocator;
 
 impl PoolAllocator<Box<usize>> for SimpleAllocator {
@@ -34,9 +35,484 @@ fn test_get_rc() {
     assert_eq!(**guard, 10);
 }
 
+#[test]
+fn test_get_owned() {
+    let pool = LocalPool::new_prefilled(10, SimpleAllocator).to_rc();
+    let a = pool.get_owned();
+    let b = pool.get_owned();
+    assert_eq!(**a, 10);
+    assert_eq!(**b, 10);
+    assert_eq!(std::rc::Rc::strong_count(&pool), 3);
+    drop((a, b));
+    assert_eq!(std::rc::Rc::strong_count(&pool), 1);
+    assert_eq!(pool.len(), 10);
+}
+
+#[test]
+fn test_leak() {
+    struct Holder {
+        guards: Vec<RefLocalGuard<'static, SimpleAllocator, Box<usize>>>,
+    }
+    let pool = LocalPool::new_prefilled(2, SimpleAllocator).leak();
+    let mut holder = Holder { guards: Vec::new() };
+    holder.guards.push(pool.get());
+    holder.guards.push(pool.get());
+    assert!(pool.is_empty());
+    drop(holder);
+    assert_eq!(pool.len(), 2);
+}
+
 #[test]
 fn test_get_rc_into_inner() {
     let pool = LocalPool::new_prefilled(10, SimpleAllocator).to_rc();
     let guard = pool.clone().get_rc().into_inner();
     assert_eq!(*guard, 10);
 }
+
+#[test]
+fn test_peek_idle() {
+    let pool = LocalPool::new(10, SimpleAllocator);
+    let (mut a, mut b) = (pool.get(), pool.get());
+    **a = 1;
+    **b = 2;
+    drop((a, b));
+    assert_eq!(pool.peek_idle(1), vec![Box::new(1)]);
+    let idle: Vec<_> = pool.iter_idle().map(|obj| *obj).collect();
+    assert_eq!(idle, vec![1, 2]);
+    assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn test_get_rc_leak() {
+    let pool = LocalPool::new(10, SimpleAllocator).to_rc();
+    let obj: &'static mut Box<usize> = pool.clone().get_rc().leak();
+    **obj = 20;
+    assert_eq!(**obj, 20);
+    assert_eq!(pool.len(), 0);
+    assert_eq!(pool.stats().live, 0);
+    assert_eq!(std::rc::Rc::strong_count(&pool), 1);
+}
+
+#[test]
+fn test_unbounded() {
+    let pool = LocalPool::new_unbounded(SimpleAllocator);
+    assert_eq!(pool.cap(), usize::MAX);
+    drop(pool.get_many(1000));
+    assert_eq!(pool.len(), 1000);
+    assert_eq!(pool.stats().dropped_full, 0);
+}
+
+#[test]
+fn test_zero_capacity() {
+    let pool = LocalPool::new_prefilled(0, SimpleAllocator);
+    assert_eq!(pool.cap(), 0);
+    let mut obj = pool.get();
+    **obj = 20;
+    drop(obj);
+    assert!(pool.is_empty());
+    assert_eq!(**pool.get(), 10);
+}
+
+#[test]
+fn test_try_get_n() {
+    let pool = LocalPool::new_prefilled(3, SimpleAllocator);
+    assert!(pool.try_get_n(4).is_none());
+    assert_eq!(pool.len(), 3);
+    let guards = pool.try_get_n(2).unwrap();
+    assert_eq!(guards.len(), 2);
+    assert_eq!(pool.len(), 1);
+    drop(guards);
+    assert_eq!(pool.len(), 3);
+}
+
+#[test]
+fn test_get_array() {
+    let pool = LocalPool::new_prefilled(3, SimpleAllocator);
+    assert!(pool.get_array::<4>().is_none());
+    assert_eq!(pool.len(), 3);
+    let [mut front, back] = pool.get_array().unwrap();
+    **front = 20;
+    assert_eq!((**front, **back), (20, 10));
+    assert_eq!(pool.len(), 1);
+    drop((front, back));
+    assert_eq!(pool.len(), 3);
+    assert_eq!(pool.stats().allocated, 3);
+}
+
+#[test]
+fn test_get_many() {
+    let pool = LocalPool::new(4, SimpleAllocator);
+    let mut objs = pool.get_many(6);
+    assert_eq!(objs.len(), 6);
+    for (i, obj) in objs.iter_mut().enumerate() {
+        **obj = i;
+    }
+    drop(objs.pop());
+    objs.push(pool.get());
+    assert_eq!(objs.len(), 6);
+    drop(objs);
+    assert_eq!(pool.len(), 4);
+}
+
+#[test]
+fn test_lazy_storage() {
+    let pool = LocalPool::new(1 << 40, SimpleAllocator);
+    assert_eq!(pool.cap(), 1 << 40);
+    drop(pool.get());
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn test_into_shared() {
+    let pool = LocalPool::new_prefilled(4, SimpleAllocator);
+    let mut obj = pool.get();
+    **obj = 20;
+    drop(obj);
+    let pool = pool.into_shared();
+    assert_eq!(pool.len(), 4);
+    assert_eq!(pool.cap(), 4);
+    let handle = std::thread::spawn({
+        let pool = pool.clone();
+        move || **pool.get_rc()
+    });
+    assert_eq!(handle.join().unwrap(), 10);
+}
+
+#[test]
+fn test_duplicate() {
+    let pool = LocalPool::new_prefilled(4, SimpleAllocator);
+    let mut obj = pool.get();
+    **obj = 20;
+    drop(obj);
+    let copy = pool.duplicate();
+    assert_eq!(copy.cap(), 4);
+    assert_eq!(
+        copy.iter_idle().collect::<Vec<_>>(),
+        pool.iter_idle().collect::<Vec<_>>()
+    );
+    **copy.get() = 30;
+    assert!(pool.iter_idle().all(|obj| *obj != 30));
+}
+
+#[test]
+fn test_stats() {
+    let pool = LocalPool::new(4, SimpleAllocator);
+    let objs = pool.get_many(5);
+    let kept = pool.get().into_inner();
+    let stats = pool.stats();
+    assert_eq!(
+        stats,
+        PoolStats {
+            name: None,
+            allocated: 6,
+            hits: 0,
+            misses: 6,
+            returns: 0,
+            dropped_invalid: 0,
+            dropped_full: 0,
+            live: 5,
+            idle: 0,
+            capacity: 4,
+        }
+    );
+    assert_eq!(stats.in_use(), 5);
+    assert_eq!(pool.utilization(), 1.0);
+    drop(objs);
+    assert_eq!(pool.stats().live, 4);
+    let obj = pool.get();
+    assert_eq!(pool.utilization(), 0.25);
+    assert_eq!(pool.fill_ratio(), 0.75);
+    assert_eq!(
+        pool.stats().to_string(),
+        "in use 1/4 (25.0%), idle 3/4 (75.0%), allocated 6"
+    );
+    drop((obj, kept));
+}
+
+#[test]
+fn test_shed_threshold() {
+    let pool = LocalPool::new(4, SimpleAllocator).with_shed_threshold(2);
+    let a = pool.try_get().unwrap();
+    let b = pool.try_get().unwrap();
+    assert_eq!(pool.try_get().unwrap_err(), PoolError::Overloaded);
+    let c = pool.get();
+    drop(a);
+    assert!(pool.try_get().is_err());
+    drop((b, c));
+    let pool = pool.to_rc();
+    let c = pool.clone().try_get_rc().unwrap();
+    let d = pool.clone().try_get_rc().unwrap();
+    assert!(pool.clone().try_get_rc().is_err());
+    drop((c, d));
+}
+
+#[test]
+fn test_reset_stats() {
+    let pool = LocalPool::new(4, SimpleAllocator);
+    drop(pool.get());
+    let obj = pool.get();
+    assert_eq!((pool.stats().hits, pool.stats().misses), (1, 1));
+    assert_eq!(pool.stats().miss_rate(), 0.5);
+    pool.reset_stats();
+    let stats = pool.stats();
+    assert_eq!((stats.allocated, stats.hits, stats.misses), (0, 0, 0));
+    assert_eq!((stats.live, stats.in_use()), (1, 1));
+    drop(obj);
+}
+
+#[test]
+fn test_name() {
+    let pool = LocalPool::new(4, SimpleAllocator).with_name("buffers");
+    assert_eq!(pool.name(), Some("buffers"));
+    assert_eq!(pool.stats().name, Some("buffers"));
+    assert!(pool.stats().to_string().starts_with("buffers: in use 0/0"));
+    assert!(format!("{pool:?}").contains("\"buffers\""));
+}
+
+#[test]
+fn test_with() {
+    let pool = LocalPool::new(4, SimpleAllocator).with_shed_threshold(1);
+    assert_eq!(pool.with(|obj| **obj), 10);
+    assert_eq!(pool.len(), 1);
+    let held = pool.get();
+    assert_eq!(pool.try_with(|obj| **obj), Err(PoolError::Overloaded));
+    drop(held);
+    assert_eq!(pool.try_with(|obj| **obj + 1), Ok(11));
+    let panicked = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        pool.with(|_| panic!("closure panicked"));
+    }));
+    assert!(panicked.is_err());
+    assert_eq!(pool.len(), 0);
+    assert_eq!(pool.stats().live, 0);
+}
+
+#[tokio::test]
+async fn test_with_async() {
+    let pool = LocalPool::new(4, SimpleAllocator);
+    let value = pool
+        .with_async(async |obj| {
+            tokio::task::yield_now().await;
+            **obj + 1
+        })
+        .await;
+    assert_eq!(value, 11);
+    assert_eq!(pool.len(), 1);
+    let pending = pool.with_async(async |_| std::future::pending::<()>().await);
+    let timed_out = tokio::time::timeout(std::time::Duration::from_millis(10), pending).await;
+    assert!(timed_out.is_err());
+    assert_eq!(pool.len(), 1);
+    assert_eq!(pool.stats().in_use(), 0);
+}
+
+#[test]
+fn test_lazy_prefill() {
+    let pool = LocalPool::new(8, SimpleAllocator).with_lazy_prefill(6, 4);
+    assert_eq!(pool.len(), 0);
+    assert_eq!(pool.stats().allocated, 0);
+    let a = pool.get();
+    assert_eq!((pool.len(), pool.stats().allocated), (4, 5));
+    let objs: Vec<_> = (0..4).map(|_| pool.get()).collect();
+    assert_eq!(pool.stats().misses, 1);
+    let b = pool.get();
+    assert_eq!((pool.len(), pool.stats().allocated), (2, 8));
+    drop((a, b, objs));
+    drop(pool.get_many(4));
+    assert_eq!(pool.stats().allocated, 8);
+    assert_eq!(pool.len(), 8);
+}
+
+#[test]
+fn test_manual_guard() {
+    let pool = LocalPool::new(4, SimpleAllocator);
+    let mut obj = pool.get_manual();
+    **obj += 1;
+    pool.put_back(obj);
+    assert_eq!(pool.len(), 1);
+    let obj = pool.get_manual();
+    assert_eq!(**obj, 11);
+    pool.put_back(obj);
+    assert_eq!(pool.stats().in_use(), 0);
+}
+
+#[test]
+fn test_return_strict() {
+    struct OddAllocator;
+    impl PoolAllocator<usize> for OddAllocator {
+        fn allocate(&self) -> usize {
+            1
+        }
+
+        fn is_valid(&self, obj: &usize) -> bool {
+            obj % 2 == 1
+        }
+    }
+    let pool = LocalPool::new(1, OddAllocator);
+    let (a, mut b) = (pool.get(), pool.get());
+    *b += 1;
+    assert_eq!(b.return_strict(), ReturnStatus::DroppedInvalid);
+    assert_eq!(a.return_strict(), ReturnStatus::Returned);
+    let pool = pool.to_rc();
+    let held = pool.clone().get_rc();
+    assert_eq!(
+        pool.clone().get_rc().return_strict(),
+        ReturnStatus::Returned
+    );
+    assert_eq!(held.return_strict(), ReturnStatus::DroppedFull);
+    assert_eq!(pool.stats().live, 1);
+}
+
+#[test]
+fn test_quarantine() {
+    struct OddAllocator;
+    impl PoolAllocator<usize> for OddAllocator {
+        fn allocate(&self) -> usize {
+            1
+        }
+
+        fn is_valid(&self, obj: &usize) -> bool {
+            obj % 2 == 1
+        }
+    }
+    let pool = LocalPool::new(4, OddAllocator).with_quarantine(1);
+    let (mut a, mut b) = (pool.get(), pool.get());
+    *a = 2;
+    *b = 4;
+    assert_eq!(a.return_strict(), ReturnStatus::Quarantined);
+    assert_eq!(b.return_strict(), ReturnStatus::DroppedInvalid);
+    assert_eq!(pool.take_quarantined(), vec![2]);
+    assert!(pool.take_quarantined().is_empty());
+    assert_eq!(pool.stats().live, 0);
+}
+
+#[test]
+fn test_hot_and_cold() {
+    let pool = LocalPool::new(4, SimpleAllocator);
+    let (mut a, mut b) = (pool.get(), pool.get());
+    **a = 1;
+    **b = 2;
+    drop((a, b));
+    assert_eq!(**pool.get_hot(), 2);
+    assert_eq!(**pool.get_cold(), 1);
+}
+
+#[test]
+fn test_regenerate() {
+    let pool = LocalPool::new(2, SimpleAllocator);
+    let mut guard = pool.get();
+    **guard = 1;
+    guard.regenerate();
+    assert_eq!(**guard, 10);
+    assert_eq!(pool.stats().misses, 2);
+    assert_eq!(pool.stats().live, 1);
+    drop(guard);
+    let mut guard = pool.to_rc().get_rc();
+    guard.regenerate();
+    assert_eq!(**guard, 10);
+}
+
+#[test]
+fn test_put() {
+    let pool = LocalPool::new(1, SimpleAllocator);
+    assert_eq!(pool.put(Box::new(5)), Ok(()));
+    assert_eq!(pool.put(Box::new(6)), Err(Box::new(6)));
+    assert_eq!(pool.stats().live, 1);
+    assert_eq!(**pool.get(), 5);
+}
+
+#[test]
+fn test_put_reentrant() {
+    // An allocator that fills the pool while an object is validated.
+    struct Filler(std::rc::Weak<LocalPool<Filler, usize>>);
+    impl PoolAllocator<usize> for Filler {
+        fn allocate(&self) -> usize {
+            0
+        }
+        fn is_valid(&self, obj: &usize) -> bool {
+            if *obj == 1 {
+                let _ = self.0.upgrade().unwrap().put(2);
+            }
+            true
+        }
+    }
+    let pool = std::rc::Rc::new_cyclic(|pool| LocalPool::new(1, Filler(pool.clone())));
+    assert_eq!(pool.put(1), Err(1));
+    assert_eq!(pool.len(), 1);
+    assert_eq!(*pool.get(), 2);
+}
+
+#[test]
+fn test_prefill() {
+    let pool = LocalPool::new(4, SimpleAllocator);
+    assert_eq!(pool.prefill(3), 3);
+    assert_eq!(pool.prefill(2), 0);
+    assert_eq!(pool.prefill(10), 1);
+    assert_eq!(pool.stats().allocated, 4);
+}
+
+#[test]
+fn test_resize() {
+    let pool = LocalPool::new_prefilled(4, SimpleAllocator);
+    let guard = pool.get();
+    pool.resize(2);
+    assert_eq!((pool.len(), pool.cap()), (2, 2));
+    drop(guard);
+    assert_eq!(pool.len(), 2);
+    pool.resize(5);
+    assert_eq!(pool.prefill(5), 3);
+    assert_eq!(pool.stats().live, 5);
+}
+
+#[test]
+fn test_get_or_fail() {
+    let pool = LocalPool::new_prefilled(1, SimpleAllocator);
+    let obj = pool.get_or_fail().unwrap();
+    assert_eq!(**obj, 10);
+    assert_eq!(pool.get_or_fail().unwrap_err(), PoolError::Empty);
+    drop(obj);
+    assert!(pool.get_or_fail().is_ok());
+    assert_eq!(pool.stats().allocated, 1);
+}
+
+#[test]
+fn test_outstanding() {
+    let pool = LocalPool::new_prefilled(2, SimpleAllocator);
+    assert_eq!(pool.outstanding(), 0);
+    let a = pool.get();
+    let b = pool.get();
+    let c = pool.get();
+    assert_eq!(pool.outstanding(), 3);
+    drop(a);
+    let _ = c.into_inner();
+    assert_eq!(pool.outstanding(), 1);
+    drop(b);
+    assert_eq!(pool.outstanding(), 0);
+    assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn test_into_shared_keeps_settings() {
+    struct ResetAllocator;
+    impl PoolAllocator<usize> for ResetAllocator {
+        fn allocate(&self) -> usize {
+            0
+        }
+        fn reset(&self, obj: &mut usize) {
+            *obj = 0;
+        }
+    }
+    let pool = LocalPool::new(4, ResetAllocator)
+        .with_name("local")
+        .with_shed_threshold(1)
+        .with_reset_policy(ResetPolicy::Deferred)
+        .with_lazy_prefill(3, 3);
+    let pool = pool.into_shared();
+    assert_eq!(pool.name(), Some("local"));
+    let mut obj = pool.try_get().unwrap();
+    assert_eq!(pool.len(), 3);
+    assert_eq!(pool.try_get().unwrap_err(), PoolError::Overloaded);
+    *obj = 20;
+    drop(obj);
+    // the deferred reset policy carried over, so the object was not reset
+    let objs: Vec<_> = (0..4).map(|_| pool.get()).collect();
+    assert!(objs.iter().any(|obj| **obj == 20));
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "tracing")]
+use opool::*;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<String>>>);
+
+struct Fields(Vec<String>);
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.push(format!("{}={value}", field.name()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.push(format!("{}={value:?}", field.name()));
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(vec![event.metadata().target().to_string()]);
+        event.record(&mut fields);
+        self.0.lock().unwrap().push(fields.0.join(" "));
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+struct SimpleAllocator;
+
+impl PoolAllocator<usize> for SimpleAllocator {
+    fn allocate(&self) -> usize {
+        0
+    }
+}
+
+#[test]
+fn test_trace_events() {
+    let capture = Capture::default();
+    tracing::subscriber::with_default(capture.clone(), || {
+        let pool = Pool::new(1, SimpleAllocator).with_name("numbers");
+        let (a, b) = (pool.get(), pool.get());
+        drop((a, b));
+        drop(pool.get());
+        LocalPool::new(1, SimpleAllocator).put(1).unwrap();
+    });
+    assert_eq!(
+        *capture.0.lock().unwrap(),
+        [
+            "opool pool=numbers event=allocate",
+            "opool pool=numbers event=checkout",
+            "opool pool=numbers event=allocate",
+            "opool pool=numbers event=checkout",
+            "opool pool=numbers event=return",
+            "opool pool=numbers event=discard",
+            "opool pool=numbers event=checkout",
+            "opool pool=numbers event=return",
+            "opool pool=usize event=return",
+            "opool pool=usize event=discard",
+            "opool pool=numbers event=discard",
+        ]
+    );
+}
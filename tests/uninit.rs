@@ -0,0 +1,38 @@
+use opool::*;
+use std::{mem::MaybeUninit, rc::Rc};
+
+#[test]
+fn test_write() {
+    let pool = Pool::new(2, UninitAllocator::<[u8; 4096]>::new());
+    let mut buf = pool.get_uninit().write([1; 4096]);
+    buf[0] = 2;
+    assert_eq!(buf[..2], [2, 1]);
+    drop(buf);
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn test_assume_init() {
+    let pool = LocalPool::new(2, UninitAllocator::<[u64; 512]>::new());
+    let mut slot = pool.get_uninit();
+    let ptr = slot.as_mut_ptr() as *mut u64;
+    for i in 0..512 {
+        unsafe { ptr.add(i).write(i as u64) };
+    }
+    let buf = unsafe { slot.assume_init() };
+    assert_eq!(buf.iter().sum::<u64>(), 511 * 512 / 2);
+}
+
+#[test]
+fn test_value_dropped_before_recycling() {
+    let value = Rc::new(());
+    let pool = Pool::new(2, UninitAllocator::<Rc<()>>::new()).to_rc();
+    let guard = pool.clone().get_rc().write(value.clone());
+    assert_eq!(Rc::strong_count(&value), 2);
+    let slot: RcGuard<_, Box<MaybeUninit<Rc<()>>>> = guard.uninit();
+    assert_eq!(Rc::strong_count(&value), 1);
+    drop(slot);
+    drop(pool.clone().get_rc().write(value.clone()));
+    assert_eq!(Rc::strong_count(&value), 1);
+    assert_eq!(pool.len(), 1);
+}
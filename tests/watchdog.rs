@@ -0,0 +1,56 @@
+#![cfg(feature = "std")]
+use opool::*;
+use std::{
+    sync::{Arc, Mutex},
+    thread::sleep,
+    time::Duration,
+};
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_hold_watchdog() {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let sink = reports.clone();
+    let watchdog = HoldWatchdog::new(Duration::from_millis(20), move |held: &LongHeld| {
+        sink.lock().unwrap().push(*held);
+    });
+    let pool = Pool::new(4, SimpleAllocator).with_hold_watchdog(watchdog);
+    let forever = pool.get_watched();
+    let line = line!() - 1;
+    drop(pool.get_watched());
+    assert!(pool.long_held().is_empty());
+    sleep(Duration::from_millis(30));
+    let quick = pool.get_watched();
+    assert_eq!(**quick, 10);
+    let reports = reports.lock().unwrap().clone();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].location.line(), line);
+    assert!(reports[0].held >= Duration::from_millis(20));
+    let held = pool.long_held();
+    assert_eq!(held.len(), 1);
+    assert_eq!(held[0].location, reports[0].location);
+    drop(forever);
+    assert!(pool.long_held().is_empty());
+}
+
+#[test]
+fn test_local_hold_watchdog() {
+    let watchdog = HoldWatchdog::new(Duration::ZERO, |_: &LongHeld| {});
+    let pool = LocalPool::new(4, SimpleAllocator).with_hold_watchdog(watchdog);
+    let guard = pool.get_watched();
+    sleep(Duration::from_millis(1));
+    assert_eq!(pool.long_held().len(), 1);
+    drop(guard);
+    assert!(pool.long_held().is_empty());
+    assert_eq!(pool.len(), 1);
+    let pool = LocalPool::new(4, SimpleAllocator);
+    assert_eq!(**pool.get_watched(), 10);
+    assert!(pool.long_held().is_empty());
+}
@@ -0,0 +1,57 @@
+use opool::*;
+use std::rc::Rc;
+use std::sync::Arc;
+
+struct SimpleAllocator;
+
+impl PoolAllocator<Box<usize>> for SimpleAllocator {
+    fn allocate(&self) -> Box<usize> {
+        Box::new(10)
+    }
+}
+
+#[test]
+fn test_weak_guard_returns_while_alive() {
+    let pool = Pool::new(10, SimpleAllocator).to_rc();
+    let weak = pool.downgrade();
+    let guard = weak.get().unwrap();
+    assert_eq!(**guard, 10);
+    assert_eq!(Arc::strong_count(&pool), 1);
+    assert!(guard.pool().is_some());
+    drop(guard);
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn test_weak_guard_outlives_pool() {
+    let pool = Pool::new(10, SimpleAllocator).to_rc();
+    let weak = pool.downgrade();
+    let mut guard = pool.get_weak();
+    drop(pool);
+    assert!(weak.upgrade().is_none());
+    assert!(weak.get().is_none());
+    assert!(guard.pool().is_none());
+    **guard = 20;
+    assert_eq!(**guard, 20);
+}
+
+#[test]
+fn test_weak_pool_never_upgrades() {
+    let weak = WeakPool::<SimpleAllocator, Box<usize>>::new();
+    assert!(weak.get().is_none());
+    let weak = WeakLocalPool::<SimpleAllocator, Box<usize>>::default();
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn test_weak_local_guard() {
+    let pool = LocalPool::new(10, SimpleAllocator).to_rc();
+    let weak = pool.downgrade();
+    drop(weak.get().unwrap());
+    assert_eq!(pool.len(), 1);
+    let guard = pool.get_weak();
+    assert_eq!(Rc::strong_count(&pool), 1);
+    drop(pool);
+    assert!(weak.upgrade().is_none());
+    assert_eq!(**guard, 10);
+}
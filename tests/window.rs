@@ -0,0 +1,38 @@
+#![cfg(feature = "std")]
+use opool::*;
+use std::time::{Duration, Instant};
+
+fn sample(hits: usize, misses: usize) -> PoolStats {
+    PoolStats {
+        hits,
+        misses,
+        ..PoolStats::default()
+    }
+}
+
+#[test]
+fn test_rolling_window() {
+    let start = Instant::now();
+    let at = |secs| start + Duration::from_secs(secs);
+    let mut window = StatsWindow::new(Duration::from_secs(10));
+    assert_eq!(window.miss_rate(), 0.0);
+    window.record_at(at(0), sample(0, 0));
+    window.record_at(at(5), sample(90, 10));
+    window.record_at(at(10), sample(100, 100));
+    assert_eq!((window.hits(), window.misses()), (100, 100));
+    assert_eq!(window.elapsed(), Duration::from_secs(10));
+    window.record_at(at(15), sample(100, 100));
+    assert_eq!((window.hits(), window.misses()), (10, 90));
+    assert_eq!(window.miss_rate(), 0.9);
+}
+
+#[test]
+fn test_window_restarts_after_reset() {
+    let start = Instant::now();
+    let mut window = StatsWindow::new(Duration::from_secs(10));
+    window.record_at(start, sample(50, 50));
+    window.record_at(start + Duration::from_secs(1), sample(2, 0));
+    assert_eq!((window.hits(), window.misses()), (0, 0));
+    window.record_at(start + Duration::from_secs(2), sample(5, 1));
+    assert_eq!((window.hits(), window.misses()), (3, 1));
+}